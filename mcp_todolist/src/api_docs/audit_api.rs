@@ -0,0 +1,22 @@
+use utoipa::OpenApi;
+
+use crate::domain::dto::audit_dto::{ChangeOrigin, ChangeSource, ResTaskAuditDto};
+
+
+
+#[derive(OpenApi)]
+#[openapi(
+    security(),
+    modifiers(),
+    paths(
+        crate::infrastructure::http_handler::audit_handler::get_history,
+    ),
+    components(
+        schemas(
+            ResTaskAuditDto,
+            ChangeOrigin,
+            ChangeSource
+        )
+    )
+)]
+pub struct AuditApi;
\ No newline at end of file
@@ -0,0 +1,24 @@
+use utoipa::OpenApi;
+
+use crate::domain::dto::batch_dto::{BatchItemStatus, BatchOperation, ReqBatchTodoDto, ResBatchItemDto, ResBatchResultDto};
+
+
+
+#[derive(OpenApi)]
+#[openapi(
+    security(),
+    modifiers(),
+    paths(
+        crate::infrastructure::http_handler::batch_handler::run_batch,
+    ),
+    components(
+        schemas(
+            ReqBatchTodoDto,
+            ResBatchResultDto,
+            ResBatchItemDto,
+            BatchOperation,
+            BatchItemStatus
+        )
+    )
+)]
+pub struct BatchApi;
\ No newline at end of file
@@ -0,0 +1,22 @@
+use utoipa::OpenApi;
+
+use crate::domain::dto::event_dto::{ChangeEntity, ChangeKind, ResChangeEventDto};
+
+
+
+#[derive(OpenApi)]
+#[openapi(
+    security(),
+    modifiers(),
+    paths(
+        crate::infrastructure::http_handler::event_handler::change_events,
+    ),
+    components(
+        schemas(
+            ResChangeEventDto,
+            ChangeKind,
+            ChangeEntity
+        )
+    )
+)]
+pub struct EventApi;
\ No newline at end of file
@@ -2,13 +2,25 @@ use utoipa::OpenApi;
 
 use crate::configuration::api_doc_config::ApiDoc;
 
-use super::todolist_api::TodolistApi;
+use super::{audit_api::AuditApi, batch_api::BatchApi, event_api::EventApi, list_api::ListApi, recurrence_api::RecurrenceApi, search_api::SearchApi, subtask_api::SubtaskApi, tag_api::TagApi, todolist_api::TodolistApi, transfer_api::TransferApi, trash_api::TrashApi, undo_api::UndoApi, webhook_api::WebhookApi};
 
 
 
 pub fn init_openapi() -> utoipa::openapi::OpenApi {
     let register: Vec<utoipa::openapi::OpenApi> = vec![
         TodolistApi::openapi(),
+        TagApi::openapi(),
+        ListApi::openapi(),
+        SubtaskApi::openapi(),
+        EventApi::openapi(),
+        BatchApi::openapi(),
+        TrashApi::openapi(),
+        AuditApi::openapi(),
+        UndoApi::openapi(),
+        SearchApi::openapi(),
+        RecurrenceApi::openapi(),
+        WebhookApi::openapi(),
+        TransferApi::openapi(),
         ApiDoc::openapi()
     ];
 
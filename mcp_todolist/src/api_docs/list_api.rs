@@ -0,0 +1,29 @@
+use utoipa::OpenApi;
+
+use crate::domain::dto::list_dto::{ReqCreateListDto, ReqUpdateListDto, ResEntryListDto, ResListCountDto};
+
+
+
+#[derive(OpenApi)]
+#[openapi(
+    security(),
+    modifiers(),
+    paths(
+        crate::infrastructure::http_handler::list_handler::create_list,
+        crate::infrastructure::http_handler::list_handler::get_all_lists,
+        crate::infrastructure::http_handler::list_handler::get_list_by_id,
+        crate::infrastructure::http_handler::list_handler::update_list,
+        crate::infrastructure::http_handler::list_handler::delete_list,
+        crate::infrastructure::http_handler::list_handler::get_tasks_in_list,
+        crate::infrastructure::http_handler::list_handler::count_tasks_in_list,
+    ),
+    components(
+        schemas(
+            ResEntryListDto,
+            ReqCreateListDto,
+            ReqUpdateListDto,
+            ResListCountDto
+        )
+    )
+)]
+pub struct ListApi;
\ No newline at end of file
@@ -1,2 +1,14 @@
 pub mod init_open_api;
-pub mod todolist_api;
\ No newline at end of file
+pub mod todolist_api;
+pub mod tag_api;
+pub mod list_api;
+pub mod subtask_api;
+pub mod event_api;
+pub mod batch_api;
+pub mod trash_api;
+pub mod audit_api;
+pub mod undo_api;
+pub mod search_api;
+pub mod recurrence_api;
+pub mod webhook_api;
+pub mod transfer_api;
\ No newline at end of file
@@ -0,0 +1,25 @@
+use utoipa::OpenApi;
+
+use crate::domain::dto::recurrence_dto::{RecurrenceFrequency, ReqCreateRecurringTaskDto, ReqRecurrenceRuleDto, ResRecurrenceDto};
+
+
+
+#[derive(OpenApi)]
+#[openapi(
+    security(),
+    modifiers(),
+    paths(
+        crate::infrastructure::http_handler::recurrence_handler::create_recurrence,
+        crate::infrastructure::http_handler::recurrence_handler::get_recurrence,
+        crate::infrastructure::http_handler::recurrence_handler::stop_recurrence,
+    ),
+    components(
+        schemas(
+            RecurrenceFrequency,
+            ReqRecurrenceRuleDto,
+            ReqCreateRecurringTaskDto,
+            ResRecurrenceDto
+        )
+    )
+)]
+pub struct RecurrenceApi;
\ No newline at end of file
@@ -0,0 +1,21 @@
+use utoipa::OpenApi;
+
+use crate::domain::dto::search_dto::{ReqSearchTodoDto, ResSearchHitDto};
+
+
+
+#[derive(OpenApi)]
+#[openapi(
+    security(),
+    modifiers(),
+    paths(
+        crate::infrastructure::http_handler::search_handler::search_todo,
+    ),
+    components(
+        schemas(
+            ReqSearchTodoDto,
+            ResSearchHitDto
+        )
+    )
+)]
+pub struct SearchApi;
\ No newline at end of file
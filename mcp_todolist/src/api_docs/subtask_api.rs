@@ -0,0 +1,25 @@
+use utoipa::OpenApi;
+
+use crate::domain::dto::subtask_dto::{ReqCreateSubtaskDto, ReqReorderSubtasksDto, ReqTickSubtaskDto};
+
+
+
+#[derive(OpenApi)]
+#[openapi(
+    security(),
+    modifiers(),
+    paths(
+        crate::infrastructure::http_handler::subtask_handler::add_subtask,
+        crate::infrastructure::http_handler::subtask_handler::get_subtasks,
+        crate::infrastructure::http_handler::subtask_handler::reorder_subtasks,
+        crate::infrastructure::http_handler::subtask_handler::tick_subtask,
+    ),
+    components(
+        schemas(
+            ReqCreateSubtaskDto,
+            ReqReorderSubtasksDto,
+            ReqTickSubtaskDto
+        )
+    )
+)]
+pub struct SubtaskApi;
\ No newline at end of file
@@ -0,0 +1,28 @@
+use utoipa::OpenApi;
+
+use crate::domain::dto::tag_dto::{ReqCreateTagDto, ReqTagNamesDto, ReqUpdateTagDto, ResEntryTagDto};
+
+
+
+#[derive(OpenApi)]
+#[openapi(
+    security(),
+    modifiers(),
+    paths(
+        crate::infrastructure::http_handler::tag_handler::create_tag,
+        crate::infrastructure::http_handler::tag_handler::get_all_tags,
+        crate::infrastructure::http_handler::tag_handler::update_tag,
+        crate::infrastructure::http_handler::tag_handler::delete_tag,
+        crate::infrastructure::http_handler::tag_handler::attach_tags,
+        crate::infrastructure::http_handler::tag_handler::detach_tag,
+    ),
+    components(
+        schemas(
+            ResEntryTagDto,
+            ReqCreateTagDto,
+            ReqUpdateTagDto,
+            ReqTagNamesDto
+        )
+    )
+)]
+pub struct TagApi;
\ No newline at end of file
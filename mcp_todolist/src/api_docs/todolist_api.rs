@@ -1,6 +1,6 @@
 use utoipa::OpenApi;
 
-use crate::domain::dto::todo_dto::{ReqCreateTodoDto, ReqUpdateTodoDto, ResEntryTodoDto};
+use crate::domain::dto::{query_dto::{ResTodoPageDto, SortDirection, TodoSortField}, todo_dto::{ReqCreateTodoDto, ReqUpdateTodoDto, ResEntryTodoDto, TaskPriority}};
 
 
 
@@ -17,12 +17,19 @@ use crate::domain::dto::todo_dto::{ReqCreateTodoDto, ReqUpdateTodoDto, ResEntryT
         crate::infrastructure::http_handler::http_handler::count_all_task,
         crate::infrastructure::http_handler::http_handler::count_done_task,
         crate::infrastructure::http_handler::http_handler::count_undone_task,
+        crate::infrastructure::http_handler::http_handler::get_overdue,
+        crate::infrastructure::http_handler::http_handler::get_due_today,
+        crate::infrastructure::http_handler::http_handler::get_due_within,
     ),
     components(
         schemas(
             ResEntryTodoDto,
             ReqCreateTodoDto,
-            ReqUpdateTodoDto
+            ReqUpdateTodoDto,
+            ResTodoPageDto,
+            TodoSortField,
+            SortDirection,
+            TaskPriority
         )
     )
 )]
@@ -0,0 +1,28 @@
+use utoipa::OpenApi;
+
+use crate::domain::dto::transfer_dto::{ImportOutcome, ResImportEntryDto, ResImportReportDto, TransferDocumentDto, TransferFormat, TransferListDto, TransferTaskDto};
+
+
+
+#[derive(OpenApi)]
+#[openapi(
+    security(),
+    modifiers(),
+    paths(
+        crate::infrastructure::http_handler::transfer_handler::export_tasks,
+        crate::infrastructure::http_handler::transfer_handler::import_tasks,
+        crate::infrastructure::http_handler::transfer_handler::calendar_feed,
+    ),
+    components(
+        schemas(
+            TransferFormat,
+            TransferDocumentDto,
+            TransferListDto,
+            TransferTaskDto,
+            ImportOutcome,
+            ResImportEntryDto,
+            ResImportReportDto
+        )
+    )
+)]
+pub struct TransferApi;
\ No newline at end of file
@@ -0,0 +1,23 @@
+use utoipa::OpenApi;
+
+use crate::domain::dto::trash_dto::ResPurgeDto;
+
+
+
+#[derive(OpenApi)]
+#[openapi(
+    security(),
+    modifiers(),
+    paths(
+        crate::infrastructure::http_handler::trash_handler::get_trash,
+        crate::infrastructure::http_handler::trash_handler::restore_todo,
+        crate::infrastructure::http_handler::trash_handler::purge_todo,
+        crate::infrastructure::http_handler::trash_handler::empty_trash,
+    ),
+    components(
+        schemas(
+            ResPurgeDto
+        )
+    )
+)]
+pub struct TrashApi;
\ No newline at end of file
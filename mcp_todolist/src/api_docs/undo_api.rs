@@ -0,0 +1,22 @@
+use utoipa::OpenApi;
+
+use crate::domain::dto::undo_dto::{ReqUndoDto, ResUndoDto, ResUndoneChangeDto};
+
+
+
+#[derive(OpenApi)]
+#[openapi(
+    security(),
+    modifiers(),
+    paths(
+        crate::infrastructure::http_handler::undo_handler::undo_session,
+    ),
+    components(
+        schemas(
+            ReqUndoDto,
+            ResUndoDto,
+            ResUndoneChangeDto
+        )
+    )
+)]
+pub struct UndoApi;
\ No newline at end of file
@@ -0,0 +1,30 @@
+use utoipa::OpenApi;
+
+use crate::domain::dto::webhook_dto::{ReqCreateWebhookDto, ReqUpdateWebhookDto, ResWebhookDeliveryDto, ResWebhookDto, ResWebhookPayloadDto, WebhookDeliveryStatus};
+
+
+
+#[derive(OpenApi)]
+#[openapi(
+    security(),
+    modifiers(),
+    paths(
+        crate::infrastructure::http_handler::webhook_handler::create_webhook,
+        crate::infrastructure::http_handler::webhook_handler::get_all_webhooks,
+        crate::infrastructure::http_handler::webhook_handler::get_webhook_by_id,
+        crate::infrastructure::http_handler::webhook_handler::update_webhook,
+        crate::infrastructure::http_handler::webhook_handler::delete_webhook,
+        crate::infrastructure::http_handler::webhook_handler::get_webhook_deliveries,
+    ),
+    components(
+        schemas(
+            ReqCreateWebhookDto,
+            ReqUpdateWebhookDto,
+            ResWebhookDto,
+            ResWebhookPayloadDto,
+            WebhookDeliveryStatus,
+            ResWebhookDeliveryDto
+        )
+    )
+)]
+pub struct WebhookApi;
\ No newline at end of file
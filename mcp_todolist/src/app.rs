@@ -18,13 +18,13 @@ pub async fn rocket() -> Result<(), rocket::Error> {
         .init();
 
     // inject db connection, and Arc for assign accross multithread
-    let db_poll = conn(&config.database_url).expect("Failed to connect to database");
+    let db_poll = conn(&config.database_url, config.migration_mode).expect("Failed to connect to database");
     let db_pool_arc = Arc::new(db_poll);
 
     // inject use case
     let todo_use_case = {
         let todo_repo = TodoListSqliteRepository::new(Arc::clone(&db_pool_arc));
-        TodolistUseCase::new(Arc::new(todo_repo))
+        TodolistUseCase::new(Arc::new(todo_repo)).with_private_webhook_targets(config.webhook_allow_private_targets)
     };
 
     // initial web server
@@ -0,0 +1,124 @@
+use std::{collections::VecDeque, sync::Mutex};
+use chrono::Utc;
+use tokio::sync::broadcast;
+
+use crate::domain::dto::{event_dto::{ChangeEntity, ChangeKind, ResChangeEventDto}, todo_dto::ResEntryTodoDto};
+
+/// How many events a slow subscriber may fall behind before it starts missing them.
+const CHANNEL_CAPACITY: usize = 256;
+/// How many past events are kept for clients resuming a feed.
+const HISTORY_CAPACITY: usize = 1000;
+
+
+/// What a change touched besides the entity itself, used to work out which views went stale.
+#[derive(Debug, Clone, Default)]
+pub struct ChangeScope {
+    pub parent_id: Option<u32>,
+    pub list_ids: Vec<u32>,
+    pub tags: Vec<String>,
+}
+
+impl ChangeScope {
+    pub fn of_task(task: &ResEntryTodoDto) -> Self {
+        ChangeScope::default().with_task(task)
+    }
+
+    /// Merge the parent, list and tags of another state of the task, e.g. before an update.
+    pub fn with_task(mut self, task: &ResEntryTodoDto) -> Self {
+        if self.parent_id.is_none() {
+            self.parent_id = task.parent_id;
+        }
+        if let Some(list_id) = task.list_id.filter(|list_id| !self.list_ids.contains(list_id)) {
+            self.list_ids.push(list_id);
+        }
+        self.with_tags(&task.tags)
+    }
+
+    pub fn with_tags(mut self, tags: &[String]) -> Self {
+        for tag in tags {
+            if !self.tags.iter().any(|known| known.eq_ignore_ascii_case(tag)) {
+                self.tags.push(tag.clone());
+            }
+        }
+        self
+    }
+}
+
+
+/// Live events of a feed, preceded by the ones a resuming client missed.
+pub struct ChangeFeed {
+    pub missed: Vec<ResChangeEventDto>,
+    /// `false` when some events after the resume point are no longer known (too old, or the
+    /// server restarted since), the client has to reload its state instead of replaying
+    pub complete: bool,
+    pub live: broadcast::Receiver<ResChangeEventDto>,
+}
+
+
+#[derive(Default)]
+struct History {
+    last_id: u64,
+    events: VecDeque<ResChangeEventDto>,
+}
+
+
+/// In-process fan-out of every stored mutation to whoever listens (MCP sessions, HTTP streams).
+/// The last events are kept so a reconnecting client can pick up where it left off.
+pub struct ChangeBus {
+    sender: broadcast::Sender<ResChangeEventDto>,
+    history: Mutex<History>,
+}
+
+impl Default for ChangeBus {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            sender,
+            history: Mutex::new(History::default()),
+        }
+    }
+}
+
+impl ChangeBus {
+    pub fn subscribe(&self) -> broadcast::Receiver<ResChangeEventDto> {
+        self.sender.subscribe()
+    }
+
+    /// Subscribe and collect the events published after `since` in one step, so nothing
+    /// falls between the replay and the live stream.
+    pub fn feed_since(&self, since: u64) -> ChangeFeed {
+        let history = self.history.lock().expect("change history lock poisoned");
+        let live = self.sender.subscribe();
+
+        let oldest_known = history.events.front().map_or(history.last_id + 1, |event| event.id);
+        ChangeFeed {
+            missed: history.events.iter().filter(|event| event.id > since).cloned().collect(),
+            complete: since <= history.last_id && since + 1 >= oldest_known,
+            live,
+        }
+    }
+
+    pub fn publish(&self, kind: ChangeKind, entity: ChangeEntity, entity_id: u32, scope: ChangeScope) -> ResChangeEventDto {
+        let mut history = self.history.lock().expect("change history lock poisoned");
+        history.last_id += 1;
+
+        let event = ResChangeEventDto {
+            id: history.last_id,
+            kind,
+            entity,
+            entity_id,
+            parent_id: scope.parent_id,
+            list_ids: scope.list_ids,
+            tags: scope.tags,
+            occurred_at: Utc::now(),
+        };
+        if history.events.len() == HISTORY_CAPACITY {
+            history.events.pop_front();
+        }
+        history.events.push_back(event.clone());
+
+        // no subscriber is not an error, the event simply goes nowhere
+        let _ = self.sender.send(event.clone());
+        event
+    }
+}
\ No newline at end of file
@@ -0,0 +1 @@
+pub mod change_bus;
\ No newline at end of file
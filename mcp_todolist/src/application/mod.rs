@@ -1 +1,5 @@
-pub mod usecase;
\ No newline at end of file
+pub mod usecase;
+pub mod event;
+pub mod reminder;
+pub mod webhook;
+pub mod transfer;
\ No newline at end of file
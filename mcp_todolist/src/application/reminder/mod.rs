@@ -0,0 +1,2 @@
+pub mod notification_sink;
+pub mod reminder_scheduler;
\ No newline at end of file
@@ -0,0 +1,13 @@
+use anyhow::Result;
+
+use crate::domain::dto::reminder_dto::ResReminderDto;
+
+
+
+/// Somewhere fired reminders are delivered to, the scheduler hands every reminder to each sink.
+#[async_trait::async_trait]
+pub trait NotificationSink: Send + Sync {
+    /// Short name used in the logs.
+    fn name(&self) -> &'static str;
+    async fn notify(&self, reminder: &ResReminderDto) -> Result<()>;
+}
\ No newline at end of file
@@ -0,0 +1,67 @@
+use std::{sync::Arc, time::Duration};
+use chrono::Utc;
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+
+use crate::application::usecase::todo_usecase::TodolistUseCase;
+
+use super::notification_sink::NotificationSink;
+
+
+
+/// Background job that looks for tasks reaching their reminder or due date and notifies the sinks.
+///
+/// Reminders are recorded as fired before they are delivered, so a restart never sends one twice
+/// and a sink failing is logged rather than retried.
+pub struct ReminderScheduler {
+    use_case: Arc<TodolistUseCase>,
+    sinks: Vec<Arc<dyn NotificationSink>>,
+    poll_interval: Duration,
+    /// how far back a missed reminder is still sent, e.g. after the server was down
+    lookback: chrono::Duration,
+}
+
+impl ReminderScheduler {
+    pub fn new(use_case: Arc<TodolistUseCase>, poll_interval: Duration, lookback: chrono::Duration) -> Self {
+        Self {
+            use_case,
+            sinks: Vec::new(),
+            poll_interval,
+            lookback,
+        }
+    }
+
+    pub fn with_sink(mut self, sink: Arc<dyn NotificationSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(self.poll_interval);
+            ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                ticks.tick().await;
+                self.run_once().await;
+            }
+        })
+    }
+
+    async fn run_once(&self) {
+        let now = Utc::now();
+        let reminders = match self.use_case.claim_due_reminders(now - self.lookback, now).await {
+            Ok(reminders) => reminders,
+            Err(e) => {
+                tracing::error!("Failed to look up due reminders: {}", e);
+                return;
+            }
+        };
+
+        for reminder in &reminders {
+            for sink in &self.sinks {
+                if let Err(e) = sink.notify(reminder).await {
+                    tracing::warn!("{} sink failed to deliver the {} notification of task {}: {}", sink.name(), reminder.kind.as_str(), reminder.task.id, e);
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
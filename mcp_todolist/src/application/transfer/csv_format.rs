@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
+use csv::{ReaderBuilder, StringRecord, Trim, Writer};
+
+use crate::domain::{dto::{todo_dto::TaskPriority, transfer_dto::{TransferDocumentDto, TransferTaskDto}}, error::domain_error::{DomainError, DomainResult}};
+
+
+
+const COLUMNS: [&str; 11] = ["id", "parent_id", "title", "description", "is_done", "priority", "due_date", "remind_at", "list", "tags", "created_at"];
+/// Tags share a single column, `home;errands`.
+const TAG_SEPARATOR: char = ';';
+/// Escapes a separator inside a tag name, `a\;b` is the single tag `a;b`.
+const TAG_ESCAPE: char = '\\';
+
+
+fn format_datetime(value: Option<DateTime<Utc>>) -> String {
+    value.map(|at| at.to_rfc3339_opts(SecondsFormat::AutoSi, true)).unwrap_or_default()
+}
+
+/// RFC 3339, or a bare `2026-10-18` / `2026-10-18 09:30:00` read as UTC since spreadsheets drop the offset.
+fn parse_datetime(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|at| at.with_timezone(&Utc))
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").map(|at| at.and_utc()))
+        .or_else(|_| NaiveDate::parse_from_str(value, "%Y-%m-%d").map(|day| day.and_time(Default::default()).and_utc()))
+        .ok()
+}
+
+fn join_tags(tags: &[String]) -> String {
+    let escaped: Vec<String> = tags
+        .iter()
+        .map(|name| {
+            name.chars().fold(String::with_capacity(name.len()), |mut escaped, c| {
+                if c == TAG_SEPARATOR || c == TAG_ESCAPE {
+                    escaped.push(TAG_ESCAPE);
+                }
+                escaped.push(c);
+                escaped
+            })
+        })
+        .collect();
+    escaped.join(&TAG_SEPARATOR.to_string())
+}
+
+fn split_tags(value: &str) -> Vec<String> {
+    let mut tags = vec![String::new()];
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            TAG_ESCAPE => tags.last_mut().expect("starts with one tag").extend(chars.next()),
+            TAG_SEPARATOR => tags.push(String::new()),
+            c => tags.last_mut().expect("starts with one tag").push(c),
+        }
+    }
+    tags.into_iter()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "" | "false" | "0" | "no" => Some(false),
+        "true" | "1" | "yes" | "x" => Some(true),
+        _ => None,
+    }
+}
+
+
+/// One row per task, subtasks right after their task.
+pub fn write(document: &TransferDocumentDto) -> DomainResult<String> {
+    let failed = |e: csv::Error| DomainError::storage(format!("Failed to write the CSV export: {}", e));
+
+    let mut writer = Writer::from_writer(Vec::new());
+    writer.write_record(COLUMNS).map_err(failed)?;
+    for task in &document.tasks {
+        writer
+            .write_record([
+                task.id.map(|id| id.to_string()).unwrap_or_default(),
+                task.parent_id.map(|id| id.to_string()).unwrap_or_default(),
+                task.title.clone(),
+                task.description.clone(),
+                task.is_done.to_string(),
+                task.priority.as_str().to_string(),
+                format_datetime(task.due_date),
+                format_datetime(task.remind_at),
+                task.list.clone().unwrap_or_default(),
+                join_tags(&task.tags),
+                format_datetime(task.created_at),
+            ])
+            .map_err(failed)?;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| DomainError::storage(format!("Failed to write the CSV export: {}", e)))?;
+    String::from_utf8(bytes).map_err(|e| DomainError::storage(format!("Failed to write the CSV export: {}", e)))
+}
+
+
+/// Columns are matched by their header name in any order, only `title` is required.
+/// Rows are checked like tasks created by hand afterwards, so a task that is not a subtask needs a `description`.
+pub fn read(content: &str) -> DomainResult<TransferDocumentDto> {
+    let mut reader = ReaderBuilder::new().trim(Trim::All).flexible(true).from_reader(content.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|e| DomainError::validation(format!("Invalid CSV header: {}", e)))?;
+    let columns: HashMap<String, usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(index, name)| (name.to_ascii_lowercase(), index))
+        .collect();
+    if !columns.contains_key("title") {
+        return Err(DomainError::validation("CSV import needs a title column"));
+    }
+
+    let mut tasks = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let row = index + 1;
+        let record = record.map_err(|e| DomainError::validation(format!("row {}: {}", row, e)))?;
+        tasks.push(read_task(&columns, &record, row)?);
+    }
+    Ok(TransferDocumentDto::new(Vec::new(), tasks))
+}
+
+fn read_task(columns: &HashMap<String, usize>, record: &StringRecord, row: usize) -> DomainResult<TransferTaskDto> {
+    let field = |name: &str| columns.get(name).and_then(|&index| record.get(index)).unwrap_or_default();
+    let invalid = |name: &str| DomainError::validation(format!("row {}: invalid {} {:?}", row, name, field(name)));
+
+    let id = |name: &str| match field(name) {
+        "" => Ok(None),
+        value => value.parse().map(Some).map_err(|_| invalid(name)),
+    };
+    let datetime = |name: &str| match field(name) {
+        "" => Ok(None),
+        value => parse_datetime(value).map(Some).ok_or_else(|| invalid(name)),
+    };
+
+    Ok(TransferTaskDto {
+        id: id("id")?,
+        parent_id: id("parent_id")?,
+        title: field("title").to_string(),
+        description: field("description").to_string(),
+        is_done: parse_bool(field("is_done")).ok_or_else(|| invalid("is_done"))?,
+        priority: match field("priority") {
+            "" => TaskPriority::default(),
+            value => TaskPriority::parse(&value.to_ascii_lowercase()).ok_or_else(|| invalid("priority"))?,
+        },
+        due_date: datetime("due_date")?,
+        remind_at: datetime("remind_at")?,
+        list: Some(field("list")).filter(|name| !name.is_empty()).map(str::to_string),
+        tags: split_tags(field("tags")),
+        created_at: datetime("created_at")?,
+        ..Default::default()
+    })
+}
\ No newline at end of file
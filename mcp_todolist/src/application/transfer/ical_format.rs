@@ -0,0 +1,617 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::domain::{dto::{todo_dto::TaskPriority, transfer_dto::{TransferDocumentDto, TransferTaskDto}}, error::domain_error::{DomainError, DomainResult}};
+
+
+
+// RFC 5545, written by hand since only VTODO (and its VALARM) is read or written.
+
+const PRODUCT_ID: &str = "-//mcp_todolist//Tasks//EN";
+const CALENDAR_NAME: &str = "Todolist";
+/// Not an iCalendar property, keeps the list of a task across an export and import.
+const LIST_PROPERTY: &str = "X-TODOLIST-LIST";
+/// Content lines are folded at 75 octets.
+const MAX_LINE_OCTETS: usize = 75;
+const DATETIME_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+
+fn task_uid(id: u32) -> String {
+    format!("task-{}@mcp_todolist", id)
+}
+
+fn format_datetime(at: DateTime<Utc>) -> String {
+    format!("{}Z", at.format(DATETIME_FORMAT))
+}
+
+/// An IANA zone name as found in `TZID` or `X-WR-TIMEZONE`, some writers put a `/` in front.
+fn parse_zone(name: &str) -> Option<Tz> {
+    name.trim().trim_start_matches('/').parse().ok()
+}
+
+/// `20261018T093000Z` in UTC, `20261018T093000` in the zone of its `TZID`, or of the calendar
+/// when it has none, and `20261018` as midnight UTC. A local time without a zone is refused
+/// rather than guessed, so is one skipped by a daylight saving change.
+fn read_datetime(line: &ContentLine, todo: usize, calendar_zone: Option<Tz>) -> DomainResult<DateTime<Utc>> {
+    let refused = |reason: String| DomainError::validation(format!("VTODO {}: {} {:?} {}", todo, line.name, line.value, reason));
+    let invalid = || DomainError::validation(format!("VTODO {}: invalid {} {:?}", todo, line.name, line.value));
+
+    let value = line.value.trim();
+    if let Ok(day) = NaiveDate::parse_from_str(value, "%Y%m%d") {
+        return Ok(day.and_time(Default::default()).and_utc());
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        return NaiveDateTime::parse_from_str(utc, DATETIME_FORMAT).map(|at| at.and_utc()).map_err(|_| invalid());
+    }
+    let local = NaiveDateTime::parse_from_str(value, DATETIME_FORMAT).map_err(|_| invalid())?;
+    let zone = match line.param("TZID") {
+        Some(name) => parse_zone(name).ok_or_else(|| refused(format!("has an unknown TZID {:?}", name)))?,
+        None => calendar_zone.ok_or_else(|| refused("is a floating time, give it a TZID or write it in UTC".to_string()))?,
+    };
+    // the first of the two readings when clocks go back
+    zone.from_local_datetime(&local)
+        .earliest()
+        .map(|at| at.with_timezone(&Utc))
+        .ok_or_else(|| refused(format!("does not exist in {}", zone.name())))
+}
+
+/// `-PT15M`, `P1D`, `-P1W`, `PT1H30M` as used by relative alarm triggers, `P` and `T` each need
+/// at least one amount after them.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let mut rest = value.strip_prefix('P')?;
+    let mut total = Duration::zero();
+    let mut in_time = false;
+    let mut amounts = 0;
+    while !rest.is_empty() {
+        if let Some(time) = rest.strip_prefix('T') {
+            if in_time || time.is_empty() {
+                return None;
+            }
+            in_time = true;
+            rest = time;
+            continue;
+        }
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        let amount: i64 = rest[..digits].parse().ok()?;
+        total += match (in_time, rest[digits..].chars().next()?) {
+            (false, 'W') => Duration::weeks(amount),
+            (false, 'D') => Duration::days(amount),
+            (true, 'H') => Duration::hours(amount),
+            (true, 'M') => Duration::minutes(amount),
+            (true, 'S') => Duration::seconds(amount),
+            _ => return None,
+        };
+        rest = &rest[digits + 1..];
+        amounts += 1;
+    }
+    (amounts > 0).then_some(if negative { -total } else { total })
+}
+
+/// 1 is the highest priority and 9 the lowest, 0 leaves it undefined.
+fn priority_level(priority: TaskPriority) -> u8 {
+    match priority {
+        TaskPriority::Urgent => 1,
+        TaskPriority::High => 3,
+        TaskPriority::Normal => 5,
+        TaskPriority::Low => 9,
+    }
+}
+
+fn priority_from_level(level: u8) -> TaskPriority {
+    match level {
+        1..=2 => TaskPriority::Urgent,
+        3..=4 => TaskPriority::High,
+        6..=9 => TaskPriority::Low,
+        _ => TaskPriority::Normal,
+    }
+}
+
+
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Split a TEXT list on its unescaped commas, unescaping each value.
+fn split_text(value: &str) -> Vec<String> {
+    let mut values = vec![String::new()];
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                Some('n' | 'N') => values.last_mut().unwrap().push('\n'),
+                Some(escaped) => values.last_mut().unwrap().push(escaped),
+                None => {}
+            },
+            ',' => values.push(String::new()),
+            _ => values.last_mut().unwrap().push(ch),
+        }
+    }
+    values
+}
+
+fn unescape_text(value: &str) -> String {
+    split_text(value).join(",")
+}
+
+
+/// Append a content line, folded so no line is longer than 75 octets.
+fn push_line(output: &mut String, line: &str) {
+    let mut width = 0;
+    for ch in line.chars() {
+        if width + ch.len_utf8() > MAX_LINE_OCTETS {
+            output.push_str("\r\n ");
+            width = 1;
+        }
+        output.push(ch);
+        width += ch.len_utf8();
+    }
+    output.push_str("\r\n");
+}
+
+fn write_todo(output: &mut String, task: &TransferTaskDto, index: usize, stamp: &str) {
+    let id = task.id.unwrap_or(index as u32 + 1);
+    push_line(output, "BEGIN:VTODO");
+    push_line(output, &format!("UID:{}", task_uid(id)));
+    push_line(output, &format!("DTSTAMP:{}", stamp));
+    if let Some(created_at) = task.created_at {
+        push_line(output, &format!("CREATED:{}", format_datetime(created_at)));
+    }
+    push_line(output, &format!("SUMMARY:{}", escape_text(task.title.trim())));
+    if !task.description.is_empty() {
+        push_line(output, &format!("DESCRIPTION:{}", escape_text(&task.description)));
+    }
+    push_line(output, if task.is_done { "STATUS:COMPLETED" } else { "STATUS:NEEDS-ACTION" });
+    if let Some(due_date) = task.due_date {
+        push_line(output, &format!("DUE:{}", format_datetime(due_date)));
+    }
+    push_line(output, &format!("PRIORITY:{}", priority_level(task.priority)));
+    if !task.tags.is_empty() {
+        let tags: Vec<String> = task.tags.iter().map(|tag| escape_text(tag)).collect();
+        push_line(output, &format!("CATEGORIES:{}", tags.join(",")));
+    }
+    if let Some(list) = &task.list {
+        push_line(output, &format!("{}:{}", LIST_PROPERTY, escape_text(list)));
+    }
+    if let Some(parent_id) = task.parent_id {
+        push_line(output, &format!("RELATED-TO;RELTYPE=PARENT:{}", task_uid(parent_id)));
+    }
+    if let Some(remind_at) = task.remind_at {
+        push_line(output, "BEGIN:VALARM");
+        push_line(output, "ACTION:DISPLAY");
+        push_line(output, &format!("DESCRIPTION:{}", escape_text(task.title.trim())));
+        push_line(output, &format!("TRIGGER;VALUE=DATE-TIME:{}", format_datetime(remind_at)));
+        push_line(output, "END:VALARM");
+    }
+    push_line(output, "END:VTODO");
+}
+
+
+/// One VTODO per task, subtasks `RELATED-TO` their task and the reminder as a VALARM.
+pub fn write(document: &TransferDocumentDto) -> DomainResult<String> {
+    let stamp = format_datetime(document.exported_at);
+    let mut output = String::new();
+    push_line(&mut output, "BEGIN:VCALENDAR");
+    push_line(&mut output, "VERSION:2.0");
+    push_line(&mut output, &format!("PRODID:{}", PRODUCT_ID));
+    push_line(&mut output, "CALSCALE:GREGORIAN");
+    push_line(&mut output, &format!("X-WR-CALNAME:{}", CALENDAR_NAME));
+    for (index, task) in document.tasks.iter().enumerate() {
+        write_todo(&mut output, task, index, &stamp);
+    }
+    push_line(&mut output, "END:VCALENDAR");
+    Ok(output)
+}
+
+
+/// A content line, `NAME;PARAM=VALUE:value`.
+struct ContentLine<'a> {
+    name: String,
+    params: Vec<(String, &'a str)>,
+    value: &'a str,
+}
+
+impl<'a> ContentLine<'a> {
+    fn parse(line: &'a str) -> Option<Self> {
+        // `:` and `;` inside a quoted parameter value do not count
+        let mut quoted = false;
+        let mut separators = Vec::new();
+        let mut colon = None;
+        for (at, ch) in line.char_indices() {
+            match ch {
+                '"' => quoted = !quoted,
+                ';' if !quoted => separators.push(at),
+                ':' if !quoted => {
+                    colon = Some(at);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let colon = colon?;
+        let head = &line[..colon];
+        let name_end = separators.first().copied().unwrap_or(colon);
+        let params = separators
+            .iter()
+            .enumerate()
+            .filter_map(|(index, &start)| {
+                let end = separators.get(index + 1).copied().unwrap_or(head.len());
+                let (key, value) = head[start + 1..end].split_once('=')?;
+                Some((key.to_ascii_uppercase(), value.trim_matches('"')))
+            })
+            .collect();
+        Some(ContentLine { name: line[..name_end].to_ascii_uppercase(), params, value: &line[colon + 1..] })
+    }
+
+    fn param(&self, key: &str) -> Option<&str> {
+        self.params.iter().find(|(name, _)| name == key).map(|(_, value)| *value)
+    }
+}
+
+
+enum Trigger {
+    At(DateTime<Utc>),
+    /// offset from DTSTART, or from DUE with `RELATED=END` or when the task has no DTSTART
+    Offset { offset: Duration, from_due: bool },
+}
+
+#[derive(Default)]
+struct TodoDraft {
+    uid: Option<String>,
+    parent_uid: Option<String>,
+    start: Option<DateTime<Utc>>,
+    trigger: Option<Trigger>,
+    task: TransferTaskDto,
+}
+
+impl TodoDraft {
+    fn read_property(&mut self, line: &ContentLine, todo: usize, calendar_zone: Option<Tz>) -> DomainResult<()> {
+        let invalid = || DomainError::validation(format!("VTODO {}: invalid {} {:?}", todo, line.name, line.value));
+        let datetime = || read_datetime(line, todo, calendar_zone);
+
+        match line.name.as_str() {
+            "UID" => self.uid = Some(line.value.to_string()),
+            "SUMMARY" => self.task.title = unescape_text(line.value),
+            "DESCRIPTION" => self.task.description = unescape_text(line.value),
+            "STATUS" => self.task.is_done = line.value.eq_ignore_ascii_case("COMPLETED"),
+            "COMPLETED" => self.task.is_done = true,
+            "DUE" => self.task.due_date = Some(datetime()?),
+            "DTSTART" => self.start = Some(datetime()?),
+            "CREATED" => self.task.created_at = Some(datetime()?),
+            "PRIORITY" => self.task.priority = priority_from_level(line.value.trim().parse().map_err(|_| invalid())?),
+            "CATEGORIES" => self.task.tags.extend(split_text(line.value).into_iter().filter(|tag| !tag.trim().is_empty())),
+            "RELATED-TO" if line.param("RELTYPE").is_none_or(|kind| kind.eq_ignore_ascii_case("PARENT")) => {
+                self.parent_uid = Some(line.value.to_string());
+            }
+            LIST_PROPERTY => self.task.list = Some(unescape_text(line.value)),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn read_trigger(&mut self, line: &ContentLine, todo: usize, calendar_zone: Option<Tz>) -> DomainResult<()> {
+        let invalid = || DomainError::validation(format!("VTODO {}: invalid TRIGGER {:?}", todo, line.value));
+        // the first alarm of a task is its reminder
+        if self.trigger.is_some() {
+            return Ok(());
+        }
+        self.trigger = Some(if line.param("VALUE").is_some_and(|kind| kind.eq_ignore_ascii_case("DATE-TIME")) {
+            Trigger::At(read_datetime(line, todo, calendar_zone)?)
+        } else {
+            Trigger::Offset {
+                offset: parse_duration(line.value).ok_or_else(invalid)?,
+                from_due: line.param("RELATED").is_some_and(|related| related.eq_ignore_ascii_case("END")),
+            }
+        });
+        Ok(())
+    }
+
+    fn remind_at(&self) -> Option<DateTime<Utc>> {
+        match self.trigger.as_ref()? {
+            Trigger::At(at) => Some(*at),
+            Trigger::Offset { offset, from_due } => {
+                let base = if *from_due { self.task.due_date } else { self.start.or(self.task.due_date) };
+                base.map(|base| base + *offset)
+            }
+        }
+    }
+}
+
+
+/// Lines continued on the next line by a leading space or tab joined back.
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        match line.strip_prefix([' ', '\t']) {
+            Some(continued) if !lines.is_empty() => lines.last_mut().unwrap().push_str(continued),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Id in the file of the top task above `index`, `None` when its `RELATED-TO` chain loops.
+fn root_of(index: usize, parents: &[Option<usize>]) -> Option<usize> {
+    let mut current = index;
+    for _ in 0..=parents.len() {
+        match parents[current] {
+            Some(parent) => current = parent,
+            None => return Some(current),
+        }
+    }
+    None
+}
+
+
+/// Every VTODO of the calendars in the file, other components are ignored.
+/// A task nested deeper than one level is attached to its top task, subtasks cannot be nested.
+///
+/// Local times are read in their `TZID` zone or else in the calendar's `X-WR-TIMEZONE`,
+/// the zones are looked up by IANA name, a VTIMEZONE component is not read.
+pub fn read(content: &str) -> DomainResult<TransferDocumentDto> {
+    let lines = unfold(content);
+    if !lines.iter().any(|line| line.trim().eq_ignore_ascii_case("BEGIN:VCALENDAR")) {
+        return Err(DomainError::validation("Invalid iCalendar import: no VCALENDAR found"));
+    }
+    let calendar_zone = match lines.iter().filter_map(|line| ContentLine::parse(line.trim_end())).find(|line| line.name == "X-WR-TIMEZONE") {
+        Some(line) => Some(parse_zone(line.value).ok_or_else(|| {
+            DomainError::validation(format!("Invalid iCalendar import: unknown X-WR-TIMEZONE {:?}", line.value))
+        })?),
+        None => None,
+    };
+
+    let mut drafts: Vec<TodoDraft> = Vec::new();
+    let mut draft: Option<TodoDraft> = None;
+    // components opened inside the current VTODO, a VALARM among them
+    let mut nested: Vec<String> = Vec::new();
+    for line in &lines {
+        let Some(line) = ContentLine::parse(line.trim_end()) else {
+            continue;
+        };
+        let value = line.value.trim().to_ascii_uppercase();
+        let todo = drafts.len() + 1;
+        match (line.name.as_str(), draft.as_mut()) {
+            ("BEGIN", None) if value == "VTODO" => draft = Some(TodoDraft::default()),
+            ("BEGIN", Some(_)) => nested.push(value),
+            ("END", Some(_)) if nested.is_empty() && value == "VTODO" => drafts.extend(draft.take()),
+            ("END", Some(_)) => {
+                nested.pop();
+            }
+            ("TRIGGER", Some(current)) if nested.last().is_some_and(|component| component == "VALARM") => {
+                current.read_trigger(&line, todo, calendar_zone)?;
+            }
+            (_, Some(current)) if nested.is_empty() => current.read_property(&line, todo, calendar_zone)?,
+            _ => {}
+        }
+    }
+
+    let uids: HashMap<&str, usize> = drafts
+        .iter()
+        .enumerate()
+        .filter_map(|(index, draft)| draft.uid.as_deref().map(|uid| (uid, index)))
+        .collect();
+    let parents: Vec<Option<usize>> = drafts
+        .iter()
+        .enumerate()
+        .map(|(index, draft)| draft.parent_uid.as_deref().and_then(|uid| uids.get(uid).copied()).filter(|&parent| parent != index))
+        .collect();
+
+    let tasks = drafts
+        .iter()
+        .enumerate()
+        .map(|(index, draft)| {
+            let parent_id = parents[index]
+                .and_then(|_| root_of(index, &parents))
+                .filter(|&root| root != index)
+                .map(|root| root as u32 + 1);
+            // DESCRIPTION is optional, a task needs one so the SUMMARY stands in
+            let description = match (&parent_id, draft.task.description.trim()) {
+                (None, "") => draft.task.title.trim().to_string(),
+                _ => draft.task.description.clone(),
+            };
+            TransferTaskDto {
+                id: Some(index as u32 + 1),
+                parent_id,
+                description,
+                remind_at: draft.remind_at(),
+                ..draft.task.clone()
+            }
+        })
+        .collect();
+    Ok(TransferDocumentDto::new(Vec::new(), tasks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    fn calendar(body: &str) -> String {
+        format!("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n{}END:VCALENDAR\r\n", body)
+    }
+
+    fn todo(properties: &str) -> String {
+        format!("BEGIN:VTODO\r\nSUMMARY:Pay rent\r\n{}END:VTODO\r\n", properties)
+    }
+
+    fn due_of(content: &str) -> DomainResult<String> {
+        read(content).map(|document| document.tasks[0].due_date.unwrap().to_rfc3339())
+    }
+
+    fn validation_message(result: DomainResult<impl std::fmt::Debug>) -> String {
+        match result {
+            Err(DomainError::Validation(message)) => message,
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+
+    #[test]
+    fn lines_fold_at_75_octets_without_splitting_a_character() {
+        let mut output = String::new();
+        let line = format!("SUMMARY:{}", "é".repeat(40));
+        push_line(&mut output, &line);
+
+        let physical: Vec<&str> = output.split("\r\n").filter(|part| !part.is_empty()).collect();
+        assert_eq!(physical.len(), 2);
+        // 8 octets of name then 33 two-octet characters, a 34th would make 76
+        assert_eq!(physical[0].len(), 74);
+        assert!(physical[1].starts_with(' '));
+        assert!(physical.iter().all(|part| part.len() <= MAX_LINE_OCTETS));
+        assert_eq!(unfold(&output), vec![line]);
+    }
+
+    #[test]
+    fn a_line_of_exactly_75_octets_is_not_folded() {
+        let mut output = String::new();
+        let line = format!("SUMMARY:{}", "a".repeat(67));
+        push_line(&mut output, &line);
+        assert_eq!(output, format!("{}\r\n", line));
+    }
+
+    #[test]
+    fn unfold_joins_space_and_tab_continuations() {
+        // only the first space or tab belongs to the fold
+        let content = "BEGIN:VTODO\r\nSUMMARY:Pay\r\n  the\r\n\t rent\r\nEND:VTODO\r\n";
+        assert_eq!(unfold(content), vec!["BEGIN:VTODO", "SUMMARY:Pay the rent", "END:VTODO"]);
+        // a leading space on the first line has nothing to continue
+        assert_eq!(unfold(" BEGIN:VCALENDAR"), vec![" BEGIN:VCALENDAR"]);
+    }
+
+    #[test]
+    fn durations_of_alarm_triggers() {
+        assert_eq!(parse_duration("-PT15M"), Some(Duration::minutes(-15)));
+        assert_eq!(parse_duration("P1W"), Some(Duration::weeks(1)));
+        assert_eq!(parse_duration("+P1DT2H30M"), Some(Duration::days(1) + Duration::hours(2) + Duration::minutes(30)));
+        assert_eq!(parse_duration("PT0S"), Some(Duration::zero()));
+        for bad in ["PT", "P", "-P", "P1DT", "PTT1M", "PT15", "P1H", "PT1D", "15M", "P-1D", ""] {
+            assert_eq!(parse_duration(bad), None, "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn root_of_follows_parents_and_gives_up_on_a_cycle() {
+        // 0 <- 1 <- 2, and 3 <-> 4
+        let parents = vec![None, Some(0), Some(1), Some(4), Some(3)];
+        assert_eq!(root_of(2, &parents), Some(0));
+        assert_eq!(root_of(0, &parents), Some(0));
+        assert_eq!(root_of(3, &parents), None);
+    }
+
+    #[test]
+    fn related_to_cycles_leave_both_tasks_on_their_own() {
+        let content = calendar(&format!(
+            "{}{}",
+            todo("UID:a\r\nRELATED-TO:b\r\n"),
+            todo("UID:b\r\nRELATED-TO;RELTYPE=PARENT:a\r\n"),
+        ));
+        let document = read(&content).unwrap();
+        assert!(document.tasks.iter().all(|task| task.parent_id.is_none()));
+    }
+
+    #[test]
+    fn grandchildren_are_attached_to_their_top_task() {
+        let content = calendar(&format!(
+            "{}{}{}",
+            todo("UID:top\r\n"),
+            todo("UID:child\r\nRELATED-TO:top\r\n"),
+            todo("UID:grandchild\r\nRELATED-TO:child\r\n"),
+        ));
+        let parents: Vec<Option<u32>> = read(&content).unwrap().tasks.iter().map(|task| task.parent_id).collect();
+        assert_eq!(parents, vec![None, Some(1), Some(1)]);
+    }
+
+    #[test]
+    fn local_times_are_read_in_their_time_zone() {
+        assert_eq!(due_of(&calendar(&todo("DUE:20261018T093000Z\r\n"))).unwrap(), "2026-10-18T09:30:00+00:00");
+        assert_eq!(due_of(&calendar(&todo("DUE;TZID=Europe/Paris:20261018T093000\r\n"))).unwrap(), "2026-10-18T07:30:00+00:00");
+        // winter time
+        assert_eq!(due_of(&calendar(&todo("DUE;TZID=/Europe/Paris:20261218T093000\r\n"))).unwrap(), "2026-12-18T08:30:00+00:00");
+        // the calendar zone stands in for a missing TZID
+        let content = format!("X-WR-TIMEZONE:America/New_York\r\n{}", todo("DUE:20261018T093000\r\n"));
+        assert_eq!(due_of(&calendar(&content)).unwrap(), "2026-10-18T13:30:00+00:00");
+        // a date has no time to shift
+        assert_eq!(due_of(&calendar(&todo("DUE;VALUE=DATE:20261018\r\n"))).unwrap(), "2026-10-18T00:00:00+00:00");
+    }
+
+    #[test]
+    fn times_without_a_known_zone_are_refused_with_their_record() {
+        let floating = calendar(&format!("{}{}", todo(""), todo("DUE:20261018T093000\r\n")));
+        let message = validation_message(read(&floating));
+        assert!(message.starts_with("VTODO 2: DUE "), "{}", message);
+        assert!(message.contains("floating"), "{}", message);
+
+        let unknown = calendar(&todo("DUE;TZID=W. Europe Standard Time:20261018T093000\r\n"));
+        assert!(validation_message(read(&unknown)).starts_with("VTODO 1: DUE "));
+
+        // clocks went from 02:00 to 03:00 that night
+        let skipped = calendar(&todo("DUE;TZID=Europe/Paris:20260329T023000\r\n"));
+        assert!(validation_message(read(&skipped)).contains("does not exist in Europe/Paris"));
+
+        let calendar_zone = calendar(&format!("X-WR-TIMEZONE:Mars/Olympus\r\n{}", todo("")));
+        assert!(validation_message(read(&calendar_zone)).contains("X-WR-TIMEZONE"));
+    }
+
+    #[test]
+    fn reminders_come_from_the_first_alarm() {
+        let alarms = "BEGIN:VALARM\r\nTRIGGER;RELATED=END:-PT15M\r\nEND:VALARM\r\nBEGIN:VALARM\r\nTRIGGER:-P1D\r\nEND:VALARM\r\n";
+        let content = calendar(&todo(&format!("DTSTART:20261017T080000Z\r\nDUE:20261018T093000Z\r\n{}", alarms)));
+        let task = &read(&content).unwrap().tasks[0];
+        assert_eq!(task.remind_at.unwrap().to_rfc3339(), "2026-10-18T09:15:00+00:00");
+
+        let later = calendar(&todo("BEGIN:VALARM\r\nTRIGGER:-PT5M\r\nEND:VALARM\r\nBEGIN:VALARM\r\nTRIGGER:soon\r\nEND:VALARM\r\n"));
+        assert!(read(&later).is_ok(), "only the first alarm is read");
+        let bad = calendar(&todo("BEGIN:VALARM\r\nTRIGGER:-PT\r\nEND:VALARM\r\n"));
+        assert_eq!(validation_message(read(&bad)), "VTODO 1: invalid TRIGGER \"-PT\"");
+    }
+
+    #[test]
+    fn a_written_calendar_reads_back() {
+        let tasks = vec![
+            TransferTaskDto {
+                id: Some(7),
+                title: "Plan the trip; pack, then leave".to_string(),
+                description: "line one\nline two \\ done".to_string(),
+                priority: TaskPriority::High,
+                due_date: "2026-10-18T09:30:00Z".parse().ok(),
+                remind_at: "2026-10-18T09:00:00Z".parse().ok(),
+                list: Some("Holidays".to_string()),
+                tags: vec!["travel, abroad".to_string(), "família".to_string()],
+                ..Default::default()
+            },
+            TransferTaskDto { id: Some(8), parent_id: Some(7), title: "Book the train".to_string(), is_done: true, ..Default::default() },
+        ];
+        let content = write(&TransferDocumentDto::new(Vec::new(), tasks.clone())).unwrap();
+        let document = read(&content).unwrap();
+
+        let (read_task, read_subtask) = (&document.tasks[0], &document.tasks[1]);
+        assert_eq!(read_task.title, tasks[0].title);
+        assert_eq!(read_task.description, tasks[0].description);
+        assert_eq!(read_task.priority, TaskPriority::High);
+        assert_eq!((read_task.due_date, read_task.remind_at), (tasks[0].due_date, tasks[0].remind_at));
+        assert_eq!(read_task.list, tasks[0].list);
+        assert_eq!(read_task.tags, tasks[0].tags);
+        assert_eq!(read_subtask.parent_id, read_task.id);
+        assert!(read_subtask.is_done);
+    }
+
+    #[test]
+    fn a_file_without_a_calendar_is_refused() {
+        assert!(validation_message(read(&todo(""))).contains("no VCALENDAR"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,28 @@
+use crate::domain::{dto::transfer_dto::{TransferDocumentDto, TransferTaskDto, TRANSFER_FORMAT_VERSION}, error::domain_error::{DomainError, DomainResult}};
+
+
+
+/// The document as is, every field of every task, list, tag and series.
+pub fn write(document: &TransferDocumentDto) -> DomainResult<String> {
+    serde_json::to_string_pretty(document)
+        .map_err(|e| DomainError::storage(format!("Failed to serialize the export: {}", e)))
+}
+
+/// An export document, or a bare array of tasks for files written by hand.
+pub fn read(content: &str) -> DomainResult<TransferDocumentDto> {
+    let invalid = |e: serde_json::Error| DomainError::validation(format!("Invalid JSON import: {}", e));
+
+    if content.trim_start().starts_with('[') {
+        let tasks: Vec<TransferTaskDto> = serde_json::from_str(content).map_err(invalid)?;
+        return Ok(TransferDocumentDto::new(Vec::new(), tasks));
+    }
+
+    let document: TransferDocumentDto = serde_json::from_str(content).map_err(invalid)?;
+    if document.format_version > TRANSFER_FORMAT_VERSION {
+        return Err(DomainError::validation(format!(
+            "format_version {} is newer than the supported version {}",
+            document.format_version, TRANSFER_FORMAT_VERSION
+        )));
+    }
+    Ok(document)
+}
\ No newline at end of file
@@ -0,0 +1,116 @@
+use crate::domain::{dto::transfer_dto::{TransferDocumentDto, TransferTaskDto}, error::domain_error::DomainResult};
+
+
+
+// Only titles, completion, subtasks and lists survive a checklist, everything else is dropped on export.
+
+const TITLE_HEADING: &str = "# Tasks";
+const SUBTASK_INDENT: &str = "  ";
+
+
+fn write_item(output: &mut String, indent: &str, task: &TransferTaskDto) {
+    let mark = if task.is_done { 'x' } else { ' ' };
+    // titles stored before line breaks were refused are flattened, one item per line
+    let title: Vec<&str> = task.title.split_whitespace().collect();
+    output.push_str(&format!("{}- [{}] {}\n", indent, mark, title.join(" ")));
+}
+
+fn write_section(output: &mut String, tasks: &[&TransferTaskDto], all: &[TransferTaskDto]) {
+    for task in tasks {
+        write_item(output, "", task);
+        for subtask in all.iter().filter(|subtask| subtask.parent_id.is_some() && subtask.parent_id == task.id) {
+            write_item(output, SUBTASK_INDENT, subtask);
+        }
+    }
+}
+
+
+/// Tasks without a list come first, then one `##` section per list.
+pub fn write(document: &TransferDocumentDto) -> DomainResult<String> {
+    let top_level: Vec<&TransferTaskDto> = document.tasks.iter().filter(|task| task.parent_id.is_none()).collect();
+
+    let mut lists: Vec<&str> = Vec::new();
+    for name in top_level.iter().filter_map(|task| task.list.as_deref()) {
+        if !lists.contains(&name) {
+            lists.push(name);
+        }
+    }
+
+    let mut output = format!("{}\n", TITLE_HEADING);
+    let unlisted: Vec<&TransferTaskDto> = top_level.iter().copied().filter(|task| task.list.is_none()).collect();
+    if !unlisted.is_empty() {
+        output.push('\n');
+        write_section(&mut output, &unlisted, &document.tasks);
+    }
+    for name in lists {
+        output.push_str(&format!("\n## {}\n\n", name));
+        let listed: Vec<&TransferTaskDto> = top_level.iter().copied().filter(|task| task.list.as_deref() == Some(name)).collect();
+        write_section(&mut output, &listed, &document.tasks);
+    }
+    Ok(output)
+}
+
+
+/// `(indented, is_done, title)` of a `- [ ] title` line, `*` and `+` bullets and `[X]` accepted.
+fn parse_item(line: &str) -> Option<(bool, bool, &str)> {
+    let content = line.trim_start();
+    let indented = content.len() < line.len();
+    let rest = content
+        .strip_prefix("- ")
+        .or_else(|| content.strip_prefix("* "))
+        .or_else(|| content.strip_prefix("+ "))?
+        .trim_start();
+    let (is_done, title) = if let Some(title) = rest.strip_prefix("[ ]") {
+        (false, title)
+    } else if let Some(title) = rest.strip_prefix("[x]").or_else(|| rest.strip_prefix("[X]")) {
+        (true, title)
+    } else {
+        return None;
+    };
+    Some((indented, is_done, title.trim()))
+}
+
+
+/// Checklist items become tasks, an indented item a subtask of the item above it and a `##` heading
+/// the list of the items below it. Any other line is ignored.
+///
+/// A checklist has no descriptions, tasks take their title as description since they need one.
+pub fn read(content: &str) -> DomainResult<TransferDocumentDto> {
+    let mut tasks: Vec<TransferTaskDto> = Vec::new();
+    let mut list: Option<String> = None;
+    let mut last_task: Option<u32> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if let Some(heading) = trimmed.strip_prefix("## ") {
+            list = Some(heading.trim().to_string()).filter(|name| !name.is_empty());
+            last_task = None;
+            continue;
+        }
+        if trimmed.starts_with("# ") {
+            list = None;
+            last_task = None;
+            continue;
+        }
+
+        let Some((indented, is_done, title)) = parse_item(line) else {
+            continue;
+        };
+        let id = tasks.len() as u32 + 1;
+        let parent_id = if indented { last_task } else { None };
+        if parent_id.is_none() {
+            last_task = Some(id);
+        }
+        tasks.push(TransferTaskDto {
+            id: Some(id),
+            title: title.to_string(),
+            description: if parent_id.is_none() { title.to_string() } else { String::new() },
+            is_done,
+            list: list.clone(),
+            parent_id,
+            ..Default::default()
+        });
+    }
+
+    Ok(TransferDocumentDto::new(Vec::new(), tasks))
+}
\ No newline at end of file
@@ -0,0 +1,4 @@
+pub mod json_format;
+pub mod csv_format;
+pub mod markdown_format;
+pub mod ical_format;
\ No newline at end of file
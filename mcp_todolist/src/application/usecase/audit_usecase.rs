@@ -0,0 +1,18 @@
+use crate::domain::{dto::audit_dto::{ResTaskAuditDto, TaskChangeDto}, error::domain_error::DomainResult};
+
+use super::todo_usecase::TodolistUseCase;
+
+
+impl TodolistUseCase {
+    pub async fn get_task_history(&self, task_id: i32) -> DomainResult<Vec<ResTaskAuditDto>> {
+        self.todo_repo.get_task_history(task_id).await
+    }
+
+    /// Publish a committed change and queue it for the webhooks.
+    ///
+    /// The repository audited it in the transaction of the change, a change that could not be audited never gets here.
+    pub(super) async fn publish_recorded_change(&self, change: &TaskChangeDto) {
+        self.publish_task_change(change.kind, &change.task, change.previous.as_ref());
+        self.queue_webhook_deliveries(change.kind, &change.task).await;
+    }
+}
\ No newline at end of file
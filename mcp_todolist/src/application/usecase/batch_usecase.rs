@@ -0,0 +1,84 @@
+use validator::Validate;
+
+use crate::domain::{dto::{audit_dto::{ChangeOrigin, TaskChangeDto}, batch_dto::{BatchItemStatus, BatchOperation, ReqBatchTodoDto, ReqCreateTasksDto, ReqTaskIdsDto, ResBatchItemDto, ResBatchResultDto, MAX_BATCH_ITEMS}, event_dto::ChangeKind, todo_dto::ResEntryTodoDto}, error::domain_error::{DomainError, DomainResult}};
+
+use super::{tag_usecase::normalize_tag_names, todo_usecase::TodolistUseCase};
+
+
+fn rolled_back_item(operation: BatchOperation, index: usize, task_id: Option<i32>) -> ResBatchItemDto {
+    ResBatchItemDto {
+        operation,
+        index,
+        task_id: task_id.map(|id| id as u32),
+        status: BatchItemStatus::RolledBack,
+        error: None,
+        task: None,
+    }
+}
+
+
+impl TodolistUseCase {
+    /// Run a batch all-or-nothing. Invalid create items fail the batch before the storage is touched,
+    /// either way the result holds one entry per item.
+    pub async fn run_batch(&self, mut batch: ReqBatchTodoDto, origin: &ChangeOrigin) -> DomainResult<ResBatchResultDto> {
+        if batch.is_empty() {
+            return Err(DomainError::validation("batch cannot be empty"));
+        }
+        if batch.len() > MAX_BATCH_ITEMS {
+            return Err(DomainError::validation(format!("batch cannot hold more than {} items", MAX_BATCH_ITEMS)));
+        }
+
+        let mut items = Vec::with_capacity(batch.len());
+        for (index, dto) in batch.create.iter_mut().enumerate() {
+            let checked = dto
+                .validate()
+                .map_err(DomainError::from)
+                .and_then(|_| normalize_tag_names(std::mem::take(&mut dto.tags)));
+            match checked {
+                Ok(tags) => {
+                    dto.tags = tags;
+                    items.push(rolled_back_item(BatchOperation::Create, index, None));
+                }
+                Err(e) => items.push(ResBatchItemDto::failed(BatchOperation::Create, index, None, e)),
+            }
+        }
+        if items.iter().any(ResBatchItemDto::is_failed) {
+            items.extend(batch.complete.iter().enumerate().map(|(index, id)| rolled_back_item(BatchOperation::Complete, index, Some(*id))));
+            items.extend(batch.delete.iter().enumerate().map(|(index, id)| rolled_back_item(BatchOperation::Delete, index, Some(*id))));
+            return Ok(ResBatchResultDto::rolled_back(items));
+        }
+
+        let (result, spawned) = self.todo_repo.run_batch(batch, origin.clone()).await?;
+        if result.committed {
+            for item in &result.items {
+                let kind = match item.status {
+                    BatchItemStatus::Created => ChangeKind::Created,
+                    BatchItemStatus::Completed => ChangeKind::Completed,
+                    BatchItemStatus::Deleted => ChangeKind::Deleted,
+                    _ => continue,
+                };
+                // subtasks deleted along with their parent carry no snapshot, the parent event covers them
+                if let Some(task) = &item.task {
+                    // a completed task only differs from what it was by `is_done`
+                    let previous = (kind == ChangeKind::Completed).then(|| ResEntryTodoDto { is_done: false, ..task.clone() });
+                    // the repository audited every item in the batch transaction
+                    self.publish_recorded_change(&TaskChangeDto { kind, task: task.clone(), previous }).await;
+                }
+            }
+            for next in &spawned {
+                self.publish_recorded_change(next).await;
+            }
+        }
+        Ok(result)
+    }
+
+    pub async fn create_tasks(&self, dto: ReqCreateTasksDto, origin: &ChangeOrigin) -> DomainResult<ResBatchResultDto> {
+        self.run_batch(ReqBatchTodoDto { create: dto.tasks, ..Default::default() }, origin).await
+    }
+    pub async fn complete_tasks(&self, dto: ReqTaskIdsDto, origin: &ChangeOrigin) -> DomainResult<ResBatchResultDto> {
+        self.run_batch(ReqBatchTodoDto { complete: dto.ids, ..Default::default() }, origin).await
+    }
+    pub async fn delete_tasks(&self, dto: ReqTaskIdsDto, origin: &ChangeOrigin) -> DomainResult<ResBatchResultDto> {
+        self.run_batch(ReqBatchTodoDto { delete: dto.ids, ..Default::default() }, origin).await
+    }
+}
\ No newline at end of file
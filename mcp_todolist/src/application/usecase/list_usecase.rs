@@ -0,0 +1,57 @@
+use validator::Validate;
+
+use crate::{application::event::change_bus::ChangeScope, domain::{dto::{event_dto::{ChangeEntity, ChangeKind}, list_dto::{ReqCreateListDto, ReqUpdateListDto, ResEntryListDto, ResListCountDto}, todo_dto::ResEntryTodoDto}, error::domain_error::{DomainError, DomainResult}}};
+
+use super::todo_usecase::TodolistUseCase;
+
+
+impl TodolistUseCase {
+    pub async fn create_list(&self, dto: ReqCreateListDto) -> DomainResult<ResEntryListDto> {
+        dto.validate()?;
+        if dto.name.trim().is_empty() {
+            return Err(DomainError::validation("list name cannot be blank"));
+        }
+        let created = self.todo_repo.create_list(dto).await?;
+        self.publish_list_change(ChangeKind::Created, created.id);
+        Ok(created)
+    }
+    pub async fn update_list(&self, list_id: i32, dto: ReqUpdateListDto) -> DomainResult<ResEntryListDto> {
+        dto.validate()?;
+        if list_id != dto.id {
+            return Err(DomainError::validation(format!("List id {} does not match payload id {}", list_id, dto.id)));
+        }
+        if dto.name.as_deref().is_some_and(|name| name.trim().is_empty()) {
+            return Err(DomainError::validation("list name cannot be blank"));
+        }
+        let updated = self.todo_repo.update_list(list_id, dto).await?;
+        self.publish_list_change(ChangeKind::Updated, updated.id);
+        Ok(updated)
+    }
+    pub async fn get_list_by_id(&self, list_id: i32) -> DomainResult<ResEntryListDto> {
+        self.todo_repo.get_list_by_id(list_id).await
+    }
+    pub async fn get_all_lists(&self) -> DomainResult<Vec<ResEntryListDto>> {
+        self.todo_repo.get_all_lists().await
+    }
+    pub async fn delete_list(&self, list_id: i32) -> DomainResult<()> {
+        self.todo_repo.delete_list(list_id).await?;
+        self.publish_list_change(ChangeKind::Deleted, list_id as u32);
+        Ok(())
+    }
+    fn publish_list_change(&self, kind: ChangeKind, list_id: u32) {
+        let scope = ChangeScope { list_ids: vec![list_id], ..Default::default() };
+        self.changes.publish(kind, ChangeEntity::List, list_id, scope);
+    }
+
+    pub async fn get_all_in_list(&self, list_id: i32) -> DomainResult<Vec<ResEntryTodoDto>> {
+        self.todo_repo.get_all_in_list(list_id).await
+    }
+    pub async fn count_tasks_in_list(&self, list_id: i32) -> DomainResult<ResListCountDto> {
+        Ok(ResListCountDto {
+            list_id: list_id as u32,
+            total: self.todo_repo.count_all_task_in_list(list_id).await?,
+            done: self.todo_repo.count_done_task_in_list(list_id).await?,
+            undone: self.todo_repo.count_undone_task_in_list(list_id).await?,
+        })
+    }
+}
\ No newline at end of file
@@ -1 +1,13 @@
-pub mod todo_usecase;
\ No newline at end of file
+pub mod todo_usecase;
+pub mod tag_usecase;
+pub mod list_usecase;
+pub mod subtask_usecase;
+pub mod batch_usecase;
+pub mod trash_usecase;
+pub mod audit_usecase;
+pub mod undo_usecase;
+pub mod search_usecase;
+pub mod recurrence_usecase;
+pub mod reminder_usecase;
+pub mod webhook_usecase;
+pub mod transfer_usecase;
\ No newline at end of file
@@ -0,0 +1,154 @@
+use chrono::Utc;
+use validator::Validate;
+
+use crate::domain::{dto::{audit_dto::ChangeOrigin, recurrence_dto::{ReqCreateRecurringTaskDto, ResRecurrenceDto}}, error::domain_error::{DomainError, DomainResult}};
+
+use super::{tag_usecase::normalize_tag_names, todo_usecase::TodolistUseCase};
+
+
+impl TodolistUseCase {
+    pub async fn create_recurring_task(&self, mut dto: ReqCreateRecurringTaskDto, origin: &ChangeOrigin) -> DomainResult<ResRecurrenceDto> {
+        dto.validate()?;
+        let starts_at = dto.task.due_date.unwrap_or_else(Utc::now);
+        if dto.recurrence.until.is_some_and(|until| until < starts_at) {
+            return Err(DomainError::validation("until cannot be before the first occurrence"));
+        }
+        dto.task.tags = normalize_tag_names(dto.task.tags)?;
+
+        let (created, first) = self.todo_repo.create_recurring_task(dto.task, dto.recurrence, origin.clone()).await?;
+        self.publish_recorded_change(&first).await;
+        Ok(created)
+    }
+    pub async fn get_recurrence(&self, recurrence_id: i32) -> DomainResult<ResRecurrenceDto> {
+        self.todo_repo.get_recurrence(recurrence_id).await
+    }
+    /// Stopping leaves every task as it is, so no change is published.
+    pub async fn stop_recurrence(&self, recurrence_id: i32) -> DomainResult<ResRecurrenceDto> {
+        self.todo_repo.stop_recurrence(recurrence_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::connection::SimpleConnection;
+    use serde_json::json;
+
+    use crate::{domain::dto::{batch_dto::ReqTaskIdsDto, todo_dto::ResEntryTodoDto}, test_support::TestDb};
+
+    use super::*;
+
+
+    fn origin() -> ChangeOrigin {
+        ChangeOrigin::http(None, "test")
+    }
+
+    async fn daily_series(use_case: &TodolistUseCase, count: Option<u32>) -> ResRecurrenceDto {
+        let dto = serde_json::from_value(json!({
+            "task": { "title": "Water the plants", "description": "the ones on the balcony", "is_done": false, "due_date": "2026-10-18T09:00:00Z" },
+            "recurrence": { "frequency": "daily", "count": count }
+        })).unwrap();
+        use_case.create_recurring_task(dto, &origin()).await.unwrap()
+    }
+
+    async fn set_done(use_case: &TodolistUseCase, task: &ResEntryTodoDto, is_done: bool) -> DomainResult<ResEntryTodoDto> {
+        let dto = serde_json::from_value(json!({ "id": task.id, "is_done": is_done })).unwrap();
+        use_case.update_task(task.id as i32, dto, &origin()).await
+    }
+
+    async fn occurrences(use_case: &TodolistUseCase, series: &ResRecurrenceDto) -> Vec<ResEntryTodoDto> {
+        use_case.get_recurrence(series.id as i32).await.unwrap().occurrences
+    }
+
+    /// Make every insert of a follow-up occurrence fail.
+    fn break_spawning(db: &TestDb) {
+        db.pool().get().unwrap().batch_execute(
+            "CREATE TRIGGER no_next_occurrence BEFORE INSERT ON todolist WHEN NEW.occurrence > 1 \
+             BEGIN SELECT RAISE(ABORT, 'no next occurrence'); END;"
+        ).unwrap();
+    }
+
+
+    #[tokio::test]
+    async fn completing_an_occurrence_spawns_the_next_one() {
+        let db = TestDb::new("recurrence_complete");
+        let use_case = db.use_case();
+        let series = daily_series(&use_case, None).await;
+        let first = &series.occurrences[0];
+
+        set_done(&use_case, first, true).await.unwrap();
+
+        let tasks = occurrences(&use_case, &series).await;
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[1].occurrence, Some(2));
+        assert!(!tasks[1].is_done);
+        assert_eq!(tasks[1].due_date.unwrap().to_rfc3339(), "2026-10-19T09:00:00+00:00");
+    }
+
+    #[tokio::test]
+    async fn completing_again_after_reopening_spawns_nothing() {
+        let db = TestDb::new("recurrence_reopen");
+        let use_case = db.use_case();
+        let series = daily_series(&use_case, None).await;
+        let first = &series.occurrences[0];
+
+        set_done(&use_case, first, true).await.unwrap();
+        set_done(&use_case, first, false).await.unwrap();
+        set_done(&use_case, first, true).await.unwrap();
+
+        assert_eq!(occurrences(&use_case, &series).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn a_stopped_series_spawns_nothing() {
+        let db = TestDb::new("recurrence_stopped");
+        let use_case = db.use_case();
+        let series = daily_series(&use_case, None).await;
+
+        let stopped = use_case.stop_recurrence(series.id as i32).await.unwrap();
+        assert!(stopped.stopped_at.is_some());
+        assert!(stopped.next_due.is_none());
+        set_done(&use_case, &series.occurrences[0], true).await.unwrap();
+
+        assert_eq!(occurrences(&use_case, &series).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn the_last_occurrence_of_a_count_spawns_nothing() {
+        let db = TestDb::new("recurrence_count");
+        let use_case = db.use_case();
+        let series = daily_series(&use_case, Some(1)).await;
+        assert!(series.next_due.is_none());
+
+        set_done(&use_case, &series.occurrences[0], true).await.unwrap();
+
+        assert_eq!(occurrences(&use_case, &series).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn completing_in_a_batch_spawns_the_next_one() {
+        let db = TestDb::new("recurrence_batch");
+        let use_case = db.use_case();
+        let series = daily_series(&use_case, None).await;
+
+        let result = use_case.complete_tasks(ReqTaskIdsDto { ids: vec![series.occurrences[0].id as i32] }, &origin()).await.unwrap();
+        assert!(result.committed);
+
+        assert_eq!(occurrences(&use_case, &series).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn a_failed_spawn_rolls_back_the_completion() {
+        let db = TestDb::new("recurrence_spawn_fails");
+        let use_case = db.use_case();
+        let series = daily_series(&use_case, None).await;
+        let first = &series.occurrences[0];
+        break_spawning(&db);
+
+        assert!(matches!(set_done(&use_case, first, true).await, Err(DomainError::Storage(_))));
+        assert!(use_case.complete_tasks(ReqTaskIdsDto { ids: vec![first.id as i32] }, &origin()).await.is_err());
+
+        let tasks = occurrences(&use_case, &series).await;
+        assert_eq!(tasks.len(), 1);
+        assert!(!tasks[0].is_done);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use tokio::sync::broadcast;
+
+use crate::domain::{dto::reminder_dto::ResReminderDto, error::domain_error::DomainResult};
+
+use super::todo_usecase::TodolistUseCase;
+
+
+impl TodolistUseCase {
+    /// Reminders reached between `since` and `now`, each one is returned by a single call only.
+    pub async fn claim_due_reminders(&self, since: DateTime<Utc>, now: DateTime<Utc>) -> DomainResult<Vec<ResReminderDto>> {
+        self.todo_repo.claim_due_reminders(since, now).await
+    }
+    /// Hand a fired reminder to every connected MCP session.
+    pub fn publish_reminder(&self, reminder: &ResReminderDto) {
+        // no session connected is not an error
+        let _ = self.reminders.send(reminder.clone());
+    }
+    pub fn subscribe_reminders(&self) -> broadcast::Receiver<ResReminderDto> {
+        self.reminders.subscribe()
+    }
+}
\ No newline at end of file
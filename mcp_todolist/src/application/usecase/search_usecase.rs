@@ -0,0 +1,17 @@
+use validator::Validate;
+
+use crate::domain::{dto::search_dto::{ReqSearchTodoDto, ResSearchHitDto, DEFAULT_SEARCH_LIMIT}, error::domain_error::{DomainError, DomainResult}};
+
+use super::todo_usecase::TodolistUseCase;
+
+
+impl TodolistUseCase {
+    pub async fn search_tasks(&self, dto: ReqSearchTodoDto) -> DomainResult<Vec<ResSearchHitDto>> {
+        dto.validate()?;
+        let query = dto.query.trim();
+        if query.is_empty() {
+            return Err(DomainError::validation("query cannot be blank"));
+        }
+        self.todo_repo.search_tasks(query.to_string(), dto.limit.unwrap_or(DEFAULT_SEARCH_LIMIT)).await
+    }
+}
\ No newline at end of file
@@ -0,0 +1,38 @@
+use validator::Validate;
+
+use crate::domain::{dto::{audit_dto::ChangeOrigin, event_dto::ChangeKind, subtask_dto::{ReqCreateSubtaskDto, ReqReorderSubtasksDto, ReqTickSubtaskDto}, todo_dto::ResEntryTodoDto}, error::domain_error::{DomainError, DomainResult}};
+
+use super::todo_usecase::TodolistUseCase;
+
+
+impl TodolistUseCase {
+    pub async fn add_subtask(&self, dto: ReqCreateSubtaskDto, origin: &ChangeOrigin) -> DomainResult<ResEntryTodoDto> {
+        dto.validate()?;
+        if dto.title.trim().is_empty() {
+            return Err(DomainError::validation("title cannot be blank"));
+        }
+        let change = self.todo_repo.add_subtask(dto, origin.clone()).await?;
+        self.publish_recorded_change(&change).await;
+        Ok(change.task)
+    }
+    pub async fn get_subtasks(&self, parent_id: i32) -> DomainResult<Vec<ResEntryTodoDto>> {
+        self.todo_repo.get_subtasks(parent_id).await
+    }
+    pub async fn reorder_subtasks(&self, dto: ReqReorderSubtasksDto, origin: &ChangeOrigin) -> DomainResult<Vec<ResEntryTodoDto>> {
+        dto.validate()?;
+        let parent_id = dto.parent_id;
+        let (reordered, changes) = self.todo_repo.reorder_subtasks(parent_id, dto.subtask_ids, origin.clone()).await?;
+        for change in &changes {
+            self.publish_recorded_change(change).await;
+        }
+        let parent = self.todo_repo.get_by_id(parent_id).await?;
+        self.publish_task_change(ChangeKind::Updated, &parent, None);
+        Ok(reordered)
+    }
+    /// Tick a subtask off (or reopen it) and return its parent with the updated roll-up.
+    pub async fn tick_subtask(&self, dto: ReqTickSubtaskDto, origin: &ChangeOrigin) -> DomainResult<ResEntryTodoDto> {
+        let (parent, change) = self.todo_repo.set_subtask_done(dto.subtask_id, dto.is_done, origin.clone()).await?;
+        self.publish_recorded_change(&change).await;
+        Ok(parent)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,90 @@
+use validator::Validate;
+
+use crate::{application::event::change_bus::ChangeScope, domain::{dto::{audit_dto::ChangeOrigin, event_dto::{ChangeEntity, ChangeKind}, tag_dto::{ReqCreateTagDto, ReqTaskTagsDto, ReqUpdateTagDto, ResEntryTagDto}, todo_dto::ResEntryTodoDto}, error::domain_error::{DomainError, DomainResult}}};
+
+use super::todo_usecase::TodolistUseCase;
+
+const MAX_TAG_NAME_LEN: usize = 50;
+
+
+/// Trim tag names, drop duplicates (case-insensitive, first spelling wins)
+/// and reject empty or over-long names.
+pub(crate) fn normalize_tag_names(names: Vec<String>) -> DomainResult<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::with_capacity(names.len());
+    for name in names {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_TAG_NAME_LEN {
+            return Err(DomainError::validation(format!("tag name must be between 1 and {} characters", MAX_TAG_NAME_LEN)));
+        }
+        if !normalized.iter().any(|known| known.eq_ignore_ascii_case(name)) {
+            normalized.push(name.to_string());
+        }
+    }
+    Ok(normalized)
+}
+
+
+impl TodolistUseCase {
+    pub async fn create_tag(&self, dto: ReqCreateTagDto) -> DomainResult<ResEntryTagDto> {
+        dto.validate()?;
+        if dto.name.trim().is_empty() {
+            return Err(DomainError::validation("tag name cannot be blank"));
+        }
+        let created = self.todo_repo.create_tag(dto).await?;
+        self.publish_tag_change(ChangeKind::Created, &created.name, created.id, None);
+        Ok(created)
+    }
+    pub async fn update_tag(&self, tag_id: i32, dto: ReqUpdateTagDto) -> DomainResult<ResEntryTagDto> {
+        dto.validate()?;
+        if tag_id != dto.id {
+            return Err(DomainError::validation(format!("Tag id {} does not match payload id {}", tag_id, dto.id)));
+        }
+        if dto.name.as_deref().is_some_and(|name| name.trim().is_empty()) {
+            return Err(DomainError::validation("tag name cannot be blank"));
+        }
+        let previous = self.find_tag(tag_id).await?;
+        let updated = self.todo_repo.update_tag(tag_id, dto).await?;
+        self.publish_tag_change(ChangeKind::Updated, &updated.name, updated.id, previous.as_deref());
+        Ok(updated)
+    }
+    pub async fn get_all_tags(&self) -> DomainResult<Vec<ResEntryTagDto>> {
+        self.todo_repo.get_all_tags().await
+    }
+    pub async fn delete_tag(&self, tag_id: i32) -> DomainResult<()> {
+        let previous = self.find_tag(tag_id).await?;
+        self.todo_repo.delete_tag(tag_id).await?;
+        if let Some(name) = previous {
+            self.publish_tag_change(ChangeKind::Deleted, &name, tag_id as u32, None);
+        }
+        Ok(())
+    }
+    /// Name of a tag, `None` when it does not exist (the repository reports the missing tag itself).
+    async fn find_tag(&self, tag_id: i32) -> DomainResult<Option<String>> {
+        Ok(self
+            .todo_repo
+            .get_all_tags()
+            .await?
+            .into_iter()
+            .find(|tag| tag.id as i32 == tag_id)
+            .map(|tag| tag.name))
+    }
+    fn publish_tag_change(&self, kind: ChangeKind, name: &str, tag_id: u32, previous_name: Option<&str>) {
+        let names: Vec<String> = [Some(name), previous_name].into_iter().flatten().map(str::to_string).collect();
+        self.changes.publish(kind, ChangeEntity::Tag, tag_id, ChangeScope::default().with_tags(&names));
+    }
+
+    pub async fn attach_tags(&self, dto: ReqTaskTagsDto, origin: &ChangeOrigin) -> DomainResult<ResEntryTodoDto> {
+        dto.validate()?;
+        let names = normalize_tag_names(dto.tags)?;
+        let change = self.todo_repo.attach_tags(dto.task_id, names, origin.clone()).await?;
+        self.publish_recorded_change(&change).await;
+        Ok(change.task)
+    }
+    pub async fn detach_tags(&self, dto: ReqTaskTagsDto, origin: &ChangeOrigin) -> DomainResult<ResEntryTodoDto> {
+        dto.validate()?;
+        let names = normalize_tag_names(dto.tags)?;
+        let change = self.todo_repo.detach_tags(dto.task_id, names, origin.clone()).await?;
+        self.publish_recorded_change(&change).await;
+        Ok(change.task)
+    }
+}
\ No newline at end of file
@@ -1,15 +1,29 @@
 use std::sync::Arc;
-use anyhow::{anyhow, Result};
+use chrono::{Duration, Utc};
+use tokio::sync::{broadcast, Notify};
+use validator::Validate;
 
-use crate::domain::{dto::todo_dto::{ReqCreateTodoDto, ReqUpdateTodoDto, ResEntryTodoDto}, repository::todo_repository::{TodoOperationRepository, TodoUtilityRepository}};
+use crate::{application::event::change_bus::{ChangeBus, ChangeFeed, ChangeScope}, domain::{dto::{audit_dto::ChangeOrigin, event_dto::{ChangeEntity, ChangeKind, ResChangeEventDto}, query_dto::{ResTodoPageDto, TodoQuery}, reminder_dto::ResReminderDto, todo_dto::{DueToday, DueWithinDays, ReqCreateTodoDto, ReqUpdateTodoDto, ResEntryTodoDto, ResTodoStatsDto}}, error::domain_error::{DomainError, DomainResult}, repository::{audit_repository::AuditRepository, list_repository::ListRepository, recurrence_repository::RecurrenceRepository, reminder_repository::ReminderRepository, search_repository::SearchRepository, subtask_repository::SubtaskRepository, tag_repository::TagRepository, todo_repository::{TodoOperationRepository, TodoUtilityRepository}, transfer_repository::TransferRepository, trash_repository::TrashRepository, webhook_repository::WebhookRepository}}};
+
+use super::tag_usecase::normalize_tag_names;
+
+pub trait TodoRepository: TodoOperationRepository + TodoUtilityRepository + TagRepository + ListRepository + SubtaskRepository + TrashRepository + AuditRepository + SearchRepository + RecurrenceRepository + ReminderRepository + WebhookRepository + TransferRepository {}
+impl<T> TodoRepository for T where T: TodoOperationRepository + TodoUtilityRepository + TagRepository + ListRepository + SubtaskRepository + TrashRepository + AuditRepository + SearchRepository + RecurrenceRepository + ReminderRepository + WebhookRepository + TransferRepository {}
+
+/// How many reminders a slow MCP session may fall behind before it starts missing them.
+const REMINDER_CHANNEL_CAPACITY: usize = 64;
 
-pub trait TodoRepository: TodoOperationRepository + TodoUtilityRepository {}
-impl<T> TodoRepository for T where T: TodoOperationRepository + TodoUtilityRepository {}
 
 #[allow(dead_code)]
 pub struct TodolistUseCase {
     
-    todo_repo: Arc<dyn TodoRepository + Send + Sync + 'static>,
+    pub(super) todo_repo: Arc<dyn TodoRepository + Send + Sync + 'static>,
+    pub(super) changes: ChangeBus,
+    pub(super) reminders: broadcast::Sender<ResReminderDto>,
+    /// woken when webhook deliveries were queued, so the dispatcher does not wait for its next poll
+    pub(super) webhooks_queued: Notify,
+    /// whether webhooks may target loopback, link-local or private addresses
+    pub(super) allow_private_webhook_targets: bool,
     
 }
 
@@ -17,65 +31,112 @@ pub struct TodolistUseCase {
 impl TodolistUseCase {
     pub fn new(repo: Arc<dyn TodoRepository + Send + Sync + 'static>) -> Self {
         Self{
-            todo_repo: repo
+            todo_repo: repo,
+            changes: ChangeBus::default(),
+            reminders: broadcast::channel(REMINDER_CHANNEL_CAPACITY).0,
+            webhooks_queued: Notify::new(),
+            allow_private_webhook_targets: false
         }
     }
 
-    pub async fn create_task(&self, dto: ReqCreateTodoDto) -> Result<ResEntryTodoDto> {
-        let result = self.todo_repo.create_task(dto).await;
-        match result {
-            Ok(data) => Ok(data),
-            Err(_) => Err(anyhow!("Fail to create")),
-        }
+    /// Let webhooks be registered for loopback, link-local and private addresses, off by default.
+    pub fn with_private_webhook_targets(mut self, allow: bool) -> Self {
+        self.allow_private_webhook_targets = allow;
+        self
     }
-    pub async fn update_task(&self, task_id: i32, dto: ReqUpdateTodoDto) -> Result<ResEntryTodoDto> {
-        let result = self.todo_repo.update_task(task_id, dto).await;
-        match result {
-            Ok(data) => Ok(data),
-            Err(_) => Err(anyhow!("Fail to update")),
-        }
+
+    /// Receive every change stored from now on, whichever side (HTTP or MCP) made it.
+    pub fn subscribe_changes(&self) -> broadcast::Receiver<ResChangeEventDto> {
+        self.changes.subscribe()
     }
-    pub async fn get_by_id(&self, task_id: i32) -> Result<ResEntryTodoDto> {
-        let result = self.todo_repo.get_by_id(task_id).await;
-        match result {
-            Ok(data) => Ok(data),
-            Err(_) => Err(anyhow!("Fail to retrive")),
-        }
+    /// Like `subscribe_changes`, preceded by the changes made after event `since`.
+    pub fn change_feed_since(&self, since: u64) -> ChangeFeed {
+        self.changes.feed_since(since)
     }
-    pub async fn get_all(&self) -> Result<Vec<ResEntryTodoDto>> {
-        let result = self.todo_repo.get_all().await;
-        match result {
-            Ok(data) => Ok(data),
-            Err(_) => Err(anyhow!("Fail to get all tasks")),
+    pub(super) fn publish_task_change(&self, kind: ChangeKind, task: &ResEntryTodoDto, previous: Option<&ResEntryTodoDto>) {
+        let mut scope = ChangeScope::of_task(task);
+        if let Some(previous) = previous {
+            scope = scope.with_task(previous);
         }
+        self.changes.publish(kind, ChangeEntity::Task, task.id, scope);
+    }
+
+    pub async fn create_task(&self, mut dto: ReqCreateTodoDto, origin: &ChangeOrigin) -> DomainResult<ResEntryTodoDto> {
+        dto.validate()?;
+        dto.tags = normalize_tag_names(dto.tags)?;
+        let change = self.todo_repo.create_task(dto, origin.clone()).await?;
+        self.publish_recorded_change(&change).await;
+        Ok(change.task)
     }
-    pub async fn delete_task(&self, task_id: i32) -> Result<()> {
-        let result = self.todo_repo.delete_task(task_id).await;
-        match result {
-            Ok(_) => Ok(()),
-            Err(_) => Err(anyhow!("Fail to delete")),
+    pub async fn update_task(&self, task_id: i32, dto: ReqUpdateTodoDto, origin: &ChangeOrigin) -> DomainResult<ResEntryTodoDto> {
+        dto.validate()?;
+        if task_id != dto.id {
+            return Err(DomainError::validation(format!("Task id {} does not match payload id {}", task_id, dto.id)));
         }
+        let (change, next) = self.todo_repo.update_task(task_id, dto, origin.clone()).await?;
+        self.publish_recorded_change(&change).await;
+        if let Some(next) = next {
+            self.publish_recorded_change(&next).await;
+        }
+        Ok(change.task)
+    }
+    pub async fn get_by_id(&self, task_id: i32) -> DomainResult<ResEntryTodoDto> {
+        self.todo_repo.get_by_id(task_id).await
+    }
+    pub async fn get_all(&self) -> DomainResult<Vec<ResEntryTodoDto>> {
+        self.todo_repo.get_all().await
+    }
+    pub async fn list_tasks(&self, query: TodoQuery) -> DomainResult<ResTodoPageDto> {
+        query.validate()?;
+        self.todo_repo.list_tasks(query).await
     }
 
-    pub async fn count_all_task(&self) -> Result<i32> {
-        let result = self.todo_repo.count_all_task().await;
-        match result {
-            Ok(data) => Ok(data),
-            Err(_) => Err(anyhow!("Fail to count all tasks")),
-        }
+    /// Undone tasks whose due date has already passed.
+    pub async fn get_overdue_tasks(&self) -> DomainResult<Vec<ResEntryTodoDto>> {
+        self.todo_repo.get_overdue(Utc::now()).await
     }
-    pub async fn count_done_task(&self) -> Result<i32> {
-        let result = self.todo_repo.count_done_task().await;
-        match result {
-            Ok(data) => Ok(data),
-            Err(_) => Err(anyhow!("Fail to count done task")),
-        }
+    /// Undone tasks due between 00:00 and 24:00 today, on the calendar of the given UTC offset.
+    pub async fn get_due_today_tasks(&self, dto: DueToday) -> DomainResult<Vec<ResEntryTodoDto>> {
+        dto.validate()?;
+        let offset = Duration::minutes(dto.utc_offset_minutes.unwrap_or(0) as i64);
+        // local midnight, then back to UTC
+        let start_of_day = (Utc::now() + offset)
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is always a valid time")
+            .and_utc() - offset;
+        self.todo_repo.get_due_between(start_of_day, start_of_day + Duration::days(1)).await
     }
-    pub async fn count_undone_task(&self) -> Result<i32> {
-        let result = self.todo_repo.count_undone_task().await;
-        match result {
-            Ok(data) => Ok(data),
-            Err(_) => Err(anyhow!("Fail to count undone task")),
-        }
+    /// Undone tasks due from now until `days` days ahead.
+    pub async fn get_due_within_tasks(&self, dto: DueWithinDays) -> DomainResult<Vec<ResEntryTodoDto>> {
+        dto.validate()?;
+        let now = Utc::now();
+        self.todo_repo.get_due_between(now, now + Duration::days(dto.days)).await
+    }
+
+    /// Move the task and its subtasks to the trash, see `restore_task` and `purge_task`.
+    pub async fn delete_task(&self, task_id: i32, origin: &ChangeOrigin) -> DomainResult<()> {
+        let change = self.todo_repo.delete_task(task_id, origin.clone()).await?;
+        self.publish_recorded_change(&change).await;
+        Ok(())
+    }
+
+    pub async fn count_all_task(&self) -> DomainResult<i32> {
+        self.todo_repo.count_all_task().await
+    }
+    pub async fn count_done_task(&self) -> DomainResult<i32> {
+        self.todo_repo.count_done_task().await
+    }
+    pub async fn count_undone_task(&self) -> DomainResult<i32> {
+        self.todo_repo.count_undone_task().await
+    }
+    pub async fn get_stats(&self) -> DomainResult<ResTodoStatsDto> {
+        Ok(ResTodoStatsDto {
+            total: self.todo_repo.count_all_task().await?,
+            done: self.todo_repo.count_done_task().await?,
+            undone: self.todo_repo.count_undone_task().await?,
+            overdue: self.get_overdue_tasks().await?.len() as i32,
+            due_today: self.get_due_today_tasks(DueToday::default()).await?.len() as i32,
+        })
     }
 }
@@ -0,0 +1,413 @@
+use std::collections::{HashMap, HashSet};
+use validator::Validate;
+
+use crate::{application::transfer::{csv_format, ical_format, json_format, markdown_format}, domain::{dto::{audit_dto::{ChangeOrigin, TaskChangeDto}, event_dto::ChangeKind, subtask_dto::ReqCreateSubtaskDto, todo_dto::{ReqCreateTodoDto, ResEntryTodoDto}, transfer_dto::{ReqImportTasksDto, ResImportReportDto, TransferDocumentDto, TransferFormat, TransferListDto, TransferRecurrenceDto, TransferTagDto, TransferTaskDto}}, error::domain_error::{DomainError, DomainResult}}};
+
+use super::{tag_usecase::normalize_tag_names, todo_usecase::TodolistUseCase};
+
+const MAX_LIST_NAME_LEN: usize = 100;
+const MAX_TAG_COLOR_LEN: usize = 20;
+const MAX_RECURRENCE_INTERVAL: u32 = 366;
+const MAX_RECURRENCE_COUNT: u32 = 1000;
+
+
+/// Put the tasks of a file in the shape `import_tasks` stores: titles, list names and tags trimmed,
+/// ids unique and every `parent_id` pointing at a task of the file that is not a subtask itself.
+/// Series and tag colours get the checks they get when created by hand.
+fn check_document(document: &mut TransferDocumentDto) -> DomainResult<()> {
+    if document.tasks.is_empty() {
+        return Err(DomainError::validation("the file holds no task to import"));
+    }
+
+    let mut series_ids: HashSet<u32> = HashSet::new();
+    for series in &document.recurrences {
+        let invalid = |message: String| DomainError::validation(format!("recurrence {}: {}", series.id, message));
+        if !series_ids.insert(series.id) {
+            return Err(invalid("the id is used by an earlier series".to_string()));
+        }
+        if !(1..=MAX_RECURRENCE_INTERVAL).contains(&series.interval) {
+            return Err(invalid(format!("interval must be between 1 and {}", MAX_RECURRENCE_INTERVAL)));
+        }
+        if series.count.is_some_and(|count| !(1..=MAX_RECURRENCE_COUNT).contains(&count)) {
+            return Err(invalid(format!("count must be between 1 and {}", MAX_RECURRENCE_COUNT)));
+        }
+    }
+    for tag in &document.tags {
+        if tag.color.as_ref().is_some_and(|color| color.chars().count() > MAX_TAG_COLOR_LEN) {
+            return Err(DomainError::validation(format!("tag {}: color cannot be longer than {} characters", tag.name, MAX_TAG_COLOR_LEN)));
+        }
+    }
+
+    let mut top_level: HashMap<u32, bool> = HashMap::new();
+    for (index, task) in document.tasks.iter().enumerate() {
+        if let Some(id) = task.id && top_level.insert(id, task.parent_id.is_none()).is_some() {
+            return Err(DomainError::validation(format!("record {}: id {} is used by an earlier task", index + 1, id)));
+        }
+    }
+
+    for (index, task) in document.tasks.iter_mut().enumerate() {
+        let record = index + 1;
+        let invalid = |message: String| DomainError::validation(format!("record {}: {}", record, message));
+
+        // the same checks as creating the task or subtask by hand
+        task.title = task.title.trim().to_string();
+        let checked = match task.parent_id {
+            None => ReqCreateTodoDto {
+                title: task.title.clone(),
+                description: task.description.clone(),
+                is_done: task.is_done,
+                priority: task.priority,
+                due_date: task.due_date,
+                remind_at: task.remind_at,
+                list_id: None,
+                tags: Vec::new(),
+            }
+            .validate(),
+            Some(parent_id) => ReqCreateSubtaskDto {
+                parent_id: parent_id as i32,
+                title: task.title.clone(),
+                description: Some(task.description.clone()),
+                is_done: task.is_done,
+            }
+            .validate(),
+        };
+        checked.map_err(|e| invalid(e.to_string()))?;
+
+        task.list = task.list.take().map(|name| name.trim().to_string()).filter(|name| !name.is_empty());
+        if let Some(name) = &task.list && name.chars().count() > MAX_LIST_NAME_LEN {
+            return Err(invalid(format!("list name must be between 1 and {} characters", MAX_LIST_NAME_LEN)));
+        }
+
+        task.tags = normalize_tag_names(std::mem::take(&mut task.tags)).map_err(|e| invalid(e.to_string()))?;
+
+        if task.version.is_some_and(|version| version < 1) {
+            return Err(invalid("version must be at least 1".to_string()));
+        }
+        match (task.recurrence_id, task.occurrence) {
+            (Some(series_id), Some(occurrence)) => {
+                if !series_ids.contains(&series_id) {
+                    return Err(invalid(format!("recurrence_id {} matches no series of the file", series_id)));
+                }
+                if occurrence < 1 {
+                    return Err(invalid("occurrence must be at least 1".to_string()));
+                }
+            }
+            (None, None) => {}
+            _ => return Err(invalid("recurrence_id and occurrence go together".to_string())),
+        }
+
+        if let Some(parent_id) = task.parent_id {
+            match top_level.get(&parent_id) {
+                None => return Err(invalid(format!("parent_id {} matches no task of the file", parent_id))),
+                Some(false) => return Err(invalid(format!("parent_id {} is a subtask, subtasks cannot be nested", parent_id))),
+                Some(true) => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+
+impl TodolistUseCase {
+    /// Every live task, each followed by its subtasks in their order, with the lists they sit in.
+    ///
+    /// `lossless` adds the trashed tasks, the tags with their colour and the recurring series,
+    /// along with the version and timestamps of every task.
+    async fn export_document(&self, lossless: bool) -> DomainResult<TransferDocumentDto> {
+        let lists = self.get_all_lists().await?;
+        let list_names: HashMap<u32, &str> = lists.iter().map(|list| (list.id, list.name.as_str())).collect();
+
+        let mut tasks = self.get_all().await?;
+        if lossless {
+            tasks.extend(self.list_trash().await?);
+        }
+        tasks.sort_by_key(|task| (task.position, task.id));
+        let exported: HashSet<u32> = tasks.iter().map(|task| task.id).collect();
+        // a subtask whose task is not exported, sitting in the trash, is exported as a task of its own
+        let is_top_level = |task: &ResEntryTodoDto| task.parent_id.is_none_or(|parent_id| !exported.contains(&parent_id));
+
+        let to_transfer = |task: &ResEntryTodoDto, parent_id: Option<u32>| TransferTaskDto {
+            id: Some(task.id),
+            title: task.title.clone(),
+            description: task.description.clone(),
+            is_done: task.is_done,
+            priority: task.priority,
+            due_date: task.due_date,
+            remind_at: task.remind_at,
+            list: task.list_id.and_then(|list_id| list_names.get(&list_id)).map(|name| name.to_string()),
+            tags: task.tags.clone(),
+            parent_id,
+            created_at: Some(task.created_at),
+            updated_at: lossless.then_some(task.updated_at),
+            version: lossless.then_some(task.version),
+            deleted_at: task.deleted_at,
+            recurrence_id: task.recurrence_id.filter(|_| lossless),
+            occurrence: task.occurrence.filter(|_| lossless),
+        };
+
+        let mut top: Vec<&ResEntryTodoDto> = tasks.iter().filter(|task| is_top_level(task)).collect();
+        top.sort_by_key(|task| task.id);
+        let mut ordered = Vec::with_capacity(tasks.len());
+        for task in top {
+            ordered.push(to_transfer(task, None));
+            for subtask in tasks.iter().filter(|subtask| !is_top_level(subtask) && subtask.parent_id == Some(task.id)) {
+                ordered.push(to_transfer(subtask, Some(task.id)));
+            }
+        }
+
+        let lists = lists
+            .iter()
+            .map(|list| TransferListDto { name: list.name.clone(), description: list.description.clone() })
+            .collect();
+        let mut document = TransferDocumentDto::new(lists, ordered);
+        if !lossless {
+            return Ok(document);
+        }
+
+        document.tags = self
+            .get_all_tags()
+            .await?
+            .into_iter()
+            .map(|tag| TransferTagDto { name: tag.name, color: tag.color })
+            .collect();
+
+        let mut series_ids: Vec<u32> = document.tasks.iter().filter_map(|task| task.recurrence_id).collect();
+        series_ids.sort_unstable();
+        series_ids.dedup();
+        for series_id in series_ids {
+            let series = self.get_recurrence(series_id as i32).await?;
+            document.recurrences.push(TransferRecurrenceDto {
+                id: series.id,
+                frequency: series.frequency,
+                interval: series.interval,
+                starts_at: series.starts_at,
+                until: series.until,
+                count: series.count,
+                stopped_at: series.stopped_at,
+            });
+        }
+        Ok(document)
+    }
+
+    pub async fn export_tasks(&self, format: TransferFormat) -> DomainResult<String> {
+        let document = self.export_document(format == TransferFormat::Json).await?;
+        match format {
+            TransferFormat::Json => json_format::write(&document),
+            TransferFormat::Csv => csv_format::write(&document),
+            TransferFormat::Markdown => markdown_format::write(&document),
+            TransferFormat::Ics => ical_format::write(&document),
+        }
+    }
+
+    /// The iCalendar feed of the tasks with a due date, a subtask without its task is shown on its own.
+    pub async fn export_calendar(&self) -> DomainResult<String> {
+        let mut document = self.export_document(false).await?;
+        document.tasks.retain(|task| task.due_date.is_some());
+        let kept: HashSet<u32> = document.tasks.iter().filter_map(|task| task.id).collect();
+        for task in &mut document.tasks {
+            task.parent_id = task.parent_id.filter(|parent_id| kept.contains(parent_id));
+        }
+        ical_format::write(&document)
+    }
+
+    /// Create the tasks of a file, skipping the ones matching a stored task (same title ignoring case,
+    /// due date, parent and trash state). Nothing is stored on a dry run or when any record is invalid.
+    pub async fn import_tasks(&self, dto: ReqImportTasksDto, origin: &ChangeOrigin) -> DomainResult<ResImportReportDto> {
+        let mut document = match dto.format {
+            TransferFormat::Json => json_format::read(&dto.content)?,
+            TransferFormat::Csv => csv_format::read(&dto.content)?,
+            TransferFormat::Markdown => markdown_format::read(&dto.content)?,
+            TransferFormat::Ics => ical_format::read(&dto.content)?,
+        };
+        check_document(&mut document)?;
+
+        let report = self.todo_repo.import_tasks(document, dto.dry_run, origin.clone()).await?;
+        // audited by the repository in the import transaction, tasks imported into the trash are not announced
+        for task in report.tasks.iter().filter(|task| task.deleted_at.is_none()) {
+            self.publish_recorded_change(&TaskChangeDto { kind: ChangeKind::Created, task: task.clone(), previous: None }).await;
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::connection::SimpleConnection;
+    use serde_json::json;
+
+    use crate::test_support::TestDb;
+
+    use super::*;
+
+
+    fn origin() -> ChangeOrigin {
+        ChangeOrigin::http(None, "test")
+    }
+
+    async fn create(use_case: &TodolistUseCase, title: &str, tags: &[&str]) -> ResEntryTodoDto {
+        let dto = serde_json::from_value(json!({
+            "title": title, "description": "from the test", "is_done": false, "due_date": "2026-10-18T09:00:00Z", "tags": tags
+        })).unwrap();
+        use_case.create_task(dto, &origin()).await.unwrap()
+    }
+
+    async fn add_subtask(use_case: &TodolistUseCase, parent: &ResEntryTodoDto, title: &str) -> ResEntryTodoDto {
+        let dto = serde_json::from_value(json!({ "parent_id": parent.id, "title": title })).unwrap();
+        use_case.add_subtask(dto, &origin()).await.unwrap()
+    }
+
+    async fn import(use_case: &TodolistUseCase, format: TransferFormat, content: &str, dry_run: bool) -> DomainResult<ResImportReportDto> {
+        use_case.import_tasks(ReqImportTasksDto { format, content: content.to_string(), dry_run }, &origin()).await
+    }
+
+    /// Export `from` in `format` and import the file into an empty database.
+    async fn round_trip(from: &TodolistUseCase, format: TransferFormat, name: &str) -> (TestDb, ResImportReportDto) {
+        let content = from.export_tasks(format).await.unwrap();
+        let db = TestDb::new(name);
+        let report = import(&db.use_case(), format, &content, false).await.unwrap();
+        (db, report)
+    }
+
+    fn validation_message(result: DomainResult<ResImportReportDto>) -> String {
+        match result {
+            Err(DomainError::Validation(message)) => message,
+            other => panic!("expected a validation error, got {:?}", other.map(|report| report.created)),
+        }
+    }
+
+
+    #[tokio::test]
+    async fn tags_holding_the_csv_separator_and_escape_survive_a_round_trip() {
+        let db = TestDb::new("transfer_tags");
+        let use_case = db.use_case();
+        create(&use_case, "Sort the receipts", &["home;office", r"C:\taxes\", "plain"]).await;
+
+        for (format, name) in [(TransferFormat::Csv, "transfer_tags_csv"), (TransferFormat::Json, "transfer_tags_json")] {
+            let (_imported, report) = round_trip(&use_case, format, name).await;
+            assert_eq!(report.tasks.len(), 1);
+            assert_eq!(report.tasks[0].tags, vec![r"C:\taxes\", "home;office", "plain"], "{:?}", format);
+        }
+    }
+
+    #[tokio::test]
+    async fn a_title_with_a_line_break_is_flattened_by_markdown_and_refused_by_csv_and_json() {
+        let db = TestDb::new("transfer_line_break");
+        let use_case = db.use_case();
+        let task = create(&use_case, "Call the bank", &[]).await;
+        // titles stored before line breaks were refused
+        db.pool().get().unwrap().batch_execute(&format!(
+            "UPDATE todolist SET title = 'Call the' || char(10) || 'bank' WHERE id = {}", task.id
+        )).unwrap();
+
+        let (_imported, report) = round_trip(&use_case, TransferFormat::Markdown, "transfer_line_break_md").await;
+        assert_eq!(report.tasks.len(), 1);
+        assert_eq!(report.tasks[0].title, "Call the bank");
+
+        for format in [TransferFormat::Csv, TransferFormat::Json] {
+            let content = use_case.export_tasks(format).await.unwrap();
+            let fresh = TestDb::new("transfer_line_break_fresh");
+            let message = validation_message(import(&fresh.use_case(), format, &content, false).await);
+            assert!(message.starts_with("record 1: "), "{:?}: {}", format, message);
+            assert!(fresh.use_case().get_all().await.unwrap().is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn a_subtask_under_a_trashed_parent_goes_back_to_the_trash_with_it() {
+        let db = TestDb::new("transfer_trashed_parent");
+        let use_case = db.use_case();
+        let parent = create(&use_case, "Plan the trip", &[]).await;
+        add_subtask(&use_case, &parent, "Book the train").await;
+        create(&use_case, "Water the plants", &[]).await;
+        use_case.delete_task(parent.id as i32, &origin()).await.unwrap();
+
+        let (imported, report) = round_trip(&use_case, TransferFormat::Json, "transfer_trashed_parent_json").await;
+        assert_eq!(report.created, 3);
+        let imported = imported.use_case();
+        let trash = imported.list_trash().await.unwrap();
+        let trashed_parent = trash.iter().find(|task| task.title == "Plan the trip").unwrap();
+        let trashed_subtask = trash.iter().find(|task| task.title == "Book the train").unwrap();
+        assert_eq!(trashed_subtask.parent_id, Some(trashed_parent.id));
+        // trashed together, so restored together
+        imported.restore_task(trashed_parent.id as i32, &origin()).await.unwrap();
+        assert_eq!(imported.get_subtasks(trashed_parent.id as i32).await.unwrap().len(), 1);
+
+        // the other formats only carry live tasks
+        for (format, name) in [(TransferFormat::Csv, "transfer_trashed_parent_csv"), (TransferFormat::Markdown, "transfer_trashed_parent_md")] {
+            let (_imported, report) = round_trip(&use_case, format, name).await;
+            let titles: Vec<&str> = report.tasks.iter().map(|task| task.title.as_str()).collect();
+            assert_eq!(titles, vec!["Water the plants"], "{:?}", format);
+        }
+    }
+
+    #[tokio::test]
+    async fn a_series_and_the_trash_survive_a_json_round_trip() {
+        let db = TestDb::new("transfer_series");
+        let use_case = db.use_case();
+        let dto = serde_json::from_value(json!({
+            "task": { "title": "Water the plants", "description": "the ones on the balcony", "is_done": false, "due_date": "2026-10-18T09:00:00Z" },
+            "recurrence": { "frequency": "weekly", "interval": 2, "count": 5 }
+        })).unwrap();
+        let series = use_case.create_recurring_task(dto, &origin()).await.unwrap();
+        let done = serde_json::from_value(json!({ "id": series.occurrences[0].id, "is_done": true })).unwrap();
+        use_case.update_task(series.occurrences[0].id as i32, done, &origin()).await.unwrap();
+        let trashed = create(&use_case, "Old errand", &["errands"]).await;
+        use_case.delete_task(trashed.id as i32, &origin()).await.unwrap();
+
+        let (imported, report) = round_trip(&use_case, TransferFormat::Json, "transfer_series_json").await;
+        assert_eq!((report.created, report.duplicates), (3, 0));
+        let imported = imported.use_case();
+
+        let occurrence = report.tasks.iter().find(|task| task.occurrence == Some(2)).unwrap();
+        let copy = imported.get_recurrence(occurrence.recurrence_id.unwrap() as i32).await.unwrap();
+        assert_eq!((copy.frequency, copy.interval, copy.count), (series.frequency, 2, Some(5)));
+        assert_eq!(copy.starts_at, series.starts_at);
+        let occurrences: Vec<(Option<i32>, bool)> = copy.occurrences.iter().map(|task| (task.occurrence, task.is_done)).collect();
+        assert_eq!(occurrences, vec![(Some(1), true), (Some(2), false)]);
+
+        let trash = imported.list_trash().await.unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].title, "Old errand");
+        assert_eq!(trash[0].tags, vec!["errands"]);
+        assert!(imported.get_all().await.unwrap().iter().all(|task| task.title != "Old errand"));
+
+        // importing the same file again finds every task
+        let content = use_case.export_tasks(TransferFormat::Json).await.unwrap();
+        let again = import(&imported, TransferFormat::Json, &content, false).await.unwrap();
+        assert_eq!((again.created, again.duplicates), (0, 3));
+    }
+
+    #[tokio::test]
+    async fn a_dry_run_counts_the_duplicates_the_import_finds() {
+        let file_tasks = json!([
+            { "id": 1, "title": "Pay rent", "description": "stored already", "is_done": false, "due_date": "2026-10-18T09:00:00Z" },
+            { "id": 2, "title": "Call the bank", "description": "new", "is_done": false },
+            { "id": 3, "parent_id": 2, "title": "Find the card", "description": "", "is_done": false },
+            { "id": 4, "title": "call  the BANK", "description": "repeated in the file", "is_done": false },
+            { "id": 5, "parent_id": 4, "title": "Find the card", "description": "", "is_done": false },
+            { "id": 6, "parent_id": 4, "title": "Ask for a new one", "description": "", "is_done": false }
+        ]);
+
+        for format in [TransferFormat::Json, TransferFormat::Csv, TransferFormat::Markdown] {
+            let db = TestDb::new("transfer_dry_run");
+            let use_case = db.use_case();
+            create(&use_case, "Pay rent", &[]).await;
+            let tasks: Vec<TransferTaskDto> = serde_json::from_value(file_tasks.clone()).unwrap();
+            let document = TransferDocumentDto::new(Vec::new(), tasks);
+            let content = match format {
+                TransferFormat::Json => json_format::write(&document),
+                TransferFormat::Csv => csv_format::write(&document),
+                _ => markdown_format::write(&document),
+            }.unwrap();
+
+            let dry = import(&use_case, format, &content, true).await.unwrap();
+            assert!(dry.tasks.is_empty());
+            assert_eq!(use_case.get_all().await.unwrap().len(), 1, "{:?}: a dry run stores nothing", format);
+            let real = import(&use_case, format, &content, false).await.unwrap();
+
+            let outcomes = |report: &ResImportReportDto| report.entries.iter().map(|entry| entry.outcome).collect::<Vec<_>>();
+            assert_eq!(outcomes(&dry), outcomes(&real), "{:?}", format);
+            assert_eq!((dry.created, dry.duplicates), (real.created, real.duplicates), "{:?}", format);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,22 @@
+use crate::domain::{dto::{audit_dto::ChangeOrigin, todo_dto::ResEntryTodoDto, trash_dto::ResPurgeDto}, error::domain_error::DomainResult};
+
+use super::todo_usecase::TodolistUseCase;
+
+
+impl TodolistUseCase {
+    pub async fn list_trash(&self) -> DomainResult<Vec<ResEntryTodoDto>> {
+        self.todo_repo.list_trash().await
+    }
+    pub async fn restore_task(&self, task_id: i32, origin: &ChangeOrigin) -> DomainResult<ResEntryTodoDto> {
+        let change = self.todo_repo.restore_task(task_id, origin.clone()).await?;
+        self.publish_recorded_change(&change).await;
+        Ok(change.task)
+    }
+    /// Trashed tasks are already gone from every view, purging them publishes no change.
+    pub async fn purge_task(&self, task_id: i32) -> DomainResult<ResPurgeDto> {
+        Ok(self.todo_repo.purge_task(task_id).await?.into())
+    }
+    pub async fn empty_trash(&self) -> DomainResult<ResPurgeDto> {
+        Ok(self.todo_repo.empty_trash().await?.into())
+    }
+}
\ No newline at end of file
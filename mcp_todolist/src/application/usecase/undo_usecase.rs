@@ -0,0 +1,42 @@
+use validator::Validate;
+
+use crate::domain::{dto::{audit_dto::ChangeOrigin, undo_dto::{ReqUndoDto, ResUndoDto}}, error::domain_error::{DomainError, DomainResult}};
+
+use super::todo_usecase::TodolistUseCase;
+
+
+impl TodolistUseCase {
+    /// Revert the last operations of an MCP session all-or-nothing.
+    ///
+    /// The reverts are audited by the repository in the same transaction, here they are only published and queued for the webhooks.
+    pub async fn undo_session(&self, session_id: &str, dto: ReqUndoDto, origin: &ChangeOrigin) -> DomainResult<ResUndoDto> {
+        dto.validate()?;
+        if session_id.trim().is_empty() {
+            return Err(DomainError::validation("session_id cannot be blank"));
+        }
+
+        let changes = self.todo_repo.undo_session(session_id.to_string(), dto.operations, origin.clone()).await?;
+        for change in &changes {
+            match (&change.after, &change.before) {
+                (Some(after), before) => {
+                    self.publish_task_change(change.action, after, before.as_ref());
+                    self.queue_webhook_deliveries(change.action, after).await;
+                }
+                (None, Some(before)) => {
+                    self.publish_task_change(change.action, before, None);
+                    self.queue_webhook_deliveries(change.action, before).await;
+                }
+                (None, None) => {}
+            }
+        }
+
+        let mut operations: Vec<&str> = changes.iter().map(|change| change.operation_id.as_str()).collect();
+        operations.sort_unstable();
+        operations.dedup();
+        Ok(ResUndoDto {
+            session_id: session_id.to_string(),
+            operations: operations.len() as u32,
+            changes,
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use validator::Validate;
+
+use crate::{application::webhook::webhook_target::check_webhook_url, domain::{dto::{event_dto::ChangeKind, todo_dto::ResEntryTodoDto, webhook_dto::{webhook_event_name, ReqCreateWebhookDto, ReqUpdateWebhookDto, ReqWebhookDeliveryLogDto, ResWebhookDeliveryDto, ResWebhookDto, ResWebhookPayloadDto, WebhookAttemptDto, WebhookDeliveryJob, DEFAULT_DELIVERY_LOG_LIMIT}}, error::domain_error::{DomainError, DomainResult}}};
+
+use super::todo_usecase::TodolistUseCase;
+
+
+impl TodolistUseCase {
+    /// Register a webhook, the response is the only one holding its secret.
+    pub async fn create_webhook(&self, dto: ReqCreateWebhookDto) -> DomainResult<ResWebhookDto> {
+        dto.validate()?;
+        check_webhook_url(&dto.url, self.allow_private_webhook_targets)?;
+        let secret = dto.secret.clone().unwrap_or_else(|| hex::encode(rand::random::<[u8; 32]>()));
+
+        let mut created = self.todo_repo.create_webhook(dto, secret.clone()).await?;
+        created.secret = Some(secret);
+        Ok(created)
+    }
+    pub async fn update_webhook(&self, webhook_id: i32, dto: ReqUpdateWebhookDto) -> DomainResult<ResWebhookDto> {
+        dto.validate()?;
+        if webhook_id != dto.id {
+            return Err(DomainError::validation(format!("Webhook id {} does not match payload id {}", webhook_id, dto.id)));
+        }
+        if let Some(url) = &dto.url {
+            check_webhook_url(url, self.allow_private_webhook_targets)?;
+        }
+        self.todo_repo.update_webhook(webhook_id, dto).await
+    }
+    pub async fn get_webhook(&self, webhook_id: i32) -> DomainResult<ResWebhookDto> {
+        self.todo_repo.get_webhook(webhook_id).await
+    }
+    pub async fn get_all_webhooks(&self) -> DomainResult<Vec<ResWebhookDto>> {
+        self.todo_repo.get_all_webhooks().await
+    }
+    pub async fn delete_webhook(&self, webhook_id: i32) -> DomainResult<()> {
+        self.todo_repo.delete_webhook(webhook_id).await
+    }
+    pub async fn get_webhook_deliveries(&self, dto: ReqWebhookDeliveryLogDto) -> DomainResult<Vec<ResWebhookDeliveryDto>> {
+        dto.validate()?;
+        self.todo_repo.get_webhook_deliveries(dto.webhook_id, dto.limit.unwrap_or(DEFAULT_DELIVERY_LOG_LIMIT)).await
+    }
+
+    /// Queue the change for the webhooks listening to it and wake the dispatcher.
+    ///
+    /// The change is already stored, so a failing queue write is logged rather than reported to the caller.
+    pub(super) async fn queue_webhook_deliveries(&self, kind: ChangeKind, task: &ResEntryTodoDto) {
+        let payload = ResWebhookPayloadDto {
+            event: webhook_event_name(kind),
+            occurred_at: Utc::now(),
+            task: task.clone(),
+        };
+        match self.todo_repo.enqueue_webhook_deliveries(kind, payload).await {
+            Ok(0) => {}
+            Ok(_) => self.webhooks_queued.notify_one(),
+            Err(e) => tracing::error!("Failed to queue webhook deliveries for task {}: {}", task.id, e),
+        }
+    }
+    /// Wait until deliveries are queued, a delivery queued while nobody waits wakes the next call at once.
+    pub async fn webhook_deliveries_queued(&self) {
+        self.webhooks_queued.notified().await
+    }
+    pub async fn get_due_webhook_deliveries(&self, now: DateTime<Utc>, limit: u32) -> DomainResult<Vec<WebhookDeliveryJob>> {
+        self.todo_repo.get_due_webhook_deliveries(now, limit).await
+    }
+    pub async fn record_webhook_attempt(&self, delivery_id: u32, attempt: WebhookAttemptDto) -> DomainResult<()> {
+        self.todo_repo.record_webhook_attempt(delivery_id as i32, attempt).await
+    }
+}
\ No newline at end of file
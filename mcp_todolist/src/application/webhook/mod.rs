@@ -0,0 +1,3 @@
+pub mod webhook_sender;
+pub mod webhook_dispatcher;
+pub mod webhook_target;
\ No newline at end of file
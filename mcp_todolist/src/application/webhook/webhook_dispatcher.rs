@@ -0,0 +1,272 @@
+use std::{sync::Arc, time::Duration};
+use chrono::{DateTime, Utc};
+use tokio::task::JoinHandle;
+
+use crate::{application::usecase::todo_usecase::TodolistUseCase, domain::dto::webhook_dto::{WebhookAttemptDto, WebhookDeliveryJob, WebhookDeliveryStatus}};
+
+use super::webhook_sender::WebhookSender;
+
+
+
+/// How often the queue is checked for retries when nothing new was queued.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// How many deliveries are loaded at a time.
+const BATCH_SIZE: u32 = 20;
+/// A delivery failing this many times is given up.
+const MAX_ATTEMPTS: u32 = 6;
+/// Wait after the first failure, doubled after each of the next ones.
+const FIRST_RETRY_DELAY: chrono::Duration = chrono::Duration::seconds(10);
+const MAX_RETRY_DELAY: chrono::Duration = chrono::Duration::hours(1);
+
+
+/// Wait before the next attempt once `attempts` attempts failed: 10s, 20s, 40s... at most an hour.
+fn retry_delay(attempts: u32) -> chrono::Duration {
+    let factor = 2i32.checked_pow(attempts.saturating_sub(1)).unwrap_or(i32::MAX);
+    FIRST_RETRY_DELAY.checked_mul(factor).map_or(MAX_RETRY_DELAY, |delay| delay.min(MAX_RETRY_DELAY))
+}
+
+
+/// Background job sending the queued webhook deliveries.
+///
+/// Deliveries live in the database until they succeed or run out of attempts,
+/// so the ones pending when the server stops are sent after the next start.
+pub struct WebhookDispatcher {
+    use_case: Arc<TodolistUseCase>,
+    sender: Arc<dyn WebhookSender>,
+}
+
+impl WebhookDispatcher {
+    pub fn new(use_case: Arc<TodolistUseCase>, sender: Arc<dyn WebhookSender>) -> Self {
+        Self { use_case, sender }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                self.run_once(Utc::now()).await;
+                tokio::select! {
+                    _ = self.use_case.webhook_deliveries_queued() => {}
+                    _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                }
+            }
+        })
+    }
+
+    /// Send every delivery due at `now`, a failed one is moved to its next attempt so it is not loaded again.
+    async fn run_once(&self, now: DateTime<Utc>) {
+        loop {
+            let due = match self.use_case.get_due_webhook_deliveries(now, BATCH_SIZE).await {
+                Ok(due) => due,
+                Err(e) => {
+                    tracing::error!("Failed to look up due webhook deliveries: {}", e);
+                    return;
+                }
+            };
+
+            for delivery in &due {
+                let attempt = self.attempt(delivery).await;
+                if let Err(e) = self.use_case.record_webhook_attempt(delivery.delivery_id, attempt).await {
+                    // left pending, the delivery is sent again on the next run
+                    tracing::error!("Failed to record the attempt of webhook delivery {}: {}", delivery.delivery_id, e);
+                    return;
+                }
+            }
+            if (due.len() as u32) < BATCH_SIZE {
+                return;
+            }
+        }
+    }
+
+    async fn attempt(&self, delivery: &WebhookDeliveryJob) -> WebhookAttemptDto {
+        let attempted_at = Utc::now();
+        let (response_status, error) = match self.sender.send(delivery).await {
+            Ok(code) if (200..300).contains(&code) => {
+                return WebhookAttemptDto {
+                    status: WebhookDeliveryStatus::Delivered,
+                    response_status: Some(code),
+                    error: None,
+                    attempted_at,
+                    next_attempt_at: None,
+                };
+            }
+            Ok(code) => (Some(code), format!("receiver answered with HTTP status {}", code)),
+            Err(e) => (None, format!("{:#}", e)),
+        };
+
+        let attempts = delivery.attempts + 1;
+        let next_attempt_at = (attempts < MAX_ATTEMPTS).then(|| attempted_at + retry_delay(attempts));
+        match next_attempt_at {
+            Some(next) => tracing::warn!("Webhook {} delivery {} ({}) failed, attempt {} of {}, retrying at {}: {}", delivery.webhook_id, delivery.delivery_id, delivery.event, attempts, MAX_ATTEMPTS, next, error),
+            None => tracing::error!("Webhook {} delivery {} ({}) failed {} times, giving up: {}", delivery.webhook_id, delivery.delivery_id, delivery.event, attempts, error),
+        }
+
+        WebhookAttemptDto {
+            status: if next_attempt_at.is_some() { WebhookDeliveryStatus::Pending } else { WebhookDeliveryStatus::Failed },
+            response_status,
+            error: Some(error),
+            attempted_at,
+            next_attempt_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::VecDeque, sync::Mutex};
+
+    use anyhow::{anyhow, Result};
+
+    use crate::{domain::dto::{audit_dto::ChangeOrigin, event_dto::ChangeKind, todo_dto::ReqCreateTodoDto, webhook_dto::{ReqCreateWebhookDto, ReqWebhookDeliveryLogDto, ResWebhookDeliveryDto}}, test_support::TestDb};
+
+    use super::*;
+
+
+    const SECRET: &str = "0123456789abcdef0123";
+
+
+    /// Answers with the scripted results in order, remembering the deliveries it was handed.
+    struct ScriptedSender {
+        answers: Mutex<VecDeque<Result<u16>>>,
+        sent: Mutex<Vec<WebhookDeliveryJob>>,
+    }
+
+    impl ScriptedSender {
+        fn new(answers: Vec<Result<u16>>) -> Arc<Self> {
+            Arc::new(Self { answers: Mutex::new(answers.into()), sent: Mutex::new(Vec::new()) })
+        }
+        fn sent(&self) -> Vec<WebhookDeliveryJob> {
+            self.sent.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl WebhookSender for ScriptedSender {
+        async fn send(&self, delivery: &WebhookDeliveryJob) -> Result<u16> {
+            self.sent.lock().unwrap().push(delivery.clone());
+            self.answers.lock().unwrap().pop_front().unwrap_or_else(|| Err(anyhow!("no answer scripted")))
+        }
+    }
+
+
+    /// Register a webhook listening to created tasks and create one, queueing a single delivery.
+    async fn queue_delivery(use_case: &TodolistUseCase) -> i32 {
+        let webhook = use_case.create_webhook(ReqCreateWebhookDto {
+            url: "https://hooks.example.com/todolist".to_string(),
+            events: vec![ChangeKind::Created],
+            secret: Some(SECRET.to_string()),
+            active: true,
+        }).await.unwrap();
+        use_case.create_task(ReqCreateTodoDto {
+            title: "Water the plants".to_string(),
+            description: "the ones on the balcony".to_string(),
+            is_done: false,
+            priority: Default::default(),
+            due_date: None,
+            remind_at: None,
+            list_id: None,
+            tags: Vec::new(),
+        }, &ChangeOrigin::http(None, "test")).await.unwrap();
+        webhook.id as i32
+    }
+
+    async fn delivery_log(use_case: &TodolistUseCase, webhook_id: i32) -> Vec<ResWebhookDeliveryDto> {
+        use_case.get_webhook_deliveries(ReqWebhookDeliveryLogDto { webhook_id, limit: None }).await.unwrap()
+    }
+
+    /// Far enough ahead for any retry to be due.
+    fn later() -> DateTime<Utc> {
+        Utc::now() + MAX_RETRY_DELAY + chrono::Duration::minutes(1)
+    }
+
+
+    #[test]
+    fn retry_delay_doubles_up_to_an_hour() {
+        assert_eq!(retry_delay(1), chrono::Duration::seconds(10));
+        assert_eq!(retry_delay(2), chrono::Duration::seconds(20));
+        assert_eq!(retry_delay(3), chrono::Duration::seconds(40));
+        assert_eq!(retry_delay(9), chrono::Duration::seconds(2560));
+        assert_eq!(retry_delay(10), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(u32::MAX), MAX_RETRY_DELAY);
+    }
+
+    #[tokio::test]
+    async fn failed_deliveries_are_retried_until_delivered() {
+        let db = TestDb::new("webhook_retry");
+        let use_case = db.use_case();
+        let webhook_id = queue_delivery(&use_case).await;
+        let sender = ScriptedSender::new(vec![Ok(500), Err(anyhow!("connection refused")), Ok(204)]);
+        let dispatcher = WebhookDispatcher::new(Arc::clone(&use_case), sender.clone());
+
+        let queued = delivery_log(&use_case, webhook_id).await;
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].event, "task.created");
+        assert_eq!(queued[0].status, WebhookDeliveryStatus::Pending);
+        assert_eq!(queued[0].attempts, 0);
+        assert_eq!(queued[0].payload["task"]["title"], "Water the plants");
+
+        dispatcher.run_once(Utc::now()).await;
+        let first = delivery_log(&use_case, webhook_id).await.remove(0);
+        assert_eq!(first.status, WebhookDeliveryStatus::Pending);
+        assert_eq!(first.attempts, 1);
+        assert_eq!(first.response_status, Some(500));
+        assert_eq!(first.last_error.as_deref(), Some("receiver answered with HTTP status 500"));
+        assert_eq!(first.next_attempt_at.unwrap() - first.last_attempt_at.unwrap(), retry_delay(1));
+        assert!(first.delivered_at.is_none());
+
+        // not due yet, nothing is sent
+        dispatcher.run_once(Utc::now()).await;
+        assert_eq!(sender.sent().len(), 1);
+
+        dispatcher.run_once(later()).await;
+        let second = delivery_log(&use_case, webhook_id).await.remove(0);
+        assert_eq!(second.status, WebhookDeliveryStatus::Pending);
+        assert_eq!(second.attempts, 2);
+        assert_eq!(second.response_status, None);
+        assert_eq!(second.last_error.as_deref(), Some("connection refused"));
+        assert_eq!(second.next_attempt_at.unwrap() - second.last_attempt_at.unwrap(), retry_delay(2));
+
+        dispatcher.run_once(later()).await;
+        let delivered = delivery_log(&use_case, webhook_id).await.remove(0);
+        assert_eq!(delivered.status, WebhookDeliveryStatus::Delivered);
+        assert_eq!(delivered.attempts, 3);
+        assert_eq!(delivered.response_status, Some(204));
+        assert!(delivered.next_attempt_at.is_none());
+        assert!(delivered.delivered_at.is_some());
+
+        let sent = sender.sent();
+        assert_eq!(sent.iter().map(|job| job.attempts).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert!(sent.iter().all(|job| job.delivery_id == delivered.id && job.secret == SECRET && job.event == "task.created"));
+
+        dispatcher.run_once(later()).await;
+        assert_eq!(sender.sent().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn deliveries_are_given_up_after_the_last_attempt() {
+        let db = TestDb::new("webhook_give_up");
+        let use_case = db.use_case();
+        let webhook_id = queue_delivery(&use_case).await;
+        let sender = ScriptedSender::new((0..MAX_ATTEMPTS).map(|_| Ok(503)).collect());
+        let dispatcher = WebhookDispatcher::new(Arc::clone(&use_case), sender.clone());
+
+        for attempt in 1..MAX_ATTEMPTS {
+            dispatcher.run_once(later()).await;
+            let delivery = delivery_log(&use_case, webhook_id).await.remove(0);
+            assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+            assert_eq!(delivery.attempts, attempt);
+            assert_eq!(delivery.next_attempt_at.unwrap() - delivery.last_attempt_at.unwrap(), retry_delay(attempt));
+        }
+
+        dispatcher.run_once(later()).await;
+        let failed = delivery_log(&use_case, webhook_id).await.remove(0);
+        assert_eq!(failed.status, WebhookDeliveryStatus::Failed);
+        assert_eq!(failed.attempts, MAX_ATTEMPTS);
+        assert_eq!(failed.response_status, Some(503));
+        assert_eq!(failed.last_error.as_deref(), Some("receiver answered with HTTP status 503"));
+        assert!(failed.next_attempt_at.is_none());
+        assert!(failed.delivered_at.is_none());
+
+        dispatcher.run_once(later()).await;
+        assert_eq!(sender.sent().len(), MAX_ATTEMPTS as usize);
+    }
+}
\ No newline at end of file
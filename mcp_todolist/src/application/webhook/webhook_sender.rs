@@ -0,0 +1,12 @@
+use anyhow::Result;
+
+use crate::domain::dto::webhook_dto::WebhookDeliveryJob;
+
+
+
+/// Transport of the webhook deliveries, signing included.
+#[async_trait::async_trait]
+pub trait WebhookSender: Send + Sync {
+    /// POST the delivery once, `Ok` holds the HTTP status the receiver answered with, successful or not.
+    async fn send(&self, delivery: &WebhookDeliveryJob) -> Result<u16>;
+}
\ No newline at end of file
@@ -0,0 +1,118 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use url::{Host, Url};
+
+use crate::domain::error::domain_error::{DomainError, DomainResult};
+
+
+
+/// Whether `ip` lies outside the loopback, link-local, private and other non-routable ranges.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_ipv4(mapped),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [first, second, ..] = ip.octets();
+    // 0.0.0.0/8 "this network" and 100.64.0.0/10 carrier-grade NAT
+    let reserved = first == 0 || (first == 100 && (second & 0xc0) == 64);
+    !(reserved
+        || ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation())
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        || ip.is_unique_local()
+        || ip.is_unicast_link_local())
+}
+
+
+/// Refuse webhook URLs the server must not POST to: anything but http(s) and,
+/// unless `allow_private` is set, loopback, link-local or private hosts.
+///
+/// Only literal addresses and `localhost` can be told apart here, host names are checked
+/// again by the sender once resolved.
+pub fn check_webhook_url(raw: &str, allow_private: bool) -> DomainResult<()> {
+    let url = Url::parse(raw).map_err(|_| DomainError::validation("url must be a valid URL"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(DomainError::validation("url must be an http or https URL"));
+    }
+    if allow_private {
+        return Ok(());
+    }
+
+    let internal = match url.host() {
+        Some(Host::Ipv4(ip)) => !is_public_ip(IpAddr::V4(ip)),
+        Some(Host::Ipv6(ip)) => !is_public_ip(IpAddr::V6(ip)),
+        Some(Host::Domain(name)) => {
+            let name = name.trim_end_matches('.').to_ascii_lowercase();
+            name == "localhost" || name.ends_with(".localhost")
+        }
+        None => true,
+    };
+    if internal {
+        return Err(DomainError::validation(format!(
+            "url must not target a loopback, link-local or private address : {}",
+            raw
+        )));
+    }
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn public_urls_are_accepted() {
+        for url in ["https://hooks.example.com/todo", "http://93.184.215.14:8080/hook", "https://[2606:4700::1111]/hook"] {
+            assert!(check_webhook_url(url, false).is_ok(), "{} was refused", url);
+        }
+    }
+
+    #[test]
+    fn internal_targets_are_refused() {
+        for url in [
+            "http://127.0.0.1:8000/v1/todo",
+            "http://2130706433/",
+            "http://localhost/hook",
+            "http://api.localhost./hook",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://10.0.0.7/hook",
+            "http://172.16.4.2/hook",
+            "http://192.168.1.1/hook",
+            "http://100.64.0.1/hook",
+            "http://0.0.0.0:8000/hook",
+            "http://[::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+        ] {
+            assert!(matches!(check_webhook_url(url, false), Err(DomainError::Validation(_))), "{} was accepted", url);
+        }
+    }
+
+    #[test]
+    fn internal_targets_can_be_allowed() {
+        assert!(check_webhook_url("http://127.0.0.1:8000/hook", true).is_ok());
+    }
+
+    #[test]
+    fn only_http_schemes_are_accepted() {
+        assert!(check_webhook_url("ftp://hooks.example.com/todo", true).is_err());
+        assert!(check_webhook_url("file:///etc/passwd", true).is_err());
+    }
+}
\ No newline at end of file
@@ -1,21 +1,130 @@
-use std::env;
+use std::{env, net::SocketAddr, time::Duration};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+
+/// How the embedded diesel migrations are handled at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationMode {
+    /// Apply pending migrations, then start the servers (default)
+    Run,
+    /// Apply pending migrations and exit (`--migrate-only`)
+    Only,
+    /// Do not touch the schema (`--no-migrate`)
+    Skip,
+}
+
+/// Which transports the MCP server listens on, set with `MCP_TRANSPORT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum McpTransport {
+    /// A single client over stdin/stdout (`stdio`, default)
+    Stdio,
+    /// Any number of remote clients over SSE on `MCP_SSE_BIND` (`sse`)
+    Sse,
+    /// stdio and SSE at the same time (`both`)
+    Both,
+}
+
+impl McpTransport {
+    pub fn uses_stdio(self) -> bool {
+        matches!(self, McpTransport::Stdio | McpTransport::Both)
+    }
+
+    pub fn uses_sse(self) -> bool {
+        matches!(self, McpTransport::Sse | McpTransport::Both)
+    }
+}
+
+const DEFAULT_MCP_SSE_BIND: &str = "127.0.0.1:8001";
+const DEFAULT_REMINDER_POLL_SECS: u64 = 30;
+const DEFAULT_REMINDER_LOOKBACK_HOURS: i64 = 24;
 
 #[derive(Debug, Clone)]
 pub struct DotEnvyConfig {
     pub database_url: String,
+    pub migration_mode: MigrationMode,
+    pub mcp_transport: McpTransport,
+    pub mcp_sse_bind: SocketAddr,
+    /// how often the reminder scheduler looks for due tasks, `None` when `REMINDER_POLL_SECS` is 0
+    pub reminder_poll_interval: Option<Duration>,
+    /// reminders missed by less than this, e.g. while the server was down, are still sent
+    pub reminder_lookback: chrono::Duration,
+    /// where the webhook sink POSTs reminders, no webhook sink without it
+    pub reminder_webhook_url: Option<String>,
+    /// let webhooks target loopback, link-local and private addresses, for receivers on the same machine or network
+    pub webhook_allow_private_targets: bool,
+}
+
+/// Read a numeric variable, falling back to `default` when it is not set.
+fn numeric_var<T: std::str::FromStr>(name: &str, default: T) -> Result<T> {
+    match dotenvy::var(name) {
+        Err(_) => Ok(default),
+        Ok(raw) => raw
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("{} must be a whole number, got {}", name, raw)),
+    }
+}
+
+/// Read a `true`/`false` variable, falling back to `default` when it is not set.
+fn flag_var(name: &str, default: bool) -> Result<bool> {
+    match dotenvy::var(name).as_deref().map(str::trim) {
+        Err(_) => Ok(default),
+        Ok("true" | "1") => Ok(true),
+        Ok("false" | "0") => Ok(false),
+        Ok(other) => Err(anyhow!("{} must be true or false, got {}", name, other)),
+    }
 }
 
 pub fn load() -> Result<DotEnvyConfig> {
     dotenvy::dotenv().ok();
 
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let migration_mode = if args.iter().any(|arg| arg == "--migrate-only") {
+        MigrationMode::Only
+    } else if args.iter().any(|arg| arg == "--no-migrate") {
+        MigrationMode::Skip
+    } else {
+        MigrationMode::Run
+    };
+
     let database_url = match dotenvy::var("DATABASE_URL") {
         Ok(url) => url,
-        Err(_) => env::args()
-            .nth(1)
+        Err(_) => args
+            .into_iter()
+            .find(|arg| !arg.starts_with("--"))
             .expect("DATABASE_URL not found in environment variables or command line arguments"),
     };
 
-    Ok(DotEnvyConfig { database_url })
+    let mcp_transport = match dotenvy::var("MCP_TRANSPORT").as_deref() {
+        Err(_) | Ok("stdio") => McpTransport::Stdio,
+        Ok("sse") => McpTransport::Sse,
+        Ok("both") => McpTransport::Both,
+        Ok(other) => return Err(anyhow!("MCP_TRANSPORT must be stdio, sse or both, got {}", other)),
+    };
+
+    let mcp_sse_bind = dotenvy::var("MCP_SSE_BIND")
+        .unwrap_or_else(|_| DEFAULT_MCP_SSE_BIND.to_string());
+    let mcp_sse_bind: SocketAddr = mcp_sse_bind
+        .parse()
+        .map_err(|e| anyhow!("MCP_SSE_BIND {} is not a valid socket address: {}", mcp_sse_bind, e))?;
+
+    let reminder_poll_secs: u64 = numeric_var("REMINDER_POLL_SECS", DEFAULT_REMINDER_POLL_SECS)?;
+    let reminder_poll_interval = (reminder_poll_secs > 0).then(|| Duration::from_secs(reminder_poll_secs));
+    let reminder_lookback_hours: i64 = numeric_var("REMINDER_LOOKBACK_HOURS", DEFAULT_REMINDER_LOOKBACK_HOURS)?;
+    let reminder_lookback = chrono::Duration::try_hours(reminder_lookback_hours)
+        .ok_or_else(|| anyhow!("REMINDER_LOOKBACK_HOURS {} is out of range", reminder_lookback_hours))?;
+    let reminder_webhook_url = dotenvy::var("REMINDER_WEBHOOK_URL").ok().filter(|url| !url.trim().is_empty());
+    let webhook_allow_private_targets = flag_var("WEBHOOK_ALLOW_PRIVATE_TARGETS", false)?;
+
+    Ok(DotEnvyConfig {
+        database_url,
+        migration_mode,
+        mcp_transport,
+        mcp_sse_bind,
+        reminder_poll_interval,
+        reminder_lookback,
+        reminder_webhook_url,
+        webhook_allow_private_targets,
+    })
 }
\ No newline at end of file
@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use super::{event_dto::ChangeKind, todo_dto::ResEntryTodoDto};
+
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum ChangeSource {
+    Http,
+    Mcp,
+}
+
+impl ChangeSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChangeSource::Http => "http",
+            ChangeSource::Mcp => "mcp",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "http" => Some(ChangeSource::Http),
+            "mcp" => Some(ChangeSource::Mcp),
+            _ => None,
+        }
+    }
+}
+
+
+/// Who made a change: the transport, the client and, for MCP, the session.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ChangeOrigin {
+    pub source: ChangeSource,
+    /// `X-Client-Name` or `User-Agent` over HTTP, `clientInfo.name` from the MCP `initialize` request
+    pub client_name: Option<String>,
+    /// one id per MCP connection, `None` over HTTP
+    pub session_id: Option<String>,
+    /// one id per MCP tool call or HTTP request, shared by every change it made
+    pub operation_id: Option<String>
+}
+
+impl ChangeOrigin {
+    pub fn http(client_name: Option<String>, operation_id: impl Into<String>) -> Self {
+        Self { source: ChangeSource::Http, client_name, session_id: None, operation_id: Some(operation_id.into()) }
+    }
+
+    pub fn mcp(client_name: Option<String>, session_id: impl Into<String>, operation_id: impl Into<String>) -> Self {
+        Self { source: ChangeSource::Mcp, client_name, session_id: Some(session_id.into()), operation_id: Some(operation_id.into()) }
+    }
+}
+
+
+/// A task change to append to the audit log.
+#[derive(Debug, Clone)]
+pub struct ReqTaskAuditDto {
+    pub task_id: u32,
+    pub action: ChangeKind,
+    pub before: Option<ResEntryTodoDto>,
+    pub after: Option<ResEntryTodoDto>,
+    pub origin: ChangeOrigin
+}
+
+
+/// A task change the repository stored along with its audit entry, handed back to be published once committed.
+#[derive(Debug, Clone)]
+pub struct TaskChangeDto {
+    pub kind: ChangeKind,
+    /// the task after the change, or as it was before being deleted
+    pub task: ResEntryTodoDto,
+    pub previous: Option<ResEntryTodoDto>
+}
+
+
+/// One entry of a task history, oldest first.
+#[derive(Deserialize, Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResTaskAuditDto {
+    pub id: u32,
+    pub task_id: u32,
+    pub action: ChangeKind,
+    /// the task before the change, `null` on create
+    #[schema(value_type = Option<Object>)]
+    pub before: Option<Value>,
+    /// the task after the change, `null` on delete
+    #[schema(value_type = Option<Object>)]
+    pub after: Option<Value>,
+    pub origin: ChangeOrigin,
+    #[schemars(with = "String")]
+    #[schema(value_type = String, format = DateTime)]
+    pub changed_at: DateTime<Utc>,
+    /// the entry written when this change was undone
+    pub reverted_by: Option<u32>
+}
\ No newline at end of file
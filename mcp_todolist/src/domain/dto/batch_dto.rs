@@ -0,0 +1,128 @@
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::dto::todo_dto::{ReqCreateTodoDto, ResEntryTodoDto};
+
+
+/// Most items (creates, completes and deletes together) a single batch may hold.
+pub const MAX_BATCH_ITEMS: usize = 100;
+
+
+/// Tasks to create, complete and delete in one all-or-nothing transaction,
+/// applied in that order.
+#[derive(Deserialize,Serialize, Debug, Clone, Default, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqBatchTodoDto {
+    #[serde(default)]
+    pub create: Vec<ReqCreateTodoDto>,
+    /// ids of the tasks to mark as done
+    #[serde(default)]
+    pub complete: Vec<i32>,
+    /// ids of the tasks to move to the trash, their subtasks go with them
+    #[serde(default)]
+    pub delete: Vec<i32>
+}
+
+impl ReqBatchTodoDto {
+    pub fn len(&self) -> usize {
+        self.create.len() + self.complete.len() + self.delete.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqCreateTasksDto {
+    pub tasks: Vec<ReqCreateTodoDto>
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqTaskIdsDto {
+    pub ids: Vec<i32>
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum BatchOperation {
+    Create,
+    Complete,
+    Delete,
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum BatchItemStatus {
+    Created,
+    Completed,
+    /// the task was done already, nothing changed
+    AlreadyDone,
+    Deleted,
+    /// this item is the reason the batch was rolled back
+    Failed,
+    /// the item itself was fine but another one failed, so nothing was applied
+    RolledBack,
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResBatchItemDto {
+    pub operation: BatchOperation,
+    /// position of the item inside its `create`, `complete` or `delete` array
+    pub index: usize,
+    pub task_id: Option<u32>,
+    pub status: BatchItemStatus,
+    pub error: Option<String>,
+    /// the task after the change, or as it was before being deleted
+    pub task: Option<ResEntryTodoDto>
+}
+
+impl ResBatchItemDto {
+    pub fn failed(operation: BatchOperation, index: usize, task_id: Option<i32>, error: impl ToString) -> Self {
+        ResBatchItemDto {
+            operation,
+            index,
+            task_id: task_id.map(|id| id as u32),
+            status: BatchItemStatus::Failed,
+            error: Some(error.to_string()),
+            task: None,
+        }
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.status == BatchItemStatus::Failed
+    }
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResBatchResultDto {
+    /// `false` when at least one item failed and the whole batch was rolled back
+    pub committed: bool,
+    pub items: Vec<ResBatchItemDto>
+}
+
+impl ResBatchResultDto {
+    /// Result of a batch that failed: items that did not fail themselves are marked rolled back.
+    pub fn rolled_back(mut items: Vec<ResBatchItemDto>) -> Self {
+        for item in items.iter_mut().filter(|item| !item.is_failed()) {
+            item.status = BatchItemStatus::RolledBack;
+            item.task = None;
+            // the id a rolled back insert got is free again
+            if item.operation == BatchOperation::Create {
+                item.task_id = None;
+            }
+        }
+        ResBatchResultDto { committed: false, items }
+    }
+}
\ No newline at end of file
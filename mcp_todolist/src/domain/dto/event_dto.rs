@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum ChangeKind {
+    Created,
+    Updated,
+    /// a task went from undone to done
+    Completed,
+    /// moved to the trash, or removed for good for lists and tags
+    Deleted,
+    /// a task came back from the trash
+    Restored,
+}
+
+impl ChangeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChangeKind::Created => "created",
+            ChangeKind::Updated => "updated",
+            ChangeKind::Completed => "completed",
+            ChangeKind::Deleted => "deleted",
+            ChangeKind::Restored => "restored",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "created" => Some(ChangeKind::Created),
+            "updated" => Some(ChangeKind::Updated),
+            "completed" => Some(ChangeKind::Completed),
+            "deleted" => Some(ChangeKind::Deleted),
+            "restored" => Some(ChangeKind::Restored),
+            _ => None,
+        }
+    }
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum ChangeEntity {
+    Task,
+    List,
+    Tag,
+}
+
+
+/// A mutation published by `TodolistUseCase` once it has been stored.
+#[derive(Deserialize, Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResChangeEventDto {
+    /// increasing sequence number, unique while the server runs
+    pub id: u64,
+    pub kind: ChangeKind,
+    pub entity: ChangeEntity,
+    pub entity_id: u32,
+    /// parent task when the change hit a subtask
+    pub parent_id: Option<u32>,
+    /// lists touched by the change, both the old and the new one on a move
+    pub list_ids: Vec<u32>,
+    /// tag names touched by the change, both the old and the new one on a rename
+    pub tags: Vec<String>,
+    #[schemars(with = "String")]
+    #[schema(value_type = String, format = DateTime)]
+    pub occurred_at: DateTime<Utc>
+}
\ No newline at end of file
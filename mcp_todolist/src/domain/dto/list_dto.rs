@@ -0,0 +1,75 @@
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+use crate::domain::{dto::todo_dto::double_option, entities::list_entity::{NewListEntity, UpdateListEntity}};
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqCreateListDto {
+    #[validate(length(min = 1, max = 100, message = "list name must be between 1 and 100 characters"))]
+    pub name: String,
+    pub description: Option<String>
+}
+
+impl From<ReqCreateListDto> for NewListEntity {
+    fn from(dto: ReqCreateListDto) -> Self {
+        NewListEntity {
+            name: dto.name.trim().to_string(),
+            description: dto.description
+        }
+    }
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqUpdateListDto {
+    pub id: i32,
+    #[validate(length(min = 1, max = 100, message = "list name must be between 1 and 100 characters"))]
+    pub name: Option<String>,
+    /// omit to keep the current description, send `null` to clear it
+    #[serde(default, deserialize_with = "double_option")]
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>)]
+    pub description: Option<Option<String>>
+}
+
+impl From<ReqUpdateListDto> for UpdateListEntity {
+    fn from(dto: ReqUpdateListDto) -> Self {
+        UpdateListEntity {
+            name: dto.name.map(|name| name.trim().to_string()),
+            description: dto.description
+        }
+    }
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResEntryListDto {
+    pub id: u32,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: String,
+    pub updated_at: String
+}
+
+
+/// Task counters of a single list.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResListCountDto {
+    pub list_id: u32,
+    pub total: i32,
+    pub done: i32,
+    pub undone: i32
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct GetListById {
+    pub id: u32
+}
\ No newline at end of file
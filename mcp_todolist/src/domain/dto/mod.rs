@@ -1 +1,15 @@
-pub mod todo_dto;
\ No newline at end of file
+pub mod todo_dto;
+pub mod query_dto;
+pub mod tag_dto;
+pub mod list_dto;
+pub mod subtask_dto;
+pub mod event_dto;
+pub mod batch_dto;
+pub mod trash_dto;
+pub mod audit_dto;
+pub mod undo_dto;
+pub mod search_dto;
+pub mod recurrence_dto;
+pub mod reminder_dto;
+pub mod webhook_dto;
+pub mod transfer_dto;
\ No newline at end of file
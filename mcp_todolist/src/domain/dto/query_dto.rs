@@ -0,0 +1,278 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use rmcp::schemars;
+use rocket::{FromForm, FromFormField};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::domain::{dto::todo_dto::{ResEntryTodoDto, TaskPriority}, error::domain_error::{DomainError, DomainResult}};
+
+
+pub const DEFAULT_PAGE_LIMIT: i64 = 50;
+pub const MAX_PAGE_LIMIT: i64 = 200;
+
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq, schemars::JsonSchema, ToSchema, FromFormField)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum TodoSortField {
+    #[default]
+    #[field(value = "created_at")]
+    CreatedAt,
+    #[field(value = "updated_at")]
+    UpdatedAt,
+    #[field(value = "title")]
+    Title,
+    #[field(value = "id")]
+    Id,
+    #[field(value = "priority")]
+    Priority,
+    #[field(value = "due_date")]
+    DueDate,
+}
+
+impl TodoSortField {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TodoSortField::CreatedAt => "created_at",
+            TodoSortField::UpdatedAt => "updated_at",
+            TodoSortField::Title => "title",
+            TodoSortField::Id => "id",
+            TodoSortField::Priority => "priority",
+            TodoSortField::DueDate => "due_date",
+        }
+    }
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq, schemars::JsonSchema, ToSchema, FromFormField)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum SortDirection {
+    #[field(value = "asc")]
+    Asc,
+    #[default]
+    #[field(value = "desc")]
+    Desc,
+}
+
+impl SortDirection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SortDirection::Asc => "asc",
+            SortDirection::Desc => "desc",
+        }
+    }
+}
+
+
+/// Filters, sorting and paging used to list tasks.
+///
+/// `cursor` is the opaque `next_cursor` returned by a previous page and takes
+/// precedence over `offset` when both are given, it only holds for the sort it was issued with.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct TodoQuery {
+    pub is_done: Option<bool>,
+    pub priority: Option<TaskPriority>,
+    /// only tasks belonging to this list
+    pub list_id: Option<i32>,
+    /// only tasks carrying every listed tag
+    pub tags: Option<Vec<String>>,
+    /// case-insensitive text searched in title and description
+    pub search: Option<String>,
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub created_from: Option<DateTime<Utc>>,
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub created_to: Option<DateTime<Utc>>,
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub updated_from: Option<DateTime<Utc>>,
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub updated_to: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub sort_by: TodoSortField,
+    #[serde(default)]
+    pub sort_dir: SortDirection,
+    #[validate(range(min = 1, max = 200, message = "limit must be between 1 and 200"))]
+    pub limit: Option<i64>,
+    #[validate(range(min = 0, message = "offset cannot be negative"))]
+    pub offset: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+impl TodoQuery {
+    pub fn page_limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT)
+    }
+
+    /// Resolve the row offset from `cursor` first, then `offset`.
+    pub fn page_offset(&self) -> DomainResult<i64> {
+        match &self.cursor {
+            Some(cursor) => decode_cursor(cursor, self.sort_by, self.sort_dir),
+            None => Ok(self.offset.unwrap_or(0).max(0)),
+        }
+    }
+}
+
+
+/// The offset of the next page, tied to the sort so the cursor cannot land in the middle of another ordering.
+pub fn encode_cursor(offset: i64, sort_by: TodoSortField, sort_dir: SortDirection) -> String {
+    format!("{}:{}:{}", sort_by.as_str(), sort_dir.as_str(), offset)
+}
+
+pub fn decode_cursor(cursor: &str, sort_by: TodoSortField, sort_dir: SortDirection) -> DomainResult<i64> {
+    let invalid = || DomainError::validation(format!("Invalid cursor : {}", cursor));
+
+    let mut parts = cursor.split(':');
+    let (Some(field), Some(dir), Some(raw_offset), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+        return Err(invalid());
+    };
+    let offset = raw_offset.parse::<i64>().ok().filter(|offset| *offset >= 0).ok_or_else(invalid)?;
+
+    if field != sort_by.as_str() || dir != sort_dir.as_str() {
+        return Err(DomainError::validation(format!(
+            "The cursor was issued for sort_by={} sort_dir={}, not sort_by={} sort_dir={}",
+            field, dir, sort_by.as_str(), sort_dir.as_str()
+        )));
+    }
+    Ok(offset)
+}
+
+
+/// Query string accepted by `GET /v1/todo`.
+///
+/// Dates accept RFC 3339 (`2025-05-09T10:45:00Z`) or a plain date (`2025-05-09`).
+#[derive(Debug, Clone, Default, FromForm, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TodoQueryParams {
+    pub is_done: Option<bool>,
+    pub priority: Option<TaskPriority>,
+    pub list_id: Option<i32>,
+    /// repeat to require several tags, e.g. `tags=work&tags=urgent`
+    pub tags: Vec<String>,
+    pub search: Option<String>,
+    pub created_from: Option<String>,
+    pub created_to: Option<String>,
+    pub updated_from: Option<String>,
+    pub updated_to: Option<String>,
+    pub sort_by: Option<TodoSortField>,
+    pub sort_dir: Option<SortDirection>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+impl TryFrom<TodoQueryParams> for TodoQuery {
+    type Error = DomainError;
+
+    fn try_from(params: TodoQueryParams) -> Result<Self, Self::Error> {
+        Ok(TodoQuery {
+            is_done: params.is_done,
+            priority: params.priority,
+            list_id: params.list_id,
+            tags: (!params.tags.is_empty()).then_some(params.tags),
+            search: params.search,
+            created_from: parse_datetime(params.created_from.as_deref(), "created_from")?,
+            created_to: parse_end_datetime(params.created_to.as_deref(), "created_to")?,
+            updated_from: parse_datetime(params.updated_from.as_deref(), "updated_from")?,
+            updated_to: parse_end_datetime(params.updated_to.as_deref(), "updated_to")?,
+            sort_by: params.sort_by.unwrap_or_default(),
+            sort_dir: params.sort_dir.unwrap_or_default(),
+            limit: params.limit,
+            offset: params.offset,
+            cursor: params.cursor,
+        })
+    }
+}
+
+pub fn parse_datetime(raw: Option<&str>, field: &str) -> DomainResult<Option<DateTime<Utc>>> {
+    parse_bound(raw, field, NaiveTime::MIN)
+}
+
+/// Like `parse_datetime` for an inclusive upper bound, a plain `YYYY-MM-DD` covers the whole day.
+pub fn parse_end_datetime(raw: Option<&str>, field: &str) -> DomainResult<Option<DateTime<Utc>>> {
+    parse_bound(raw, field, NaiveTime::from_hms_nano_opt(23, 59, 59, 999_999_999).expect("a valid time of day"))
+}
+
+fn parse_bound(raw: Option<&str>, field: &str, time_of_day: NaiveTime) -> DomainResult<Option<DateTime<Utc>>> {
+    let Some(raw) = raw else {
+        return Ok(None);
+    };
+
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(Some(date_time.with_timezone(&Utc)));
+    }
+
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .map(|date| Some(date.and_time(time_of_day).and_utc()))
+        .map_err(|_| DomainError::validation(format!("{} must be an RFC 3339 date time or YYYY-MM-DD", field)))
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResTodoPageDto {
+    pub items: Vec<ResEntryTodoDto>,
+    /// total number of tasks matching the filters, ignoring paging
+    pub total: i64,
+    /// pass back as `cursor` to fetch the next page, `null` on the last page
+    pub next_cursor: Option<String>,
+}
+
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+
+    #[test]
+    fn a_date_only_lower_bound_starts_the_day() {
+        let from = parse_datetime(Some("2025-05-09"), "created_from").unwrap();
+        assert_eq!(from, Some(Utc.with_ymd_and_hms(2025, 5, 9, 0, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn a_date_only_upper_bound_covers_the_whole_day() {
+        let to = parse_end_datetime(Some("2025-05-09"), "created_to").unwrap().unwrap();
+        assert!(to > Utc.with_ymd_and_hms(2025, 5, 9, 23, 59, 59).unwrap());
+        assert!(to < Utc.with_ymd_and_hms(2025, 5, 10, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn a_date_time_upper_bound_is_kept_as_given() {
+        let to = parse_end_datetime(Some("2025-05-09T10:45:00+02:00"), "created_to").unwrap();
+        assert_eq!(to, Some(Utc.with_ymd_and_hms(2025, 5, 9, 8, 45, 0).unwrap()));
+    }
+
+    #[test]
+    fn a_malformed_bound_is_a_validation_error() {
+        assert!(matches!(parse_end_datetime(Some("09/05/2025"), "created_to"), Err(DomainError::Validation(_))));
+    }
+
+    #[test]
+    fn a_cursor_reads_back_under_its_own_sort() {
+        let cursor = encode_cursor(40, TodoSortField::Title, SortDirection::Asc);
+        assert_eq!(decode_cursor(&cursor, TodoSortField::Title, SortDirection::Asc).unwrap(), 40);
+    }
+
+    #[test]
+    fn a_cursor_is_rejected_under_another_sort() {
+        let cursor = encode_cursor(40, TodoSortField::Title, SortDirection::Asc);
+        assert!(matches!(decode_cursor(&cursor, TodoSortField::Id, SortDirection::Asc), Err(DomainError::Validation(_))));
+        assert!(matches!(decode_cursor(&cursor, TodoSortField::Title, SortDirection::Desc), Err(DomainError::Validation(_))));
+    }
+
+    #[test]
+    fn a_tampered_cursor_is_a_validation_error() {
+        for cursor in ["", "o40", "title:asc", "title:asc:-1", "title:asc:forty", "title:asc:40:1"] {
+            assert!(
+                matches!(decode_cursor(cursor, TodoSortField::Title, SortDirection::Asc), Err(DomainError::Validation(_))),
+                "cursor {:?} was accepted", cursor
+            );
+        }
+    }
+}
\ No newline at end of file
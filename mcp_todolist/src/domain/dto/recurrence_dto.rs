@@ -0,0 +1,160 @@
+use chrono::{DateTime, Duration, Months, Utc};
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::todo_dto::{ReqCreateTodoDto, ResEntryTodoDto};
+
+
+/// Unit a series advances by, `interval` of them at a time.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum RecurrenceFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl RecurrenceFrequency {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RecurrenceFrequency::Daily => "daily",
+            RecurrenceFrequency::Weekly => "weekly",
+            RecurrenceFrequency::Monthly => "monthly",
+            RecurrenceFrequency::Yearly => "yearly",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "daily" => Some(RecurrenceFrequency::Daily),
+            "weekly" => Some(RecurrenceFrequency::Weekly),
+            "monthly" => Some(RecurrenceFrequency::Monthly),
+            "yearly" => Some(RecurrenceFrequency::Yearly),
+            _ => None,
+        }
+    }
+
+    /// `start` moved forward by `units` of this frequency.
+    ///
+    /// Months are always counted from `start`, so a series starting on the 31st
+    /// falls on the last day of shorter months and is back on the 31st afterwards.
+    pub fn advance(self, start: DateTime<Utc>, units: u32) -> Option<DateTime<Utc>> {
+        match self {
+            RecurrenceFrequency::Daily => start.checked_add_signed(Duration::days(units as i64)),
+            RecurrenceFrequency::Weekly => start.checked_add_signed(Duration::weeks(units as i64)),
+            RecurrenceFrequency::Monthly => start.checked_add_months(Months::new(units)),
+            RecurrenceFrequency::Yearly => start.checked_add_months(Months::new(units.checked_mul(12)?)),
+        }
+    }
+}
+
+
+fn default_interval() -> u32 {
+    1
+}
+
+
+/// When a series repeats and when it ends, modelled on iCalendar RRULE (FREQ, INTERVAL, UNTIL, COUNT).
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqRecurrenceRuleDto {
+    pub frequency: RecurrenceFrequency,
+    /// repeat every `interval` frequency units, 1 by default (e.g. `weekly` with 2 is every other week)
+    #[serde(default = "default_interval")]
+    #[validate(range(min = 1, max = 366, message = "interval must be between 1 and 366"))]
+    pub interval: u32,
+    /// no occurrence is due after this date
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub until: Option<DateTime<Utc>>,
+    /// the series ends after this many occurrences, the first task included
+    #[validate(range(min = 1, max = 1000, message = "count must be between 1 and 1000"))]
+    pub count: Option<u32>
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqCreateRecurringTaskDto {
+    /// the first occurrence, its due date (or now when it has none) anchors the series
+    #[validate(nested)]
+    pub task: ReqCreateTodoDto,
+    #[validate(nested)]
+    pub recurrence: ReqRecurrenceRuleDto
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct GetRecurrenceById {
+    pub id: u32
+}
+
+
+/// A series of recurring tasks.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResRecurrenceDto {
+    pub id: u32,
+    pub frequency: RecurrenceFrequency,
+    pub interval: u32,
+    /// due date of the first occurrence, the schedule is counted from here
+    #[schemars(with = "String")]
+    #[schema(value_type = String, format = DateTime)]
+    pub starts_at: DateTime<Utc>,
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub until: Option<DateTime<Utc>>,
+    pub count: Option<u32>,
+    /// set once the series was stopped, its tasks are kept
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub stopped_at: Option<DateTime<Utc>>,
+    /// when the next occurrence is due, `null` once the series is over
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub next_due: Option<DateTime<Utc>>,
+    /// tasks of the series that are not in the trash, in order
+    pub occurrences: Vec<ResEntryTodoDto>
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+
+    fn at(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 9, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn months_are_counted_from_the_start() {
+        let start = at(2026, 1, 31);
+        assert_eq!(RecurrenceFrequency::Monthly.advance(start, 1), Some(at(2026, 2, 28)));
+        assert_eq!(RecurrenceFrequency::Monthly.advance(start, 2), Some(at(2026, 3, 31)));
+        assert_eq!(RecurrenceFrequency::Monthly.advance(start, 3), Some(at(2026, 4, 30)));
+        assert_eq!(RecurrenceFrequency::Monthly.advance(at(2028, 1, 31), 1), Some(at(2028, 2, 29)));
+    }
+
+    #[test]
+    fn a_leap_day_falls_back_to_february_28() {
+        assert_eq!(RecurrenceFrequency::Yearly.advance(at(2028, 2, 29), 1), Some(at(2029, 2, 28)));
+        assert_eq!(RecurrenceFrequency::Yearly.advance(at(2028, 2, 29), 4), Some(at(2032, 2, 29)));
+    }
+
+    #[test]
+    fn days_and_weeks_keep_the_time_of_day() {
+        assert_eq!(RecurrenceFrequency::Daily.advance(at(2026, 12, 31), 1), Some(at(2027, 1, 1)));
+        assert_eq!(RecurrenceFrequency::Weekly.advance(at(2026, 10, 18), 2), Some(at(2026, 11, 1)));
+    }
+
+    #[test]
+    fn advancing_out_of_range_gives_none() {
+        assert_eq!(RecurrenceFrequency::Yearly.advance(at(2026, 1, 1), u32::MAX), None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::todo_dto::ResEntryTodoDto;
+
+
+/// What a notification is about.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum ReminderKind {
+    /// the `remind_at` of the task was reached
+    Reminder,
+    /// the `due_date` of the task was reached
+    Due,
+}
+
+impl ReminderKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReminderKind::Reminder => "reminder",
+            ReminderKind::Due => "due",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "reminder" => Some(ReminderKind::Reminder),
+            "due" => Some(ReminderKind::Due),
+            _ => None,
+        }
+    }
+}
+
+
+/// A reminder or due-date notification, sent once per task and date.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResReminderDto {
+    pub kind: ReminderKind,
+    /// the `remind_at` or `due_date` that was reached
+    #[schemars(with = "String")]
+    #[schema(value_type = String, format = DateTime)]
+    pub fire_at: DateTime<Utc>,
+    #[schemars(with = "String")]
+    #[schema(value_type = String, format = DateTime)]
+    pub fired_at: DateTime<Utc>,
+    pub task: ResEntryTodoDto
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::todo_dto::ResEntryTodoDto;
+
+
+/// Matches returned when the request does not ask for a number.
+pub const DEFAULT_SEARCH_LIMIT: u32 = 10;
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqSearchTodoDto {
+    /// words to look for in titles and descriptions, every word has to match
+    #[validate(length(min = 1, max = 200, message = "query must be between 1 and 200 characters"))]
+    pub query: String,
+    /// how many matches to return, 10 by default
+    #[validate(range(min = 1, max = 50, message = "limit must be between 1 and 50"))]
+    pub limit: Option<u32>
+}
+
+
+/// A task matching a search, best matches come first.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResSearchHitDto {
+    /// relevance of the match, higher is better, a title match weighs more than a description match
+    pub score: f64,
+    /// parts of the title and description around the matches, matched text is wrapped in `**`
+    pub title_snippet: String,
+    pub description_snippet: String,
+    pub task: ResEntryTodoDto
+}
\ No newline at end of file
@@ -0,0 +1,44 @@
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::todo_dto::single_line_title;
+
+
+fn default_true() -> bool {
+    true
+}
+
+
+/// A checklist step added at the end of its parent task.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqCreateSubtaskDto {
+    pub parent_id: i32,
+    #[validate(length(min = 1, max = 100, message = "title must be between 1 and 100 characters"), custom(function = "single_line_title"))]
+    pub title: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub is_done: bool
+}
+
+
+/// The full, new order of the subtasks of a task, first to last.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqReorderSubtasksDto {
+    pub parent_id: i32,
+    #[validate(length(min = 1, message = "subtask_ids cannot be empty"))]
+    pub subtask_ids: Vec<i32>
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqTickSubtaskDto {
+    pub subtask_id: i32,
+    /// `true` (default) ticks the subtask off, `false` reopens it
+    #[serde(default = "default_true")]
+    pub is_done: bool
+}
\ No newline at end of file
@@ -0,0 +1,82 @@
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+use crate::domain::{dto::todo_dto::double_option, entities::tag_entity::{NewTagEntity, UpdateTagEntity}};
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqCreateTagDto {
+    #[validate(length(min = 1, max = 50, message = "tag name must be between 1 and 50 characters"))]
+    pub name: String,
+    #[validate(length(max = 20, message = "color cannot be longer than 20 characters"))]
+    pub color: Option<String>
+}
+
+impl From<ReqCreateTagDto> for NewTagEntity {
+    fn from(dto: ReqCreateTagDto) -> Self {
+        NewTagEntity {
+            name: dto.name.trim().to_string(),
+            color: dto.color
+        }
+    }
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqUpdateTagDto {
+    pub id: i32,
+    #[validate(length(min = 1, max = 50, message = "tag name must be between 1 and 50 characters"))]
+    pub name: Option<String>,
+    /// omit to keep the current color, send `null` to clear it
+    #[serde(default, deserialize_with = "double_option")]
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>)]
+    pub color: Option<Option<String>>
+}
+
+impl From<ReqUpdateTagDto> for UpdateTagEntity {
+    fn from(dto: ReqUpdateTagDto) -> Self {
+        UpdateTagEntity {
+            name: dto.name.map(|name| name.trim().to_string()),
+            color: dto.color
+        }
+    }
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResEntryTagDto {
+    pub id: u32,
+    pub name: String,
+    pub color: Option<String>
+}
+
+
+/// Tags to attach to or detach from a task, by name.
+/// Attaching a name that does not exist yet creates the tag.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqTaskTagsDto {
+    pub task_id: i32,
+    #[validate(length(min = 1, message = "at least one tag is required"))]
+    pub tags: Vec<String>
+}
+
+
+/// Body of `POST /v1/todo/{todo_id}/tags`, the task id comes from the path.
+#[derive(Deserialize,Serialize, Debug, Clone, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqTagNamesDto {
+    pub tags: Vec<String>
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct GetTagById {
+    pub id: u32
+}
\ No newline at end of file
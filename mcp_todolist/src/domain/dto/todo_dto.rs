@@ -1,18 +1,107 @@
+use chrono::{DateTime, Utc};
 use rmcp::schemars;
-use serde::{Deserialize, Serialize};
+use rocket::FromFormField;
+use serde::{Deserialize, Deserializer, Serialize};
 use utoipa::ToSchema;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 use crate::domain::entities::todo_entity::{ NewTodoEntity, UpdateTodoEntity};
 
 
+/// Priority of a task, stored as an integer so it sorts naturally.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, schemars::JsonSchema, ToSchema, FromFormField)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum TaskPriority {
+    #[field(value = "low")]
+    Low,
+    #[default]
+    #[field(value = "normal")]
+    Normal,
+    #[field(value = "high")]
+    High,
+    #[field(value = "urgent")]
+    Urgent,
+}
+
+impl TaskPriority {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskPriority::Low => "low",
+            TaskPriority::Normal => "normal",
+            TaskPriority::High => "high",
+            TaskPriority::Urgent => "urgent",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "low" => Some(TaskPriority::Low),
+            "normal" => Some(TaskPriority::Normal),
+            "high" => Some(TaskPriority::High),
+            "urgent" => Some(TaskPriority::Urgent),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(self) -> i32 {
+        match self {
+            TaskPriority::Low => 0,
+            TaskPriority::Normal => 1,
+            TaskPriority::High => 2,
+            TaskPriority::Urgent => 3,
+        }
+    }
+
+    pub fn from_i32(value: i32) -> Self {
+        match value {
+            i32::MIN..=0 => TaskPriority::Low,
+            1 => TaskPriority::Normal,
+            2 => TaskPriority::High,
+            _ => TaskPriority::Urgent,
+        }
+    }
+}
+
+
+/// Lets an update payload tell "field missing" (`None`) apart from "field set to null" (`Some(None)`).
+pub(crate) fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Titles are a single line, a line break would split the task in a Markdown checklist.
+pub(crate) fn single_line_title(title: &str) -> Result<(), ValidationError> {
+    if title.chars().any(char::is_control) {
+        return Err(ValidationError::new("single_line").with_message("title cannot contain line breaks or other control characters".into()));
+    }
+    Ok(())
+}
+
+
 #[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
 #[serde(crate = "rocket::serde")]
 pub struct ReqCreateTodoDto{
-    #[validate(length(min = 1, message = "title cannot be empty"))]
+    #[validate(length(min = 1, max = 100, message = "title must be between 1 and 100 characters"), custom(function = "single_line_title"))]
     pub title: String,
     #[validate(length(min = 1, message = "description cannot be empty"))]
     pub description: String,
-    pub is_done: bool
+    pub is_done: bool,
+    #[serde(default)]
+    pub priority: TaskPriority,
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub due_date: Option<DateTime<Utc>>,
+    /// when to send a reminder about the task
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub remind_at: Option<DateTime<Utc>>,
+    /// list the task belongs to, `null` keeps it out of every list
+    pub list_id: Option<i32>,
+    /// tag names to attach, missing tags are created on the fly
+    #[serde(default)]
+    pub tags: Vec<String>
 }
 
 impl From<ReqCreateTodoDto> for NewTodoEntity {
@@ -20,19 +109,45 @@ impl From<ReqCreateTodoDto> for NewTodoEntity {
         NewTodoEntity { 
             title: dto.title, 
             description: dto.description, 
-            is_done: dto.is_done
+            is_done: dto.is_done,
+            priority: dto.priority.as_i32(),
+            due_date: dto.due_date.map(|due| due.naive_utc()),
+            list_id: dto.list_id,
+            parent_id: None,
+            position: 0,
+            remind_at: dto.remind_at.map(|remind| remind.naive_utc())
         }
     }
 }
 
 
-#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
 #[serde(crate = "rocket::serde")]
 pub struct ReqUpdateTodoDto{
     pub id: i32,
+    #[validate(length(min = 1, max = 100, message = "title must be between 1 and 100 characters"), custom(function = "single_line_title"))]
     pub title: Option<String>,
+    #[validate(length(min = 1, message = "description cannot be empty"))]
     pub description: Option<String>,
-    pub is_done: Option<bool>
+    pub is_done: Option<bool>,
+    pub priority: Option<TaskPriority>,
+    /// omit to keep the current due date, send `null` to clear it
+    #[serde(default, deserialize_with = "double_option")]
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub due_date: Option<Option<DateTime<Utc>>>,
+    /// omit to keep the current list, send `null` to take the task out of its list
+    #[serde(default, deserialize_with = "double_option")]
+    #[schemars(with = "Option<i32>")]
+    #[schema(value_type = Option<i32>)]
+    pub list_id: Option<Option<i32>>,
+    /// omit to keep the current reminder, send `null` to cancel it
+    #[serde(default, deserialize_with = "double_option")]
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub remind_at: Option<Option<DateTime<Utc>>>,
+    /// the `version` the change is based on, the update is refused with a conflict when the task has moved on
+    pub expected_version: Option<i32>
 }
 
 impl From<ReqUpdateTodoDto> for UpdateTodoEntity {
@@ -40,7 +155,11 @@ impl From<ReqUpdateTodoDto> for UpdateTodoEntity {
         UpdateTodoEntity { 
             title: dto.title, 
             description: dto.description, 
-            is_done: dto.is_done 
+            is_done: dto.is_done,
+            priority: dto.priority.map(TaskPriority::as_i32),
+            due_date: dto.due_date.map(|due| due.map(|due| due.naive_utc())),
+            list_id: dto.list_id,
+            remind_at: dto.remind_at.map(|remind| remind.map(|remind| remind.naive_utc()))
         }
     }
 }
@@ -52,8 +171,51 @@ pub struct ResEntryTodoDto{
     pub title: String,
     pub description: String,
     pub is_done: bool,
-    pub created_at: String,
-    pub updated_at: String
+    pub priority: TaskPriority,
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub due_date: Option<DateTime<Utc>>,
+    pub list_id: Option<u32>,
+    /// set on subtasks, the task they belong to
+    pub parent_id: Option<u32>,
+    /// order of a subtask inside its parent
+    pub position: i32,
+    /// set while the task sits in the trash
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// bumped on every change, send it back as `expected_version` (or `If-Match` over HTTP)
+    pub version: i32,
+    /// set on recurring tasks, the series they belong to and their place in it (1 for the first task)
+    pub recurrence_id: Option<u32>,
+    pub occurrence: Option<i32>,
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub remind_at: Option<DateTime<Utc>>,
+    pub tags: Vec<String>,
+    /// completion roll-up, `subtasks_done` of `subtasks_total` subtasks are done
+    pub subtasks_done: i32,
+    pub subtasks_total: i32,
+    #[schemars(with = "String")]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTime<Utc>,
+    /// moves on every change, together with `version`
+    #[schemars(with = "String")]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTime<Utc>
+}
+
+
+/// Counters over every task, served as the `todo://stats` MCP resource.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResTodoStatsDto {
+    pub total: i32,
+    pub done: i32,
+    pub undone: i32,
+    pub overdue: i32,
+    /// undone tasks due on the current UTC day
+    pub due_today: i32
 }
 
 
@@ -61,4 +223,22 @@ pub struct ResEntryTodoDto{
 #[serde(crate = "rocket::serde")]
 pub struct GetTaskById {
     pub id: u32
+}
+
+
+/// Which calendar day counts as today, UTC unless an offset is given.
+#[derive(Deserialize,Serialize, Debug, Clone, Default, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct DueToday {
+    /// minutes east of UTC of the caller, e.g. 420 for UTC+7 or -300 for UTC-5
+    #[validate(range(min = -840, max = 840, message = "utc_offset_minutes must be between -840 and 840"))]
+    pub utc_offset_minutes: Option<i32>
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct DueWithinDays {
+    #[validate(range(min = 1, max = 365, message = "days must be between 1 and 365"))]
+    pub days: i64
 }
\ No newline at end of file
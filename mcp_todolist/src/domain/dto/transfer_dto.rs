@@ -0,0 +1,242 @@
+use chrono::{DateTime, Utc};
+use rmcp::schemars;
+use rocket::FromFormField;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{recurrence_dto::RecurrenceFrequency, todo_dto::{ResEntryTodoDto, TaskPriority}};
+
+
+/// Version written in JSON exports, bumped when the layout changes.
+pub const TRANSFER_FORMAT_VERSION: u32 = 2;
+
+
+/// File format of an export or import.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq, schemars::JsonSchema, ToSchema, FromFormField)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum TransferFormat {
+    /// every field, lists, tags, series and trashed tasks included, reads back what was written
+    #[default]
+    #[field(value = "json")]
+    Json,
+    /// one row per task, for spreadsheets
+    #[field(value = "csv")]
+    Csv,
+    /// `- [ ]` / `- [x]` checklist, subtasks indented under their task and lists as `##` headings
+    #[field(value = "markdown")]
+    Markdown,
+    /// iCalendar VTODO components, for calendar apps
+    #[field(value = "ics")]
+    Ics,
+}
+
+impl TransferFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TransferFormat::Json => "json",
+            TransferFormat::Csv => "csv",
+            TransferFormat::Markdown => "markdown",
+            TransferFormat::Ics => "ics",
+        }
+    }
+
+    pub fn media_type(self) -> &'static str {
+        match self {
+            TransferFormat::Json => "application/json",
+            TransferFormat::Csv => "text/csv; charset=utf-8",
+            TransferFormat::Markdown => "text/markdown; charset=utf-8",
+            TransferFormat::Ics => "text/calendar; charset=utf-8",
+        }
+    }
+
+    pub fn file_extension(self) -> &'static str {
+        match self {
+            TransferFormat::Json => "json",
+            TransferFormat::Csv => "csv",
+            TransferFormat::Markdown => "md",
+            TransferFormat::Ics => "ics",
+        }
+    }
+}
+
+
+/// A task as written to and read from a file.
+///
+/// `id`, `parent_id` and `recurrence_id` only link tasks together inside the file, imported tasks get new ids.
+/// Only the JSON format carries the version, timestamps, series and trash.
+#[derive(Deserialize,Serialize, Debug, Clone, Default, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct TransferTaskDto {
+    pub id: Option<u32>,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub is_done: bool,
+    #[serde(default)]
+    pub priority: TaskPriority,
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub due_date: Option<DateTime<Utc>>,
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub remind_at: Option<DateTime<Utc>>,
+    /// name of the list, created on import when missing
+    pub list: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// `id` of the task this one is a subtask of
+    pub parent_id: Option<u32>,
+    /// kept on import, now when missing
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub created_at: Option<DateTime<Utc>>,
+    /// kept on import, now when missing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub updated_at: Option<DateTime<Utc>>,
+    /// kept on import, 1 when missing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<i32>,
+    /// set for a task in the trash, imported back into the trash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// `id` of the series in `recurrences` this task is an occurrence of
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence_id: Option<u32>,
+    /// number of the occurrence in its series, starting at 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub occurrence: Option<i32>
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct TransferListDto {
+    pub name: String,
+    pub description: Option<String>
+}
+
+
+/// A tag with its colour, created with it on import when missing.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct TransferTagDto {
+    pub name: String,
+    pub color: Option<String>
+}
+
+
+/// A series of recurring tasks, created again on import for the tasks pointing at its `id`.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct TransferRecurrenceDto {
+    pub id: u32,
+    pub frequency: RecurrenceFrequency,
+    pub interval: u32,
+    #[schemars(with = "String")]
+    #[schema(value_type = String, format = DateTime)]
+    pub starts_at: DateTime<Utc>,
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub until: Option<DateTime<Utc>>,
+    pub count: Option<u32>,
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub stopped_at: Option<DateTime<Utc>>
+}
+
+
+/// Content of an export, the JSON format writes it as is.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct TransferDocumentDto {
+    pub format_version: u32,
+    #[schemars(with = "String")]
+    #[schema(value_type = String, format = DateTime)]
+    pub exported_at: DateTime<Utc>,
+    #[serde(default)]
+    pub lists: Vec<TransferListDto>,
+    #[serde(default)]
+    pub tags: Vec<TransferTagDto>,
+    #[serde(default)]
+    pub recurrences: Vec<TransferRecurrenceDto>,
+    /// tasks in file order, each subtask right after its task
+    pub tasks: Vec<TransferTaskDto>
+}
+
+impl TransferDocumentDto {
+    pub fn new(lists: Vec<TransferListDto>, tasks: Vec<TransferTaskDto>) -> Self {
+        TransferDocumentDto {
+            format_version: TRANSFER_FORMAT_VERSION,
+            exported_at: Utc::now(),
+            lists,
+            tags: Vec::new(),
+            recurrences: Vec::new(),
+            tasks,
+        }
+    }
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqExportTasksDto {
+    /// `json` by default
+    #[serde(default)]
+    pub format: TransferFormat
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqImportTasksDto {
+    /// `json` by default
+    #[serde(default)]
+    pub format: TransferFormat,
+    /// the file content, as written by the export in the same format
+    pub content: String,
+    /// only report what would be imported, nothing is stored
+    #[serde(default)]
+    pub dry_run: bool
+}
+
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum ImportOutcome {
+    /// a new task was (or on a dry run would be) created
+    Created,
+    /// a task with the same title, due date and parent exists already, or comes earlier in the file.
+    /// Live tasks are matched against live tasks and trashed ones against the trash.
+    Duplicate,
+}
+
+
+/// What happened to one task of the file.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResImportEntryDto {
+    /// position of the task in the file, starting at 1
+    pub record: u32,
+    pub title: String,
+    pub outcome: ImportOutcome,
+    /// the new task, or the one it duplicates, `null` for a task created by a dry run
+    pub task_id: Option<u32>
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResImportReportDto {
+    pub dry_run: bool,
+    pub created: u32,
+    pub duplicates: u32,
+    /// one entry per task of the file, in file order
+    pub entries: Vec<ResImportEntryDto>,
+    /// the tasks created, empty on a dry run
+    pub tasks: Vec<ResEntryTodoDto>
+}
\ No newline at end of file
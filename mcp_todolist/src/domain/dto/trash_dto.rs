@@ -0,0 +1,21 @@
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+
+/// Tasks removed for good from the trash, subtasks included.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResPurgeDto {
+    pub purged: i32,
+    pub task_ids: Vec<u32>
+}
+
+impl From<Vec<i32>> for ResPurgeDto {
+    fn from(task_ids: Vec<i32>) -> Self {
+        Self {
+            purged: task_ids.len() as i32,
+            task_ids: task_ids.into_iter().map(|id| id as u32).collect(),
+        }
+    }
+}
\ No newline at end of file
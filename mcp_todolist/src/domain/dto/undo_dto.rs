@@ -0,0 +1,54 @@
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::{event_dto::ChangeKind, todo_dto::ResEntryTodoDto};
+
+
+/// Most operations a single undo may revert.
+pub const MAX_UNDO_OPERATIONS: u32 = 50;
+
+fn default_undo_operations() -> u32 {
+    1
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqUndoDto {
+    /// how many of the last operations (tool calls) of the session to revert, 1 by default
+    #[serde(default = "default_undo_operations")]
+    #[validate(range(min = 1, max = 50, message = "operations must be between 1 and 50"))]
+    pub operations: u32
+}
+
+
+/// One task change reverted by an undo.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResUndoneChangeDto {
+    /// audit entry of the change that was reverted
+    pub audit_id: u32,
+    pub operation_id: String,
+    pub task_id: u32,
+    /// what the reverted change did
+    pub undone_action: ChangeKind,
+    /// what the undo did to revert it
+    pub action: ChangeKind,
+    /// the task before the undo, `null` when the undo restored it from the trash
+    pub before: Option<ResEntryTodoDto>,
+    /// the task after the undo, `null` when the undo moved it to the trash
+    pub after: Option<ResEntryTodoDto>
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResUndoDto {
+    pub session_id: String,
+    /// operations reverted, fewer than requested when the session has no more
+    pub operations: u32,
+    /// reverted changes, most recent first
+    pub changes: Vec<ResUndoneChangeDto>
+}
\ No newline at end of file
@@ -0,0 +1,187 @@
+use chrono::{DateTime, Utc};
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::{event_dto::ChangeKind, todo_dto::ResEntryTodoDto};
+
+
+/// Where a delivery stands, `pending` ones are (re)tried by the dispatcher.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    /// the receiver answered with a 2xx status
+    Delivered,
+    /// every attempt failed, the delivery is no longer retried
+    Failed,
+}
+
+impl WebhookDeliveryStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WebhookDeliveryStatus::Pending => "pending",
+            WebhookDeliveryStatus::Delivered => "delivered",
+            WebhookDeliveryStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(WebhookDeliveryStatus::Pending),
+            "delivered" => Some(WebhookDeliveryStatus::Delivered),
+            "failed" => Some(WebhookDeliveryStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+
+/// Name of a task event as sent to the receivers, e.g. `task.completed`.
+pub fn webhook_event_name(kind: ChangeKind) -> String {
+    format!("task.{}", kind.as_str())
+}
+
+
+/// How many deliveries the log returns when no limit is given.
+pub const DEFAULT_DELIVERY_LOG_LIMIT: u32 = 20;
+
+
+fn default_active() -> bool {
+    true
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqCreateWebhookDto {
+    /// http(s) URL the events are POSTed to, not on a loopback, link-local or private address
+    #[validate(url(message = "url must be a valid URL"), length(max = 2000, message = "url must be at most 2000 characters"))]
+    pub url: String,
+    /// task events to send, every event when empty
+    #[serde(default)]
+    pub events: Vec<ChangeKind>,
+    /// key of the `X-Todolist-Signature-256` header, a random one is generated when omitted
+    #[validate(length(min = 16, max = 256, message = "secret must be between 16 and 256 characters"))]
+    pub secret: Option<String>,
+    #[serde(default = "default_active")]
+    pub active: bool
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqUpdateWebhookDto {
+    pub id: i32,
+    #[validate(url(message = "url must be a valid URL"), length(max = 2000, message = "url must be at most 2000 characters"))]
+    pub url: Option<String>,
+    /// replaces the event filter, an empty list sends every event
+    pub events: Option<Vec<ChangeKind>>,
+    #[validate(length(min = 16, max = 256, message = "secret must be between 16 and 256 characters"))]
+    pub secret: Option<String>,
+    /// an inactive webhook keeps its delivery log but gets no new deliveries
+    pub active: Option<bool>
+}
+
+
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, Validate, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ReqWebhookDeliveryLogDto {
+    pub webhook_id: i32,
+    /// how many deliveries to return, newest first, 20 by default
+    #[validate(range(min = 1, max = 200, message = "limit must be between 1 and 200"))]
+    pub limit: Option<u32>
+}
+
+
+/// A registered webhook, the secret is only returned by the call creating it.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResWebhookDto {
+    pub id: u32,
+    pub url: String,
+    /// every event when empty
+    pub events: Vec<ChangeKind>,
+    pub active: bool,
+    pub secret: Option<String>,
+    #[schemars(with = "String")]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTime<Utc>,
+    #[schemars(with = "String")]
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTime<Utc>
+}
+
+
+/// JSON body POSTed to the receivers.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResWebhookPayloadDto {
+    /// `task.created`, `task.updated`, `task.completed`, `task.deleted` or `task.restored`
+    pub event: String,
+    #[schemars(with = "String")]
+    #[schema(value_type = String, format = DateTime)]
+    pub occurred_at: DateTime<Utc>,
+    /// the task after the change, or as it was before being deleted
+    pub task: ResEntryTodoDto
+}
+
+
+/// An entry of the delivery log of a webhook.
+#[derive(Deserialize,Serialize, Debug, Clone, schemars::JsonSchema, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResWebhookDeliveryDto {
+    pub id: u32,
+    pub webhook_id: u32,
+    pub event: String,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    /// HTTP status of the last answer, `null` when the receiver could not be reached
+    pub response_status: Option<u16>,
+    pub last_error: Option<String>,
+    /// the body sent, a `ResWebhookPayloadDto`
+    #[schema(value_type = Object)]
+    pub payload: Value,
+    #[schemars(with = "String")]
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTime<Utc>,
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    /// when a pending delivery is tried again
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    #[schemars(with = "Option<String>")]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub delivered_at: Option<DateTime<Utc>>
+}
+
+
+/// A pending delivery handed to the dispatcher, with what it needs to send and sign it.
+#[derive(Debug, Clone)]
+pub struct WebhookDeliveryJob {
+    pub delivery_id: u32,
+    pub webhook_id: u32,
+    pub url: String,
+    pub secret: String,
+    pub event: String,
+    /// the JSON body, sent as stored so the signature covers the exact bytes
+    pub payload: String,
+    /// attempts made before this one
+    pub attempts: u32
+}
+
+
+/// Result of one attempt of a delivery, as recorded in the log.
+#[derive(Debug, Clone)]
+pub struct WebhookAttemptDto {
+    pub status: WebhookDeliveryStatus,
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+    pub attempted_at: DateTime<Utc>,
+    /// when to try again, only for a delivery still `pending`
+    pub next_attempt_at: Option<DateTime<Utc>>
+}
\ No newline at end of file
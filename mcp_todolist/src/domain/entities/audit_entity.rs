@@ -0,0 +1,74 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use crate::domain::{dto::{audit_dto::{ChangeOrigin, ChangeSource, ResTaskAuditDto}, event_dto::ChangeKind}, error::domain_error::DomainError, schema::schema::task_audit};
+
+
+
+#[derive(Insertable)]
+#[diesel(table_name=task_audit)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewTaskAuditEntity {
+    pub todo_id: i32,
+    pub action: String,
+    pub before_snapshot: Option<String>,
+    pub after_snapshot: Option<String>,
+    pub origin: String,
+    pub client_name: Option<String>,
+    pub session_id: Option<String>,
+    pub changed_at: NaiveDateTime,
+    pub operation_id: Option<String>
+}
+
+
+#[derive(Queryable, Selectable, Clone)]
+#[diesel(table_name=task_audit)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct EntryTaskAuditEntity {
+    pub id: i32,
+    pub todo_id: i32,
+    pub action: String,
+    pub before_snapshot: Option<String>,
+    pub after_snapshot: Option<String>,
+    pub origin: String,
+    pub client_name: Option<String>,
+    pub session_id: Option<String>,
+    pub changed_at: NaiveDateTime,
+    pub operation_id: Option<String>,
+    pub reverted_by: Option<i32>
+}
+
+
+fn parse_snapshot(snapshot: Option<String>) -> Result<Option<serde_json::Value>, DomainError> {
+    snapshot
+        .map(|json| serde_json::from_str(&json))
+        .transpose()
+        .map_err(|e| DomainError::storage(format!("Corrupted audit snapshot: {}", e)))
+}
+
+
+impl TryFrom<EntryTaskAuditEntity> for ResTaskAuditDto {
+    type Error = DomainError;
+
+    fn try_from(the_entity: EntryTaskAuditEntity) -> Result<Self, Self::Error> {
+        let action = ChangeKind::parse(&the_entity.action)
+            .ok_or_else(|| DomainError::storage(format!("Unknown audit action {}", the_entity.action)))?;
+        let source = ChangeSource::parse(&the_entity.origin)
+            .ok_or_else(|| DomainError::storage(format!("Unknown audit origin {}", the_entity.origin)))?;
+
+        Ok(ResTaskAuditDto {
+            id: the_entity.id as u32,
+            task_id: the_entity.todo_id as u32,
+            action,
+            before: parse_snapshot(the_entity.before_snapshot)?,
+            after: parse_snapshot(the_entity.after_snapshot)?,
+            origin: ChangeOrigin {
+                source,
+                client_name: the_entity.client_name,
+                session_id: the_entity.session_id,
+                operation_id: the_entity.operation_id,
+            },
+            changed_at: the_entity.changed_at.and_utc(),
+            reverted_by: the_entity.reverted_by.map(|entry| entry as u32),
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,46 @@
+use diesel::prelude::*;
+use crate::domain::{dto::list_dto::ResEntryListDto, schema::schema::lists};
+
+
+
+#[derive(Insertable)]
+#[diesel(table_name=lists)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewListEntity {
+    pub name: String,
+    pub description: Option<String>
+}
+
+
+#[derive(AsChangeset)]
+#[diesel(table_name = lists)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct UpdateListEntity {
+    pub name: Option<String>,
+    pub description: Option<Option<String>>,
+}
+
+
+#[derive(Queryable, Selectable, Clone)]
+#[diesel(table_name=lists)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct EntryListEntity {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>
+}
+
+
+impl From<EntryListEntity> for ResEntryListDto {
+    fn from(the_entity: EntryListEntity) -> Self {
+        ResEntryListDto {
+            id: the_entity.id as u32,
+            name: the_entity.name,
+            description: the_entity.description,
+            created_at: the_entity.created_at.unwrap_or_default(),
+            updated_at: the_entity.updated_at.unwrap_or_default()
+        }
+    }
+}
\ No newline at end of file
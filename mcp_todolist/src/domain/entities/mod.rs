@@ -1 +1,8 @@
-pub mod todo_entity;
\ No newline at end of file
+pub mod todo_entity;
+pub mod tag_entity;
+pub mod list_entity;
+pub mod audit_entity;
+pub mod search_entity;
+pub mod recurrence_entity;
+pub mod reminder_entity;
+pub mod webhook_entity;
\ No newline at end of file
@@ -0,0 +1,125 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use crate::domain::{dto::recurrence_dto::{RecurrenceFrequency, ReqRecurrenceRuleDto}, error::domain_error::DomainError, schema::schema::recurrences};
+
+
+
+#[derive(Insertable)]
+#[diesel(table_name=recurrences)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewRecurrenceEntity {
+    pub frequency: String,
+    pub interval: i32,
+    pub starts_at: NaiveDateTime,
+    pub until: Option<NaiveDateTime>,
+    pub max_occurrences: Option<i32>,
+    pub created_at: NaiveDateTime
+}
+
+impl NewRecurrenceEntity {
+    pub fn new(rule: &ReqRecurrenceRuleDto, starts_at: NaiveDateTime, created_at: NaiveDateTime) -> Self {
+        NewRecurrenceEntity {
+            frequency: rule.frequency.as_str().to_string(),
+            interval: rule.interval as i32,
+            starts_at,
+            until: rule.until.map(|until| until.naive_utc()),
+            max_occurrences: rule.count.map(|count| count as i32),
+            created_at
+        }
+    }
+}
+
+
+#[derive(Queryable, Selectable, Clone)]
+#[diesel(table_name=recurrences)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct EntryRecurrenceEntity {
+    pub id: i32,
+    pub frequency: String,
+    pub interval: i32,
+    pub starts_at: NaiveDateTime,
+    pub until: Option<NaiveDateTime>,
+    pub max_occurrences: Option<i32>,
+    pub stopped_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime
+}
+
+impl EntryRecurrenceEntity {
+    pub fn frequency(&self) -> Result<RecurrenceFrequency, DomainError> {
+        RecurrenceFrequency::parse(&self.frequency)
+            .ok_or_else(|| DomainError::storage(format!("Unknown recurrence frequency: {}", self.frequency)))
+    }
+
+    /// Due date of occurrence `occurrence` (1 for the first task), `None` when it falls outside the series.
+    pub fn occurrence_due(&self, occurrence: i32) -> Result<Option<NaiveDateTime>, DomainError> {
+        if occurrence < 1 || self.max_occurrences.is_some_and(|max| occurrence > max) {
+            return Ok(None);
+        }
+        let Some(units) = (occurrence as u32 - 1).checked_mul(self.interval as u32) else {
+            return Ok(None);
+        };
+        let due = self.frequency()?
+            .advance(self.starts_at.and_utc(), units)
+            .map(|due| due.naive_utc());
+        Ok(due.filter(|due| self.until.is_none_or(|until| *due <= until)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+
+    fn at(year: i32, month: u32, day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap().and_hms_opt(9, 0, 0).unwrap()
+    }
+
+    fn series(frequency: &str, interval: i32, starts_at: NaiveDateTime) -> EntryRecurrenceEntity {
+        EntryRecurrenceEntity {
+            id: 1,
+            frequency: frequency.to_string(),
+            interval,
+            starts_at,
+            until: None,
+            max_occurrences: None,
+            stopped_at: None,
+            created_at: starts_at,
+        }
+    }
+
+    #[test]
+    fn the_first_occurrence_is_due_at_the_start() {
+        let series = series("weekly", 2, at(2026, 10, 18));
+        assert_eq!(series.occurrence_due(1).unwrap(), Some(at(2026, 10, 18)));
+        assert_eq!(series.occurrence_due(2).unwrap(), Some(at(2026, 11, 1)));
+        assert_eq!(series.occurrence_due(0).unwrap(), None);
+    }
+
+    #[test]
+    fn a_monthly_series_from_the_31st_follows_the_month_end() {
+        let series = series("monthly", 1, at(2026, 1, 31));
+        assert_eq!(series.occurrence_due(2).unwrap(), Some(at(2026, 2, 28)));
+        assert_eq!(series.occurrence_due(3).unwrap(), Some(at(2026, 3, 31)));
+    }
+
+    #[test]
+    fn an_occurrence_due_exactly_at_until_is_kept() {
+        let series = EntryRecurrenceEntity { until: Some(at(2026, 10, 20)), ..series("daily", 1, at(2026, 10, 18)) };
+        assert_eq!(series.occurrence_due(3).unwrap(), Some(at(2026, 10, 20)));
+        assert_eq!(series.occurrence_due(4).unwrap(), None);
+    }
+
+    #[test]
+    fn the_series_ends_after_count_occurrences() {
+        let series = EntryRecurrenceEntity { max_occurrences: Some(3), ..series("daily", 1, at(2026, 10, 18)) };
+        assert_eq!(series.occurrence_due(3).unwrap(), Some(at(2026, 10, 20)));
+        assert_eq!(series.occurrence_due(4).unwrap(), None);
+    }
+
+    #[test]
+    fn an_unknown_frequency_is_a_storage_error() {
+        assert!(matches!(series("hourly", 1, at(2026, 10, 18)).occurrence_due(2), Err(DomainError::Storage(_))));
+    }
+}
\ No newline at end of file
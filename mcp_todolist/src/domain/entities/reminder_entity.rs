@@ -0,0 +1,15 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use crate::domain::schema::schema::fired_reminders;
+
+
+
+#[derive(Insertable)]
+#[diesel(table_name=fired_reminders)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewFiredReminderEntity {
+    pub todo_id: i32,
+    pub kind: String,
+    pub fire_at: NaiveDateTime,
+    pub fired_at: NaiveDateTime
+}
\ No newline at end of file
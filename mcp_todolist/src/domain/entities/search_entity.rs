@@ -0,0 +1,17 @@
+use diesel::{sql_types::{Double, Integer, Text}, QueryableByName};
+
+
+
+/// One row of the ranked full-text query over `todo_search`.
+#[derive(QueryableByName)]
+pub struct SearchMatchEntity {
+    #[diesel(sql_type = Integer)]
+    pub todo_id: i32,
+    /// bm25 of the match, lower is better
+    #[diesel(sql_type = Double)]
+    pub rank: f64,
+    #[diesel(sql_type = Text)]
+    pub title_snippet: String,
+    #[diesel(sql_type = Text)]
+    pub description_snippet: String
+}
\ No newline at end of file
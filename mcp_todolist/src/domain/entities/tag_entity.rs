@@ -0,0 +1,52 @@
+use diesel::prelude::*;
+use crate::domain::{dto::tag_dto::ResEntryTagDto, schema::schema::{tags, todo_tags}};
+
+
+
+#[derive(Insertable)]
+#[diesel(table_name=tags)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewTagEntity {
+    pub name: String,
+    pub color: Option<String>
+}
+
+
+#[derive(AsChangeset)]
+#[diesel(table_name = tags)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct UpdateTagEntity {
+    pub name: Option<String>,
+    pub color: Option<Option<String>>,
+}
+
+
+#[derive(Queryable, Selectable, Clone)]
+#[diesel(table_name=tags)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct EntryTagEntity {
+    pub id: i32,
+    pub name: String,
+    pub color: Option<String>,
+    pub created_at: Option<String>
+}
+
+
+#[derive(Insertable, Queryable, Selectable, Clone)]
+#[diesel(table_name=todo_tags)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct TodoTagEntity {
+    pub todo_id: i32,
+    pub tag_id: i32
+}
+
+
+impl From<EntryTagEntity> for ResEntryTagDto {
+    fn from(the_entity: EntryTagEntity) -> Self {
+        ResEntryTagDto {
+            id: the_entity.id as u32,
+            name: the_entity.name,
+            color: the_entity.color
+        }
+    }
+}
\ No newline at end of file
@@ -1,5 +1,6 @@
+use chrono::NaiveDateTime;
 use diesel::prelude::*;
-use crate::domain::{dto::todo_dto::ResEntryTodoDto, schema::schema::todolist};
+use crate::domain::{dto::todo_dto::{ResEntryTodoDto, TaskPriority}, schema::schema::todolist};
 
 
 
@@ -9,7 +10,13 @@ use crate::domain::{dto::todo_dto::ResEntryTodoDto, schema::schema::todolist};
 pub struct NewTodoEntity {
     pub title: String,
     pub description: String,
-    pub is_done: bool
+    pub is_done: bool,
+    pub priority: i32,
+    pub due_date: Option<NaiveDateTime>,
+    pub list_id: Option<i32>,
+    pub parent_id: Option<i32>,
+    pub position: i32,
+    pub remind_at: Option<NaiveDateTime>
 }
 
 
@@ -20,6 +27,10 @@ pub struct UpdateTodoEntity {
     pub title: Option<String>,
     pub description: Option<String>,
     pub is_done: Option<bool>,
+    pub priority: Option<i32>,
+    pub due_date: Option<Option<NaiveDateTime>>,
+    pub list_id: Option<Option<i32>>,
+    pub remind_at: Option<Option<NaiveDateTime>>,
 }
 
 
@@ -31,8 +42,18 @@ pub struct EntryTodoEntity {
     pub title: String,
     pub description: String,
     pub is_done: bool,
-    pub created_at: Option<String>,
-    pub updated_at: Option<String>
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+    pub priority: i32,
+    pub due_date: Option<NaiveDateTime>,
+    pub list_id: Option<i32>,
+    pub parent_id: Option<i32>,
+    pub position: i32,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub version: i32,
+    pub recurrence_id: Option<i32>,
+    pub occurrence: Option<i32>,
+    pub remind_at: Option<NaiveDateTime>
 }
 
 
@@ -44,8 +65,22 @@ impl From<EntryTodoEntity> for ResEntryTodoDto {
             title: the_entity.title, 
             description: the_entity.description, 
             is_done: the_entity.is_done, 
-            created_at: the_entity.created_at.unwrap_or_else(||"".to_string()), 
-            updated_at: the_entity.updated_at.unwrap_or_else(||"".to_string()) 
+            priority: TaskPriority::from_i32(the_entity.priority),
+            due_date: the_entity.due_date.map(|due| due.and_utc()),
+            list_id: the_entity.list_id.map(|list| list as u32),
+            parent_id: the_entity.parent_id.map(|parent| parent as u32),
+            position: the_entity.position,
+            deleted_at: the_entity.deleted_at.map(|deleted| deleted.and_utc()),
+            version: the_entity.version,
+            recurrence_id: the_entity.recurrence_id.map(|recurrence| recurrence as u32),
+            occurrence: the_entity.occurrence,
+            remind_at: the_entity.remind_at.map(|remind| remind.and_utc()),
+            tags: Vec::new(),
+            subtasks_done: 0,
+            subtasks_total: 0,
+            // only rows written behind the repository's back lack timestamps, they read as the epoch
+            created_at: the_entity.created_at.unwrap_or_default().and_utc(),
+            updated_at: the_entity.updated_at.or(the_entity.created_at).unwrap_or_default().and_utc()
         }
     }
 }
\ No newline at end of file
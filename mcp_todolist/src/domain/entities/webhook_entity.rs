@@ -0,0 +1,141 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use crate::domain::{dto::{event_dto::ChangeKind, webhook_dto::{ResWebhookDeliveryDto, ResWebhookDto, WebhookDeliveryStatus}}, error::domain_error::DomainError, schema::schema::{webhook_deliveries, webhooks}};
+
+
+
+/// Column form of an event filter, `created,completed`, empty for every event.
+pub fn join_events(events: &[ChangeKind]) -> String {
+    let mut names: Vec<&str> = events.iter().map(|kind| kind.as_str()).collect();
+    names.sort_unstable();
+    names.dedup();
+    names.join(",")
+}
+
+
+#[derive(Insertable)]
+#[diesel(table_name=webhooks)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewWebhookEntity {
+    pub url: String,
+    pub events: String,
+    pub secret: String,
+    pub active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime
+}
+
+
+#[derive(AsChangeset)]
+#[diesel(table_name = webhooks)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct UpdateWebhookEntity {
+    pub url: Option<String>,
+    pub events: Option<String>,
+    pub secret: Option<String>,
+    pub active: Option<bool>,
+    pub updated_at: NaiveDateTime
+}
+
+
+#[derive(Queryable, Selectable, Clone)]
+#[diesel(table_name=webhooks)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct EntryWebhookEntity {
+    pub id: i32,
+    pub url: String,
+    pub events: String,
+    pub secret: String,
+    pub active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime
+}
+
+impl EntryWebhookEntity {
+    pub fn events(&self) -> Result<Vec<ChangeKind>, DomainError> {
+        self.events
+            .split(',')
+            .filter(|name| !name.is_empty())
+            .map(|name| ChangeKind::parse(name).ok_or_else(|| DomainError::storage(format!("Unknown webhook event {}", name))))
+            .collect()
+    }
+
+    pub fn wants(&self, kind: ChangeKind) -> bool {
+        self.events.is_empty() || self.events.split(',').any(|name| name == kind.as_str())
+    }
+}
+
+
+impl TryFrom<EntryWebhookEntity> for ResWebhookDto {
+    type Error = DomainError;
+
+    fn try_from(the_entity: EntryWebhookEntity) -> Result<Self, Self::Error> {
+        Ok(ResWebhookDto {
+            id: the_entity.id as u32,
+            events: the_entity.events()?,
+            url: the_entity.url,
+            active: the_entity.active,
+            secret: None,
+            created_at: the_entity.created_at.and_utc(),
+            updated_at: the_entity.updated_at.and_utc(),
+        })
+    }
+}
+
+
+#[derive(Insertable)]
+#[diesel(table_name=webhook_deliveries)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct NewWebhookDeliveryEntity {
+    pub webhook_id: i32,
+    pub event: String,
+    pub payload: String,
+    pub created_at: NaiveDateTime,
+    pub next_attempt_at: Option<NaiveDateTime>
+}
+
+
+#[derive(Queryable, Selectable, Clone)]
+#[diesel(table_name=webhook_deliveries)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct EntryWebhookDeliveryEntity {
+    pub id: i32,
+    pub webhook_id: i32,
+    pub event: String,
+    pub payload: String,
+    pub status: String,
+    pub attempts: i32,
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub last_attempt_at: Option<NaiveDateTime>,
+    pub next_attempt_at: Option<NaiveDateTime>,
+    pub delivered_at: Option<NaiveDateTime>
+}
+
+
+impl TryFrom<EntryWebhookDeliveryEntity> for ResWebhookDeliveryDto {
+    type Error = DomainError;
+
+    fn try_from(the_entity: EntryWebhookDeliveryEntity) -> Result<Self, Self::Error> {
+        let status = WebhookDeliveryStatus::parse(&the_entity.status)
+            .ok_or_else(|| DomainError::storage(format!("Unknown webhook delivery status {}", the_entity.status)))?;
+        let payload = serde_json::from_str(&the_entity.payload)
+            .map_err(|e| DomainError::storage(format!("Corrupted webhook payload: {}", e)))?;
+
+        Ok(ResWebhookDeliveryDto {
+            id: the_entity.id as u32,
+            webhook_id: the_entity.webhook_id as u32,
+            event: the_entity.event,
+            status,
+            attempts: the_entity.attempts as u32,
+            response_status: the_entity.response_status.map(|code| code as u16),
+            last_error: the_entity.last_error,
+            payload,
+            created_at: the_entity.created_at.and_utc(),
+            last_attempt_at: the_entity.last_attempt_at.map(|at| at.and_utc()),
+            next_attempt_at: the_entity.next_attempt_at.map(|at| at.and_utc()),
+            delivered_at: the_entity.delivered_at.map(|at| at.and_utc()),
+        })
+    }
+}
\ No newline at end of file
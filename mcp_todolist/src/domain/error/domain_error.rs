@@ -0,0 +1,70 @@
+use diesel::{r2d2::PoolError, result::{DatabaseErrorKind, Error as DieselError}};
+use validator::ValidationErrors;
+
+
+
+/// Errors that can flow out of the repository and use case layers.
+///
+/// Each variant is mapped to a dedicated HTTP status in the Rocket handlers
+/// and to a dedicated MCP error code in `MCPHandler`.
+#[derive(Debug, thiserror::Error)]
+pub enum DomainError {
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    Validation(String),
+    #[error("{0}")]
+    Conflict(String),
+    #[error("{0}")]
+    Storage(String),
+}
+
+pub type DomainResult<T> = Result<T, DomainError>;
+
+
+impl DomainError {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        DomainError::NotFound(message.into())
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        DomainError::Validation(message.into())
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        DomainError::Conflict(message.into())
+    }
+
+    pub fn storage(message: impl Into<String>) -> Self {
+        DomainError::Storage(message.into())
+    }
+}
+
+
+impl From<DieselError> for DomainError {
+    fn from(err: DieselError) -> Self {
+        match err {
+            DieselError::NotFound => DomainError::NotFound("Record not found".to_string()),
+            DieselError::DatabaseError(
+                DatabaseErrorKind::UniqueViolation | DatabaseErrorKind::ForeignKeyViolation,
+                info,
+            ) => DomainError::Conflict(info.message().to_string()),
+            DieselError::DatabaseError(DatabaseErrorKind::CheckViolation, info) => {
+                DomainError::Validation(info.message().to_string())
+            }
+            other => DomainError::Storage(other.to_string()),
+        }
+    }
+}
+
+impl From<PoolError> for DomainError {
+    fn from(err: PoolError) -> Self {
+        DomainError::Storage(format!("Failed to get DB connection from pool: {}", err))
+    }
+}
+
+impl From<ValidationErrors> for DomainError {
+    fn from(err: ValidationErrors) -> Self {
+        DomainError::Validation(err.to_string())
+    }
+}
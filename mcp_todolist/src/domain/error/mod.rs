@@ -0,0 +1 @@
+pub mod domain_error;
\ No newline at end of file
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_todolist_due_date;
+
+ALTER TABLE todolist DROP COLUMN due_date;
+ALTER TABLE todolist DROP COLUMN priority;
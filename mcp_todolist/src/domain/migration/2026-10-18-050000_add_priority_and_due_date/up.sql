@@ -0,0 +1,5 @@
+-- priority: 0 = low, 1 = normal, 2 = high, 3 = urgent
+ALTER TABLE todolist ADD COLUMN priority INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE todolist ADD COLUMN due_date DATETIME;
+
+CREATE INDEX idx_todolist_due_date ON todolist (due_date);
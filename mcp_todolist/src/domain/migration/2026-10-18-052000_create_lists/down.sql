@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_todolist_list_id;
+
+ALTER TABLE todolist DROP COLUMN list_id;
+
+DROP TABLE IF EXISTS lists;
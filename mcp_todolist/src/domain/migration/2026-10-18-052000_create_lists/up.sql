@@ -0,0 +1,12 @@
+CREATE TABLE lists (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    name VARCHAR(100) NOT NULL UNIQUE COLLATE NOCASE,
+    description TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- deleting a list keeps its tasks, they just stop belonging to any list
+ALTER TABLE todolist ADD COLUMN list_id INTEGER REFERENCES lists (id) ON DELETE SET NULL;
+
+CREATE INDEX idx_todolist_list_id ON todolist (list_id);
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_todolist_parent_id;
+
+DELETE FROM todolist WHERE parent_id IS NOT NULL;
+
+ALTER TABLE todolist DROP COLUMN position;
+ALTER TABLE todolist DROP COLUMN parent_id;
@@ -0,0 +1,6 @@
+-- a subtask is a regular task row pointing at its parent, removed together with it
+ALTER TABLE todolist ADD COLUMN parent_id INTEGER REFERENCES todolist (id) ON DELETE CASCADE;
+-- order of the subtasks inside their parent, 0 for top level tasks
+ALTER TABLE todolist ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_todolist_parent_id ON todolist (parent_id, position);
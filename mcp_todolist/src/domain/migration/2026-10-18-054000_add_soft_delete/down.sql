@@ -0,0 +1,5 @@
+DROP INDEX idx_todolist_deleted_at;
+
+DELETE FROM todolist WHERE deleted_at IS NOT NULL;
+
+ALTER TABLE todolist DROP COLUMN deleted_at;
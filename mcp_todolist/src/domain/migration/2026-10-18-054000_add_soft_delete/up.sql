@@ -0,0 +1,4 @@
+-- trashed tasks keep their row until purged, every query skips them
+ALTER TABLE todolist ADD COLUMN deleted_at DATETIME;
+
+CREATE INDEX idx_todolist_deleted_at ON todolist (deleted_at);
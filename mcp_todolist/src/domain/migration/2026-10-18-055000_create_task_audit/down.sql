@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_task_audit_session_id;
+DROP INDEX IF EXISTS idx_task_audit_todo_id;
+
+DROP TABLE task_audit;
@@ -0,0 +1,16 @@
+-- one row per task change, kept after the task is purged so there is no foreign key
+CREATE TABLE task_audit (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    todo_id INTEGER NOT NULL,
+    action VARCHAR(20) NOT NULL,
+    -- JSON snapshots of the task, before is NULL on create and after is NULL on delete
+    before_snapshot TEXT,
+    after_snapshot TEXT,
+    origin VARCHAR(10) NOT NULL,
+    client_name VARCHAR(100),
+    session_id VARCHAR(50),
+    changed_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_task_audit_todo_id ON task_audit (todo_id, id);
+CREATE INDEX idx_task_audit_session_id ON task_audit (session_id, id);
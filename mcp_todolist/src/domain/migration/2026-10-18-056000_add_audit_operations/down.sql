@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_task_audit_operation_id;
+
+ALTER TABLE task_audit DROP COLUMN reverted_by;
+ALTER TABLE task_audit DROP COLUMN operation_id;
@@ -0,0 +1,6 @@
+-- every change made by one tool call or HTTP request shares an operation id, undo works per operation
+ALTER TABLE task_audit ADD COLUMN operation_id VARCHAR(60);
+-- the entry written when this change was undone
+ALTER TABLE task_audit ADD COLUMN reverted_by INTEGER REFERENCES task_audit (id);
+
+CREATE INDEX idx_task_audit_operation_id ON task_audit (session_id, operation_id);
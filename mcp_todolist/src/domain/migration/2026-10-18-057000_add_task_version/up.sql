@@ -0,0 +1,2 @@
+-- bumped on every write to the row, clients send it back to detect concurrent edits
+ALTER TABLE todolist ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1 @@
+-- the backfilled timestamps are kept, there is nothing to revert
@@ -0,0 +1,3 @@
+-- timestamps are written by the application now, fill the gaps left by rows inserted without them
+UPDATE todolist SET created_at = COALESCE(created_at, updated_at, CURRENT_TIMESTAMP) WHERE created_at IS NULL;
+UPDATE todolist SET updated_at = created_at WHERE updated_at IS NULL;
@@ -0,0 +1,4 @@
+DROP TRIGGER todo_search_after_update;
+DROP TRIGGER todo_search_after_delete;
+DROP TRIGGER todo_search_after_insert;
+DROP TABLE todo_search;
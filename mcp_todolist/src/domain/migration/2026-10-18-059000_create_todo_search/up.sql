@@ -0,0 +1,24 @@
+-- full-text index over titles and descriptions, the rows themselves stay in todolist (external content)
+-- trigram tokens match inside words, which Thai text written without spaces needs
+CREATE VIRTUAL TABLE todo_search USING fts5(
+    title,
+    description,
+    content = 'todolist',
+    content_rowid = 'id',
+    tokenize = 'trigram'
+);
+
+CREATE TRIGGER todo_search_after_insert AFTER INSERT ON todolist BEGIN
+    INSERT INTO todo_search (rowid, title, description) VALUES (new.id, new.title, new.description);
+END;
+
+CREATE TRIGGER todo_search_after_delete AFTER DELETE ON todolist BEGIN
+    INSERT INTO todo_search (todo_search, rowid, title, description) VALUES ('delete', old.id, old.title, old.description);
+END;
+
+CREATE TRIGGER todo_search_after_update AFTER UPDATE OF title, description ON todolist BEGIN
+    INSERT INTO todo_search (todo_search, rowid, title, description) VALUES ('delete', old.id, old.title, old.description);
+    INSERT INTO todo_search (rowid, title, description) VALUES (new.id, new.title, new.description);
+END;
+
+INSERT INTO todo_search (todo_search) VALUES ('rebuild');
@@ -0,0 +1,4 @@
+DROP INDEX idx_todolist_recurrence_id;
+ALTER TABLE todolist DROP COLUMN occurrence;
+ALTER TABLE todolist DROP COLUMN recurrence_id;
+DROP TABLE recurrences;
@@ -0,0 +1,17 @@
+-- a series of recurring tasks, occurrence n is due at starts_at + (n - 1) * interval frequency units
+CREATE TABLE recurrences (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    frequency VARCHAR(10) NOT NULL,
+    interval INTEGER NOT NULL DEFAULT 1,
+    starts_at DATETIME NOT NULL,
+    -- the series ends after this date or this many occurrences, whichever comes first
+    until DATETIME,
+    max_occurrences INTEGER,
+    stopped_at DATETIME,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+ALTER TABLE todolist ADD COLUMN recurrence_id INTEGER REFERENCES recurrences (id) ON DELETE SET NULL;
+ALTER TABLE todolist ADD COLUMN occurrence INTEGER;
+
+CREATE INDEX idx_todolist_recurrence_id ON todolist (recurrence_id, occurrence);
@@ -0,0 +1,3 @@
+DROP TABLE fired_reminders;
+DROP INDEX idx_todolist_remind_at;
+ALTER TABLE todolist DROP COLUMN remind_at;
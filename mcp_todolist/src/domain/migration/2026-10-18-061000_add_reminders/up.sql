@@ -0,0 +1,15 @@
+ALTER TABLE todolist ADD COLUMN remind_at DATETIME;
+
+CREATE INDEX idx_todolist_remind_at ON todolist (remind_at);
+
+-- one row per notification sent, a task moved to a new date gets notified again
+CREATE TABLE fired_reminders (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    todo_id INTEGER NOT NULL REFERENCES todolist (id) ON DELETE CASCADE,
+    -- 'reminder' for remind_at, 'due' for due_date
+    kind VARCHAR(10) NOT NULL,
+    fire_at DATETIME NOT NULL,
+    fired_at DATETIME NOT NULL
+);
+
+CREATE UNIQUE INDEX idx_fired_reminders_unique ON fired_reminders (todo_id, kind, fire_at);
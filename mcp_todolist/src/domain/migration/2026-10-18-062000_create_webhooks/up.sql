@@ -0,0 +1,32 @@
+CREATE TABLE webhooks (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    url TEXT NOT NULL,
+    -- comma separated task events ('created,completed'), empty for every event
+    events TEXT NOT NULL DEFAULT '',
+    -- key of the HMAC-SHA256 signature sent with every delivery
+    secret TEXT NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT 1,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL
+);
+
+-- outbox of the webhooks, a row is queued per webhook and event and kept as the delivery log
+CREATE TABLE webhook_deliveries (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    webhook_id INTEGER NOT NULL REFERENCES webhooks (id) ON DELETE CASCADE,
+    event VARCHAR(30) NOT NULL,
+    payload TEXT NOT NULL,
+    -- 'pending' until it is 'delivered' or 'failed' for good
+    status VARCHAR(10) NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    response_status INTEGER,
+    last_error TEXT,
+    created_at DATETIME NOT NULL,
+    last_attempt_at DATETIME,
+    -- when a pending delivery is tried (again), NULL once it is settled
+    next_attempt_at DATETIME,
+    delivered_at DATETIME
+);
+
+CREATE INDEX idx_webhook_deliveries_pending ON webhook_deliveries (status, next_attempt_at);
+CREATE INDEX idx_webhook_deliveries_webhook ON webhook_deliveries (webhook_id, id);
@@ -0,0 +1,6 @@
+use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+
+
+// every migration folder beside this file is compiled into the binary,
+// path is relative to the crate root (same as `diesel.toml`)
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("src/domain/migration");
\ No newline at end of file
@@ -2,4 +2,5 @@ pub mod entities;
 pub mod repository;
 pub mod schema;
 pub mod migration;
-pub mod dto;
\ No newline at end of file
+pub mod dto;
+pub mod error;
\ No newline at end of file
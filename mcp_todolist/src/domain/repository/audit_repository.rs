@@ -0,0 +1,11 @@
+use crate::domain::{dto::{audit_dto::{ChangeOrigin, ResTaskAuditDto}, undo_dto::ResUndoneChangeDto}, error::domain_error::DomainResult};
+
+#[async_trait::async_trait]
+#[mockall::automock]
+pub trait AuditRepository {
+    /// Every recorded change of the task, oldest first. Works for trashed and purged tasks too.
+    async fn get_task_history(&self, task_id: i32) -> DomainResult<Vec<ResTaskAuditDto>>;
+    /// Revert the last `operations` operations of the session in one transaction, recording the reverts as made by `origin`.
+    /// Fails with a conflict, changing nothing, when another client changed one of the tasks since.
+    async fn undo_session(&self, session_id: String, operations: u32, origin: ChangeOrigin) -> DomainResult<Vec<ResUndoneChangeDto>>;
+}
\ No newline at end of file
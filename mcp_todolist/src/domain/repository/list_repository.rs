@@ -0,0 +1,12 @@
+use crate::domain::{dto::list_dto::{ReqCreateListDto, ReqUpdateListDto, ResEntryListDto}, error::domain_error::DomainResult};
+
+#[async_trait::async_trait]
+#[mockall::automock]
+pub trait ListRepository {
+    async fn create_list(&self, dto: ReqCreateListDto) -> DomainResult<ResEntryListDto>;
+    async fn update_list(&self, list_id: i32, dto: ReqUpdateListDto) -> DomainResult<ResEntryListDto>;
+    async fn get_list_by_id(&self, list_id: i32) -> DomainResult<ResEntryListDto>;
+    async fn get_all_lists(&self) -> DomainResult<Vec<ResEntryListDto>>;
+    /// Delete a list, its tasks are kept and no longer belong to any list.
+    async fn delete_list(&self, list_id: i32) -> DomainResult<()>;
+}
\ No newline at end of file
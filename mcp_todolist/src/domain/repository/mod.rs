@@ -1 +1,11 @@
-pub mod todo_repository;
\ No newline at end of file
+pub mod todo_repository;
+pub mod tag_repository;
+pub mod list_repository;
+pub mod subtask_repository;
+pub mod trash_repository;
+pub mod audit_repository;
+pub mod search_repository;
+pub mod recurrence_repository;
+pub mod reminder_repository;
+pub mod webhook_repository;
+pub mod transfer_repository;
\ No newline at end of file
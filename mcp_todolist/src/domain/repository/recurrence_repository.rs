@@ -0,0 +1,11 @@
+use crate::domain::{dto::{audit_dto::{ChangeOrigin, TaskChangeDto}, recurrence_dto::{ReqRecurrenceRuleDto, ResRecurrenceDto}, todo_dto::ReqCreateTodoDto}, error::domain_error::DomainResult};
+
+#[async_trait::async_trait]
+#[mockall::automock]
+pub trait RecurrenceRepository {
+    /// Start a series with `task` as its first occurrence, returns the series and the creation of that first task.
+    async fn create_recurring_task(&self, task: ReqCreateTodoDto, rule: ReqRecurrenceRuleDto, origin: ChangeOrigin) -> DomainResult<(ResRecurrenceDto, TaskChangeDto)>;
+    async fn get_recurrence(&self, recurrence_id: i32) -> DomainResult<ResRecurrenceDto>;
+    /// Spawn no further occurrences, the tasks of the series are kept.
+    async fn stop_recurrence(&self, recurrence_id: i32) -> DomainResult<ResRecurrenceDto>;
+}
\ No newline at end of file
@@ -0,0 +1,11 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::{dto::reminder_dto::ResReminderDto, error::domain_error::DomainResult};
+
+#[async_trait::async_trait]
+#[mockall::automock]
+pub trait ReminderRepository {
+    /// Reminders and due dates of open tasks reached between `since` and `now` that were not sent yet.
+    /// They are recorded as fired before being returned, so each one is handed out only once.
+    async fn claim_due_reminders(&self, since: DateTime<Utc>, now: DateTime<Utc>) -> DomainResult<Vec<ResReminderDto>>;
+}
\ No newline at end of file
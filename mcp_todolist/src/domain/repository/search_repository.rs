@@ -0,0 +1,8 @@
+use crate::domain::{dto::search_dto::ResSearchHitDto, error::domain_error::DomainResult};
+
+#[async_trait::async_trait]
+#[mockall::automock]
+pub trait SearchRepository {
+    /// Tasks outside the trash whose title or description contain every word of `query`, best matches first.
+    async fn search_tasks(&self, query: String, limit: u32) -> DomainResult<Vec<ResSearchHitDto>>;
+}
\ No newline at end of file
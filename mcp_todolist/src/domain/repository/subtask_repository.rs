@@ -0,0 +1,13 @@
+use crate::domain::{dto::{audit_dto::{ChangeOrigin, TaskChangeDto}, subtask_dto::ReqCreateSubtaskDto, todo_dto::ResEntryTodoDto}, error::domain_error::DomainResult};
+
+#[async_trait::async_trait]
+#[mockall::automock]
+pub trait SubtaskRepository {
+    async fn add_subtask(&self, dto: ReqCreateSubtaskDto, origin: ChangeOrigin) -> DomainResult<TaskChangeDto>;
+    async fn get_subtasks(&self, parent_id: i32) -> DomainResult<Vec<ResEntryTodoDto>>;
+    /// `subtask_ids` must list every subtask of the parent exactly once.
+    /// Returns the subtasks in their new order and a change for each one that moved.
+    async fn reorder_subtasks(&self, parent_id: i32, subtask_ids: Vec<i32>, origin: ChangeOrigin) -> DomainResult<(Vec<ResEntryTodoDto>, Vec<TaskChangeDto>)>;
+    /// Tick a subtask off or reopen it, returns the parent with its updated roll-up and the change of the subtask.
+    async fn set_subtask_done(&self, subtask_id: i32, is_done: bool, origin: ChangeOrigin) -> DomainResult<(ResEntryTodoDto, TaskChangeDto)>;
+}
\ No newline at end of file
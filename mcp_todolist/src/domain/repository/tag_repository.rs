@@ -0,0 +1,13 @@
+use crate::domain::{dto::{audit_dto::{ChangeOrigin, TaskChangeDto}, tag_dto::{ReqCreateTagDto, ReqUpdateTagDto, ResEntryTagDto}}, error::domain_error::DomainResult};
+
+#[async_trait::async_trait]
+#[mockall::automock]
+pub trait TagRepository {
+    async fn create_tag(&self, dto: ReqCreateTagDto) -> DomainResult<ResEntryTagDto>;
+    async fn update_tag(&self, tag_id: i32, dto: ReqUpdateTagDto) -> DomainResult<ResEntryTagDto>;
+    async fn get_all_tags(&self) -> DomainResult<Vec<ResEntryTagDto>>;
+    async fn delete_tag(&self, tag_id: i32) -> DomainResult<()>;
+    /// Attach tags by name, creating the ones that do not exist yet.
+    async fn attach_tags(&self, task_id: i32, names: Vec<String>, origin: ChangeOrigin) -> DomainResult<TaskChangeDto>;
+    async fn detach_tags(&self, task_id: i32, names: Vec<String>, origin: ChangeOrigin) -> DomainResult<TaskChangeDto>;
+}
\ No newline at end of file
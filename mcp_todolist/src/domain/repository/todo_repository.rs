@@ -1,22 +1,34 @@
-use anyhow::Result;
+use chrono::{DateTime, Utc};
 
-use crate::domain::dto::todo_dto::{ReqCreateTodoDto, ReqUpdateTodoDto, ResEntryTodoDto};
+use crate::domain::{dto::{audit_dto::{ChangeOrigin, TaskChangeDto}, batch_dto::{ReqBatchTodoDto, ResBatchResultDto}, query_dto::{ResTodoPageDto, TodoQuery}, todo_dto::{ReqCreateTodoDto, ReqUpdateTodoDto, ResEntryTodoDto}}, error::domain_error::DomainResult};
 
 #[async_trait::async_trait]
 #[mockall::automock]
 pub trait TodoOperationRepository {
-    async fn create_task(&self, dto: ReqCreateTodoDto) -> Result<ResEntryTodoDto>;
-    async fn update_task(&self, task_id: i32, dto: ReqUpdateTodoDto) -> Result<ResEntryTodoDto>;
-    async fn get_by_id(&self, task_id: i32) -> Result<ResEntryTodoDto>;
-    async fn get_all(&self) -> Result<Vec<ResEntryTodoDto>>;
-    async fn delete_task(&self, task_id: i32) -> Result<()>;
+    /// Mutations store their audit entry in the same transaction and hand the change back to be published.
+    async fn create_task(&self, dto: ReqCreateTodoDto, origin: ChangeOrigin) -> DomainResult<TaskChangeDto>;
+    /// Completing a recurring task also creates its next occurrence, returned as the second change.
+    async fn update_task(&self, task_id: i32, dto: ReqUpdateTodoDto, origin: ChangeOrigin) -> DomainResult<(TaskChangeDto, Option<TaskChangeDto>)>;
+    async fn get_by_id(&self, task_id: i32) -> DomainResult<ResEntryTodoDto>;
+    async fn get_all(&self) -> DomainResult<Vec<ResEntryTodoDto>>;
+    async fn get_all_in_list(&self, list_id: i32) -> DomainResult<Vec<ResEntryTodoDto>>;
+    async fn list_tasks(&self, query: TodoQuery) -> DomainResult<ResTodoPageDto>;
+    async fn get_overdue(&self, now: DateTime<Utc>) -> DomainResult<Vec<ResEntryTodoDto>>;
+    async fn get_due_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> DomainResult<Vec<ResEntryTodoDto>>;
+    async fn delete_task(&self, task_id: i32, origin: ChangeOrigin) -> DomainResult<TaskChangeDto>;
+    /// Create, complete then delete in a single transaction, rolled back as a whole when any item fails.
+    /// Also returns the next occurrences created for the recurring tasks it completed.
+    async fn run_batch(&self, batch: ReqBatchTodoDto, origin: ChangeOrigin) -> DomainResult<(ResBatchResultDto, Vec<TaskChangeDto>)>;
 }
 
 
 #[async_trait::async_trait]
 #[mockall::automock]
 pub trait TodoUtilityRepository {
-    async fn count_all_task(&self) -> Result<i32>;
-    async fn count_done_task(&self) -> Result<i32>;
-    async fn count_undone_task(&self) -> Result<i32>;
+    async fn count_all_task(&self) -> DomainResult<i32>;
+    async fn count_done_task(&self) -> DomainResult<i32>;
+    async fn count_undone_task(&self) -> DomainResult<i32>;
+    async fn count_all_task_in_list(&self, list_id: i32) -> DomainResult<i32>;
+    async fn count_done_task_in_list(&self, list_id: i32) -> DomainResult<i32>;
+    async fn count_undone_task_in_list(&self, list_id: i32) -> DomainResult<i32>;
 }
\ No newline at end of file
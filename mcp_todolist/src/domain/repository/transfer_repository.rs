@@ -0,0 +1,10 @@
+use crate::domain::{dto::{audit_dto::ChangeOrigin, transfer_dto::{ResImportReportDto, TransferDocumentDto}}, error::domain_error::DomainResult};
+
+#[async_trait::async_trait]
+#[mockall::automock]
+pub trait TransferRepository {
+    /// Create the tasks of a validated document in one transaction, skipping the duplicates.
+    /// Missing lists are created by name and every task created is audited as made by `origin`.
+    /// On a dry run the report is built and nothing is stored.
+    async fn import_tasks(&self, document: TransferDocumentDto, dry_run: bool, origin: ChangeOrigin) -> DomainResult<ResImportReportDto>;
+}
\ No newline at end of file
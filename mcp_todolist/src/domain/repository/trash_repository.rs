@@ -0,0 +1,14 @@
+use crate::domain::{dto::{audit_dto::{ChangeOrigin, TaskChangeDto}, todo_dto::ResEntryTodoDto}, error::domain_error::DomainResult};
+
+#[async_trait::async_trait]
+#[mockall::automock]
+pub trait TrashRepository {
+    /// Trashed tasks and subtasks, most recently deleted first.
+    async fn list_trash(&self) -> DomainResult<Vec<ResEntryTodoDto>>;
+    /// Bring a trashed task back along with the subtasks trashed together with it.
+    async fn restore_task(&self, task_id: i32, origin: ChangeOrigin) -> DomainResult<TaskChangeDto>;
+    /// Permanently delete a trashed task and its subtasks, returns the ids removed.
+    async fn purge_task(&self, task_id: i32) -> DomainResult<Vec<i32>>;
+    /// Permanently delete everything in the trash, returns the ids removed.
+    async fn empty_trash(&self) -> DomainResult<Vec<i32>>;
+}
\ No newline at end of file
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::{dto::{event_dto::ChangeKind, webhook_dto::{ReqCreateWebhookDto, ReqUpdateWebhookDto, ResWebhookDeliveryDto, ResWebhookDto, ResWebhookPayloadDto, WebhookAttemptDto, WebhookDeliveryJob}}, error::domain_error::DomainResult};
+
+#[async_trait::async_trait]
+#[mockall::automock]
+pub trait WebhookRepository {
+    /// Register a webhook signing its deliveries with `secret`, the one of the DTO is ignored.
+    async fn create_webhook(&self, dto: ReqCreateWebhookDto, secret: String) -> DomainResult<ResWebhookDto>;
+    async fn update_webhook(&self, webhook_id: i32, dto: ReqUpdateWebhookDto) -> DomainResult<ResWebhookDto>;
+    async fn get_webhook(&self, webhook_id: i32) -> DomainResult<ResWebhookDto>;
+    async fn get_all_webhooks(&self) -> DomainResult<Vec<ResWebhookDto>>;
+    /// Delete a webhook along with its delivery log.
+    async fn delete_webhook(&self, webhook_id: i32) -> DomainResult<()>;
+    /// Latest deliveries of a webhook, newest first.
+    async fn get_webhook_deliveries(&self, webhook_id: i32, limit: u32) -> DomainResult<Vec<ResWebhookDeliveryDto>>;
+
+    /// Queue a delivery of `payload` for every active webhook listening to `kind`, returns how many were queued.
+    async fn enqueue_webhook_deliveries(&self, kind: ChangeKind, payload: ResWebhookPayloadDto) -> DomainResult<usize>;
+    /// Pending deliveries whose next attempt is due at `now`, oldest first.
+    async fn get_due_webhook_deliveries(&self, now: DateTime<Utc>, limit: u32) -> DomainResult<Vec<WebhookDeliveryJob>>;
+    async fn record_webhook_attempt(&self, delivery_id: i32, attempt: WebhookAttemptDto) -> DomainResult<()>;
+}
\ No newline at end of file
@@ -1,5 +1,70 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    fired_reminders (id) {
+        id -> Integer,
+        todo_id -> Integer,
+        kind -> Text,
+        fire_at -> Timestamp,
+        fired_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    lists (id) {
+        id -> Integer,
+        name -> Text,
+        description -> Nullable<Text>,
+        created_at -> Nullable<Timestamp>,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    recurrences (id) {
+        id -> Integer,
+        frequency -> Text,
+        interval -> Integer,
+        starts_at -> Timestamp,
+        until -> Nullable<Timestamp>,
+        max_occurrences -> Nullable<Integer>,
+        stopped_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    tags (id) {
+        id -> Integer,
+        name -> Text,
+        color -> Nullable<Text>,
+        created_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    task_audit (id) {
+        id -> Integer,
+        todo_id -> Integer,
+        action -> Text,
+        before_snapshot -> Nullable<Text>,
+        after_snapshot -> Nullable<Text>,
+        origin -> Text,
+        client_name -> Nullable<Text>,
+        session_id -> Nullable<Text>,
+        changed_at -> Timestamp,
+        operation_id -> Nullable<Text>,
+        reverted_by -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    todo_tags (todo_id, tag_id) {
+        todo_id -> Integer,
+        tag_id -> Integer,
+    }
+}
+
 diesel::table! {
     todolist (id) {
         id -> Integer,
@@ -8,5 +73,63 @@ diesel::table! {
         is_done -> Bool,
         created_at -> Nullable<Timestamp>,
         updated_at -> Nullable<Timestamp>,
+        priority -> Integer,
+        due_date -> Nullable<Timestamp>,
+        list_id -> Nullable<Integer>,
+        parent_id -> Nullable<Integer>,
+        position -> Integer,
+        deleted_at -> Nullable<Timestamp>,
+        version -> Integer,
+        recurrence_id -> Nullable<Integer>,
+        occurrence -> Nullable<Integer>,
+        remind_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> Integer,
+        webhook_id -> Integer,
+        event -> Text,
+        payload -> Text,
+        status -> Text,
+        attempts -> Integer,
+        response_status -> Nullable<Integer>,
+        last_error -> Nullable<Text>,
+        created_at -> Timestamp,
+        last_attempt_at -> Nullable<Timestamp>,
+        next_attempt_at -> Nullable<Timestamp>,
+        delivered_at -> Nullable<Timestamp>,
     }
 }
+
+diesel::table! {
+    webhooks (id) {
+        id -> Integer,
+        url -> Text,
+        events -> Text,
+        secret -> Text,
+        active -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::joinable!(fired_reminders -> todolist (todo_id));
+diesel::joinable!(todo_tags -> tags (tag_id));
+diesel::joinable!(todo_tags -> todolist (todo_id));
+diesel::joinable!(todolist -> lists (list_id));
+diesel::joinable!(todolist -> recurrences (recurrence_id));
+diesel::joinable!(webhook_deliveries -> webhooks (webhook_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    fired_reminders,
+    lists,
+    recurrences,
+    tags,
+    task_audit,
+    todo_tags,
+    todolist,
+    webhook_deliveries,
+    webhooks,
+);
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+use rocket::{get, routes, Route, State};
+use crate::application::usecase::todo_usecase::TodolistUseCase;
+use crate::domain::dto::audit_dto::ResTaskAuditDto;
+use crate::infrastructure::http_handler::response_type::Response;
+
+use super::http_handler::parse_todo_id;
+use super::response_type::{ResponseBody, SuccessResponse};
+
+
+
+pub fn audit_routes() -> Vec<Route> {
+    routes![
+        get_history
+    ]
+}
+
+
+/// Retrieve the change history of a task, oldest first.
+///
+/// Every create, update, completion, delete and restore is recorded with the task before
+/// and after the change, when it happened and who made it (HTTP or MCP, and the client name).
+/// The history stays available once the task is in the trash or purged.
+///
+/// # Path Parameters
+/// - `todo_id`: ID of the task (as a string, parsed to i32)
+///
+/// # Responses
+/// - `200 OK`: `data` holds the entries as `Vec<ResTaskAuditDto>`
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No task and no history exist for the given ID
+/// - `500 Internal Server Error`: Failed to retrieve the history due to a storage error
+#[utoipa::path(
+    get,
+    path = "/todo/{todo_id}/history",
+    params(
+        ("todo_id" = i32, Path, description = "Unique identifier of the task")
+    ),
+    responses(
+        (status = 200, description = "History retrieved successfully", body = ResponseBody<Vec<ResTaskAuditDto>>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "Task not found", body = ResponseBody<String>),
+        (status = 500, description = "Failed to retrieve history. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/todo/<todo_id>/history")]
+pub async fn get_history(
+    todo_id: String,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<Vec<ResTaskAuditDto>> {
+    let id = parse_todo_id(&todo_id)?;
+    match state.get_task_history(id).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "History retrieved successfully")),
+        Err(e) => Err(e.into())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+use rocket::{http::Status, post, routes, serde::json::Json, Route, State};
+use crate::domain::dto::audit_dto::ChangeOrigin;
+use crate::application::usecase::todo_usecase::TodolistUseCase;
+use crate::domain::dto::batch_dto::{ReqBatchTodoDto, ResBatchResultDto};
+use crate::infrastructure::http_handler::response_type::Response;
+
+use super::response_type::{ResponseBody, SuccessResponse};
+
+
+
+pub fn batch_routes() -> Vec<Route> {
+    routes![
+        run_batch
+    ]
+}
+
+
+/// Create, complete and delete several tasks in one all-or-nothing transaction.
+///
+/// Items are applied in the order `create`, `complete`, `delete`. When any item fails,
+/// nothing is applied and `data.items` tells which item failed and why
+/// (`failed`) and which ones were dropped with it (`rolled_back`).
+///
+/// # Request Body
+/// - `create`: (Optional) Tasks to create, same shape as `POST /v1/todo`
+/// - `complete`: (Optional) IDs of the tasks to mark as done
+/// - `delete`: (Optional) IDs of the tasks to move to the trash, their subtasks go with them
+///
+/// # Responses
+/// - `200 OK`: Every item was applied, `data.committed` is `true`
+/// - `422 Unprocessable Entity`: The batch is empty or too large (`data` is `null`),
+///   or an item failed and the batch was rolled back (`data.committed` is `false`)
+/// - `500 Internal Server Error`: The batch failed due to a storage error
+#[utoipa::path(
+    post,
+    path = "/todo/batch",
+    request_body = ReqBatchTodoDto,
+    responses(
+        (status = 200, description = "Batch applied", body = ResponseBody<ResBatchResultDto>),
+        (status = 422, description = "Batch rejected or rolled back", body = ResponseBody<ResBatchResultDto>),
+        (status = 500, description = "Failed to apply batch. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[post("/todo/batch", data = "<batch_data>")]
+pub async fn run_batch(
+    batch_data: Json<ReqBatchTodoDto>,
+    origin: ChangeOrigin,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResBatchResultDto> {
+    match state.run_batch(batch_data.into_inner(), &origin).await {
+        Ok(data) if data.committed => Ok(SuccessResponse::ok(data, "Batch applied successfully")),
+        Ok(data) => Ok(SuccessResponse::new(Status::UnprocessableEntity, data, "Batch rolled back, no change was applied")),
+        Err(e) => Err(e.into())
+    }
+}
\ No newline at end of file
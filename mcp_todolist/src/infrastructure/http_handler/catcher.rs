@@ -0,0 +1,44 @@
+use rocket::{catch, catchers, http::Status, Catcher, Request};
+
+use super::response_type::ErrorResponse;
+
+
+
+/// Errors Rocket raises itself (unknown route, body the `Json` guard rejects, panic)
+/// answered with the same JSON envelope as the routes.
+pub fn json_catchers() -> Vec<Catcher> {
+    catchers![
+        bad_request,
+        not_found,
+        unprocessable_entity,
+        internal_error,
+        any_error
+    ]
+}
+
+
+#[catch(400)]
+fn bad_request() -> ErrorResponse {
+    ErrorResponse::bad_request("The request could not be read, check its body and parameters")
+}
+
+#[catch(404)]
+fn not_found(request: &Request<'_>) -> ErrorResponse {
+    ErrorResponse::new(Status::NotFound, "NOT_FOUND", format!("No route for {} {}", request.method(), request.uri()))
+}
+
+#[catch(422)]
+fn unprocessable_entity() -> ErrorResponse {
+    ErrorResponse::new(Status::UnprocessableEntity, "VALIDATION_ERROR", "The request body is malformed or misses a required field")
+}
+
+#[catch(500)]
+fn internal_error() -> ErrorResponse {
+    ErrorResponse::new(Status::InternalServerError, "INTERNAL_ERROR", "Internal server error, please try again")
+}
+
+#[catch(default)]
+fn any_error(status: Status, _request: &Request<'_>) -> ErrorResponse {
+    let code = status.reason().unwrap_or("Error").to_ascii_uppercase().replace([' ', '-'], "_");
+    ErrorResponse::new(status, code, status.reason().unwrap_or("Request failed"))
+}
\ No newline at end of file
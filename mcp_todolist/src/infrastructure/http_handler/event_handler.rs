@@ -0,0 +1,106 @@
+use std::sync::Arc;
+use rocket::{get, request::{FromRequest, Outcome}, response::stream::{Event, EventStream}, routes, tokio::{select, sync::broadcast::error::RecvError}, Request, Route, Shutdown, State};
+use serde_json::json;
+use crate::application::usecase::todo_usecase::TodolistUseCase;
+use crate::domain::dto::event_dto::ResChangeEventDto;
+
+
+
+pub fn event_routes() -> Vec<Route> {
+    routes![
+        change_events
+    ]
+}
+
+
+/// Value of the `Last-Event-ID` header an `EventSource` sends back when it reconnects.
+pub struct LastEventId(Option<u64>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for LastEventId {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let id = request
+            .headers()
+            .get_one("Last-Event-ID")
+            .and_then(|raw| raw.trim().parse().ok());
+        Outcome::Success(LastEventId(id))
+    }
+}
+
+
+fn change_event(event: &ResChangeEventDto) -> Event {
+    Event::json(event).id(event.id.to_string()).event(event.kind.as_str())
+}
+
+/// Tells the client events were lost and it has to reload what it shows.
+fn resync_event(reason: &str) -> Event {
+    Event::json(&json!({ "reason": reason })).event("resync")
+}
+
+
+/// Stream task, list and tag changes as Server-Sent Events.
+///
+/// Every event is named after its kind (`created`, `updated`, `completed`, `deleted`, `restored`), carries
+/// its sequence number as the SSE `id` and a `ResChangeEventDto` as JSON data.
+/// Clients resume with the standard `Last-Event-ID` header (sent automatically by `EventSource`)
+/// or the `since` query parameter; the changes made in between are replayed first.
+/// A `resync` event means some changes could not be replayed and the client should reload.
+///
+/// # Query Parameters
+/// - `since`: (Optional) Replay the changes after this event id, the header wins when both are given
+///
+/// # Responses
+/// - `200 OK`: `text/event-stream` that stays open until the client or the server goes away
+#[utoipa::path(
+    get,
+    path = "/events",
+    params(
+        ("since" = Option<u64>, Query, description = "Replay the changes after this event id"),
+        ("Last-Event-ID" = Option<u64>, Header, description = "Id of the last event received, sent by EventSource on reconnect")
+    ),
+    responses(
+        (status = 200, description = "Change feed", content_type = "text/event-stream", body = ResChangeEventDto)
+    )
+)]
+#[get("/events?<since>")]
+pub fn change_events(
+    since: Option<u64>,
+    last_event_id: LastEventId,
+    state: &State<Arc<TodolistUseCase>>,
+    mut shutdown: Shutdown
+) -> EventStream![] {
+    let resume_from = last_event_id.0.or(since);
+    let (missed, complete, mut live) = match resume_from {
+        Some(since) => {
+            let feed = state.change_feed_since(since);
+            (feed.missed, feed.complete, feed.live)
+        }
+        None => (Vec::new(), true, state.subscribe_changes()),
+    };
+
+    EventStream! {
+        if !complete {
+            yield resync_event("history_unavailable");
+        }
+        for event in &missed {
+            yield change_event(event);
+        }
+
+        loop {
+            let event = select! {
+                received = live.recv() => match received {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(_)) => {
+                        yield resync_event("lagged");
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                _ = &mut shutdown => break,
+            };
+            yield change_event(&event);
+        }
+    }
+}
\ No newline at end of file
@@ -1,11 +1,14 @@
 use std::sync::Arc;
-use rocket::{delete, get, put, routes, Route};
-use rocket::{http::Status, post, serde::json::Json, State};
-use crate::domain::dto::todo_dto::ReqUpdateTodoDto;
+use rocket::{delete, get, put, request::{FromRequest, Outcome}, routes, Request, Route};
+use rocket::{post, serde::json::Json, State};
+use crate::domain::dto::audit_dto::ChangeOrigin;
+use crate::domain::error::domain_error::DomainError;
+use crate::domain::dto::query_dto::{ResTodoPageDto, TodoQuery, TodoQueryParams};
+use crate::domain::dto::todo_dto::{DueToday, DueWithinDays, ReqUpdateTodoDto};
 use crate::infrastructure::http_handler::response_type::Response;
 use crate::{application::usecase::todo_usecase::TodolistUseCase, domain::dto::todo_dto::{ReqCreateTodoDto, ResEntryTodoDto}};
 
-use super::response_type::{ErrorResponse, SuccessResponse};
+use super::response_type::{ErrorResponse, ResponseBody, SuccessResponse, TaggedResponse, TaggedResult};
 
 
 
@@ -18,11 +21,56 @@ pub fn todolist_routes() -> Vec<Route> {
         delete_todo,
         count_all_task,
         count_done_task,
-        count_undone_task
+        count_undone_task,
+        get_overdue,
+        get_due_today,
+        get_due_within
     ]
 }
 
 
+pub(crate) fn parse_todo_id(todo_id: &str) -> Result<i32, ErrorResponse> {
+    todo_id
+        .parse()
+        .map_err(|_| ErrorResponse::bad_request(format!("Invalid todo id : {}", todo_id)))
+}
+
+
+/// Raw `If-Match` header, the `ETag`s of the task versions the client accepts.
+pub struct IfMatch(Option<String>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IfMatch {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(IfMatch(request.headers().get_one("If-Match").map(str::to_string)))
+    }
+}
+
+impl IfMatch {
+    /// Versions the client accepts, `None` when the header is missing or `*`.
+    ///
+    /// The header may list several entity tags, the ones that are not task versions can never match and are left out.
+    pub fn versions(&self) -> Result<Option<Vec<i32>>, ErrorResponse> {
+        let Some(raw) = self.0.as_deref().map(str::trim) else {
+            return Ok(None);
+        };
+        if raw == "*" {
+            return Ok(None);
+        }
+        let tags: Vec<&str> = raw.split(',').map(str::trim).collect();
+        if tags.iter().any(|tag| tag.is_empty()) {
+            return Err(ErrorResponse::bad_request(format!("Invalid If-Match header : {}", raw)));
+        }
+        Ok(Some(tags
+            .into_iter()
+            .filter_map(|tag| tag.trim_start_matches("W/").trim_matches('"').parse().ok())
+            .collect()))
+    }
+}
+
+
 /// Create a new todo entry.
 ///
 /// This endpoint allows the client to create a new todo item by providing a title and description.
@@ -31,27 +79,35 @@ pub fn todolist_routes() -> Vec<Route> {
 /// # Request Body
 /// - `title`: Title of the task (String)
 /// - `description`: Detailed information about the task (String)
+/// - `priority`: (Optional) `low`, `normal` (default), `high` or `urgent`
+/// - `due_date`: (Optional) RFC 3339 due date
+/// - `remind_at`: (Optional) RFC 3339 date to send a reminder at
+/// - `list_id`: (Optional) ID of the list the task belongs to
+/// - `tags`: (Optional) Tag names to attach, unknown tags are created
 ///
 /// # Responses
-/// - `200 OK`: Task created successfully, returns a `ResEntryTodoDto` object.
-/// - `400 Bad Request`: Task creation failed due to invalid input or internal error.
+/// - `200 OK`: Task created successfully, `data` holds the new `ResEntryTodoDto`.
+/// - `422 Unprocessable Entity`: Input validation failed.
+/// - `500 Internal Server Error`: Task creation failed due to a storage error.
 #[utoipa::path(
     post,
     path = "/todo",
     request_body = ReqCreateTodoDto,
     responses(
-        (status = 200, description = "Todo created successfully", body = ResEntryTodoDto),
-        (status = 400, description = "Failed to create todo. Input validation failed or internal error occurred")
+        (status = 200, description = "Todo created successfully", body = ResponseBody<ResEntryTodoDto>),
+        (status = 422, description = "Input validation failed", body = ResponseBody<String>),
+        (status = 500, description = "Failed to create todo. Internal storage error", body = ResponseBody<String>)
     )
 )]
 #[post("/todo", data = "<todo_data>")]
 pub async fn create_todo(
     todo_data: Json<ReqCreateTodoDto>,
+    origin: ChangeOrigin,
     state: &State<Arc<TodolistUseCase>>
-) -> Response<String> {
-    match state.create_task(todo_data.into_inner()).await {
-        Ok(data) => Ok(SuccessResponse((Status::Ok, format!("Task create succesfull {:?}",data)))),
-        Err(_) => Err(ErrorResponse((Status::BadRequest, format!("Failed to create task please try again"))))
+) -> Response<ResEntryTodoDto> {
+    match state.create_task(todo_data.into_inner(), &origin).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Task created successfully")),
+        Err(e) => Err(e.into())
     }
 }
 
@@ -69,29 +125,68 @@ pub async fn create_todo(
 /// - `title`: (Optional) New title for the task
 /// - `description`: (Optional) New description for the task
 /// - `is_done`: (Optional) Boolean to mark task as done or not
+/// - `priority`: (Optional) New priority
+/// - `due_date`: (Optional) New RFC 3339 due date, `null` clears it
+/// - `remind_at`: (Optional) New RFC 3339 reminder date, `null` cancels the reminder
+/// - `list_id`: (Optional) Move the task to this list, `null` takes it out of its list
+/// - `expected_version`: (Optional) Version the change is based on, same as `If-Match`
+///
+/// # Headers
+/// - `If-Match`: (Optional) `ETag` of the task version the change is based on, or a comma-separated list
+///   of accepted ones, wins over `expected_version`
 ///
 /// # Responses
-/// - `200 OK`: Task updated successfully, returns the updated `ResEntryTodoDto`
-/// - `400 Bad Request`: Update failed due to missing ID, invalid input, or task not found
+/// - `200 OK`: Task updated successfully, `data` holds the updated `ResEntryTodoDto` and `ETag` its new version
+/// - `400 Bad Request`: The `If-Match` header has an empty entity tag
+/// - `404 Not Found`: No task exists with the given ID
+/// - `409 Conflict`: The task changed since the expected version, or is at none of the `If-Match` versions
+/// - `422 Unprocessable Entity`: Input validation failed
+/// - `500 Internal Server Error`: Update failed due to a storage error
 #[utoipa::path(
     put,
     path = "/todo",
     request_body = ReqUpdateTodoDto,
+    params(
+        ("If-Match" = Option<String>, Header, description = "ETag of the task version the change is based on, or a comma-separated list of accepted ones")
+    ),
     responses(
-        (status = 200, description = "Todo updated successfully", body = ResEntryTodoDto),
-        (status = 400, description = "Failed to update todo. Invalid input or task not found")
+        (status = 200, description = "Todo updated successfully", body = ResponseBody<ResEntryTodoDto>,
+            headers(("ETag" = String, description = "Version of the updated task"))),
+        (status = 400, description = "Invalid If-Match header", body = ResponseBody<String>),
+        (status = 404, description = "Task not found", body = ResponseBody<String>),
+        (status = 409, description = "Task changed since the expected version", body = ResponseBody<String>),
+        (status = 422, description = "Input validation failed", body = ResponseBody<String>),
+        (status = 500, description = "Failed to update todo. Internal storage error", body = ResponseBody<String>)
     )
 )]
 #[put("/todo", data = "<todo_data>")]
 pub async fn update_todo(
     todo_data: Json<ReqUpdateTodoDto>,
+    if_match: IfMatch,
+    origin: ChangeOrigin,
     state: &State<Arc<TodolistUseCase>>
-) -> Response<String> {
-    let json_val = todo_data.clone();
-    let extract_id = json_val.into_inner().id;
-    match state.update_task(extract_id, todo_data.into_inner()).await {
-        Ok(data) => Ok(SuccessResponse((Status::Ok, format!("Task Update Successfull {:?}", data)))),
-        Err(_) => Err(ErrorResponse((Status::BadRequest, format!("Failed to update the task"))))
+) -> TaggedResult<ResEntryTodoDto> {
+    let mut dto = todo_data.into_inner();
+    if let Some(versions) = if_match.versions()? {
+        let expected = match versions.as_slice() {
+            [version] => *version,
+            // keep the listed version the task is at, the update still refuses it if the task moves on meanwhile
+            _ => {
+                let current = state.get_by_id(dto.id).await?.version;
+                if !versions.contains(&current) {
+                    return Err(DomainError::conflict(format!("Task {} is at version {}, which If-Match does not list", dto.id, current)).into());
+                }
+                current
+            }
+        };
+        dto.expected_version = Some(expected);
+    }
+    match state.update_task(dto.id, dto, &origin).await {
+        Ok(data) => {
+            let version = data.version;
+            Ok(TaggedResponse::new(SuccessResponse::ok(data, "Task updated successfully"), version))
+        },
+        Err(e) => Err(e.into())
     }
 }
 
@@ -108,8 +203,10 @@ pub async fn update_todo(
 /// - `todo_id`: ID of the todo item to retrieve (as a string, parsed to i32)
 ///
 /// # Responses
-/// - `200 OK`: Task retrieved successfully, returns a `ResEntryTodoDto` object
-/// - `400 Bad Request`: Failed to retrieve task, either due to invalid ID format or item not found
+/// - `200 OK`: Task retrieved successfully, `data` holds the `ResEntryTodoDto` and `ETag` its version
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No task exists with the given ID
+/// - `500 Internal Server Error`: Failed to retrieve task due to a storage error
 #[utoipa::path(
     get,
     path = "/todo/{todo_id}",
@@ -117,48 +214,72 @@ pub async fn update_todo(
         ("todo_id" = i32, Path, description = "Unique identifier of the todo task")
     ),
     responses(
-        (status = 200, description = "Todo retrieved successfully", body = ResEntryTodoDto),
-        (status = 400, description = "Failed to retrieve todo. Invalid ID or item not found")
+        (status = 200, description = "Todo retrieved successfully", body = ResponseBody<ResEntryTodoDto>,
+            headers(("ETag" = String, description = "Version of the task"))),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "Task not found", body = ResponseBody<String>),
+        (status = 500, description = "Failed to retrieve todo. Internal storage error", body = ResponseBody<String>)
     )
 )]
 #[get("/todo/<todo_id>")]
 pub async fn get_by_id(
     todo_id: String,
     state: &State<Arc<TodolistUseCase>>
-) -> Response<String> {
-    let id: i32 = todo_id.parse().unwrap();
+) -> TaggedResult<ResEntryTodoDto> {
+    let id = parse_todo_id(&todo_id)?;
     match state.get_by_id(id).await {
-        Ok(data) => Ok(SuccessResponse((Status::Ok, format!("{:?}",data)))),
-        Err(_) => Err(ErrorResponse((Status::BadRequest, format!("Fail to get todo by id : {:?}",todo_id))))
+        Ok(data) => {
+            let version = data.version;
+            Ok(TaggedResponse::new(SuccessResponse::ok(data, "Task retrieved successfully"), version))
+        },
+        Err(e) => Err(e.into())
     }
 }
 
 
 
 
-/// Retrieve all todo entries.
+/// Retrieve todo entries page by page.
 ///
-/// This endpoint returns a list of all existing todo tasks. Each item contains the full details
-/// of the task including ID, title, description, completion status, and timestamps.
+/// This endpoint returns one page of todo tasks matching the optional filters. Each item
+/// contains the full details of the task including ID, title, description, completion status,
+/// and timestamps.
+///
+/// # Query Parameters
+/// - `is_done`: Only tasks with this completion status
+/// - `priority`: Only tasks with this priority
+/// - `list_id`: Only tasks of this list
+/// - `tags`: Only tasks carrying every given tag, repeat the parameter for several tags
+/// - `search`: Text searched in title and description
+/// - `created_from` / `created_to` / `updated_from` / `updated_to`: Date range (RFC 3339 or YYYY-MM-DD), a date-only `_to` bound includes that whole day
+/// - `sort_by`: `created_at` (default), `updated_at`, `title`, `id`, `priority` or `due_date`
+/// - `sort_dir`: `desc` (default) or `asc`
+/// - `limit`: Page size, 1 to 200 (default 50)
+/// - `offset` / `cursor`: Where the page starts, `cursor` is the `next_cursor` of the previous page and needs the same `sort_by` and `sort_dir`
 ///
 /// # Responses
-/// - `200 OK`: Returns a list of all todo tasks as `Vec<ResEntryTodoDto>`
-/// - `400 Bad Request`: Failed to retrieve tasks due to an internal error
+/// - `200 OK`: `data` holds a `ResTodoPageDto` with the items, the total and the next cursor
+/// - `422 Unprocessable Entity`: A query parameter is invalid, or the cursor belongs to another sort
+/// - `500 Internal Server Error`: Failed to retrieve tasks due to a storage error
 #[utoipa::path(
     get,
     path = "/todo",
+    params(TodoQueryParams),
     responses(
-        (status = 200, description = "All todos retrieved successfully", body = [ResEntryTodoDto]),
-        (status = 400, description = "Failed to retrieve todos. Internal error occurred")
+        (status = 200, description = "Todos retrieved successfully", body = ResponseBody<ResTodoPageDto>),
+        (status = 422, description = "Invalid query parameter", body = ResponseBody<String>),
+        (status = 500, description = "Failed to retrieve todos. Internal storage error", body = ResponseBody<String>)
     )
 )]
-#[get("/todo")]
+#[get("/todo?<query..>")]
 pub async fn get_all(
+    query: TodoQueryParams,
     state: &State<Arc<TodolistUseCase>>
-) -> Response<String> {
-    match state.get_all().await {
-        Ok(data) => Ok(SuccessResponse((Status::Ok, format!("{:?}", data)))),
-        Err(_) => Err(ErrorResponse((Status::BadRequest, format!("Failed to get all todo"))))
+) -> Response<ResTodoPageDto> {
+    let query = TodoQuery::try_from(query)?;
+    match state.list_tasks(query).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Tasks retrieved successfully")),
+        Err(e) => Err(e.into())
     }
 }
 
@@ -169,17 +290,21 @@ pub async fn get_all(
 
 
 
+
 /// Delete a todo entry by ID.
 ///
 /// This endpoint deletes a specific todo task using its unique ID. If the task exists,
-/// it will be permanently removed from the database.
+/// it is moved to the trash together with its subtasks; it can be restored from there
+/// or purged for good (see `/todo/trash`).
 ///
 /// # Path Parameters
 /// - `todo_id`: ID of the todo task to delete (as a string, parsed to i32)
 ///
 /// # Responses
-/// - `200 OK`: Task deleted successfully, returns the deleted task ID or confirmation
-/// - `400 Bad Request`: Deletion failed due to invalid ID or task not found
+/// - `200 OK`: Task deleted successfully, `data` holds the deleted task ID
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No task exists with the given ID
+/// - `500 Internal Server Error`: Deletion failed due to a storage error
 #[utoipa::path(
     delete,
     path = "/todo/{todo_id}",
@@ -187,20 +312,23 @@ pub async fn get_all(
         ("todo_id" = i32, Path, description = "Unique identifier of the todo task to delete")
     ),
     responses(
-        (status = 200, description = "Todo deleted successfully"),
-        (status = 400, description = "Failed to delete todo. Invalid ID or task not found")
+        (status = 200, description = "Todo deleted successfully", body = ResponseBody<i32>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "Task not found", body = ResponseBody<String>),
+        (status = 500, description = "Failed to delete todo. Internal storage error", body = ResponseBody<String>)
     )
 )]
 #[delete("/todo/<todo_id>")]
 pub async fn delete_todo(
     todo_id: String,
+    origin: ChangeOrigin,
     state: &State<Arc<TodolistUseCase>>
-) -> Response<String>
+) -> Response<i32>
 {
-    let id: i32 = todo_id.parse().unwrap();
-    match state.delete_task(id).await {
-        Ok(data) => Ok(SuccessResponse((Status::Ok, format!("Task id : {:?} has deleted", data)))),
-        Err(_) => Err(ErrorResponse((Status::BadRequest, format!("Fail to delte task id: {:?}", id))))
+    let id = parse_todo_id(&todo_id)?;
+    match state.delete_task(id, &origin).await {
+        Ok(_) => Ok(SuccessResponse::ok(id, format!("Task id : {} has deleted", id))),
+        Err(e) => Err(e.into())
     }
 }
 
@@ -213,24 +341,24 @@ pub async fn delete_todo(
 /// This endpoint returns the total number of todo items stored in the system.
 ///
 /// # Responses
-/// - `200 OK`: Successfully counted tasks, returns the total count as a number wrapped in a string message
-/// - `400 Bad Request`: Failed to count tasks due to an internal error
+/// - `200 OK`: Successfully counted tasks, `data` holds the total count
+/// - `500 Internal Server Error`: Failed to count tasks due to a storage error
 #[utoipa::path(
     get,
     path = "/todo/all",
     responses(
-        (status = 200, description = "Successfully retrieved the total number of todo items"),
-        (status = 400, description = "Failed to retrieve todo count due to an internal error")
+        (status = 200, description = "Successfully retrieved the total number of todo items", body = ResponseBody<i32>),
+        (status = 500, description = "Failed to retrieve todo count due to a storage error", body = ResponseBody<String>)
     )
 )]
 #[get("/todo/all")]
 pub async fn count_all_task(
     state: &State<Arc<TodolistUseCase>>
-) -> Response<String> 
+) -> Response<i32> 
 {
     match state.count_all_task().await {
-        Ok(items) => Ok(SuccessResponse((Status::Ok, format!("all todo have {:?} items",items)))),
-        Err(_) => Err(ErrorResponse((Status::BadRequest, format!("Fail to get all count"))))
+        Ok(items) => Ok(SuccessResponse::ok(items, "Total number of todo items")),
+        Err(e) => Err(e.into())
     }
 }
 
@@ -245,23 +373,23 @@ pub async fn count_all_task(
 /// This endpoint returns the total number of todo items that have been marked as done.
 ///
 /// # Responses
-/// - `200 OK`: Successfully counted completed tasks, returns the total number in a message string
-/// - `400 Bad Request`: Failed to retrieve the count due to an internal error
+/// - `200 OK`: Successfully counted completed tasks, `data` holds the total number
+/// - `500 Internal Server Error`: Failed to retrieve the count due to a storage error
 #[utoipa::path(
     get,
     path = "/todo/done",
     responses(
-        (status = 200, description = "Successfully retrieved the total number of completed todo items"),
-        (status = 400, description = "Failed to retrieve completed todo count due to an internal error")
+        (status = 200, description = "Successfully retrieved the total number of completed todo items", body = ResponseBody<i32>),
+        (status = 500, description = "Failed to retrieve completed todo count due to a storage error", body = ResponseBody<String>)
     )
 )]
 #[get("/todo/done")]
 pub async fn count_done_task(
     state: &State<Arc<TodolistUseCase>>
-) -> Response<String> {
+) -> Response<i32> {
     match state.count_done_task().await {
-        Ok(items) => Ok(SuccessResponse((Status::Ok, format!("all todo have {:?} task that mark as done",items)))),
-        Err(_) => Err(ErrorResponse((Status::BadRequest, format!("Fail to count done task"))))
+        Ok(items) => Ok(SuccessResponse::ok(items, "Number of todo items marked as done")),
+        Err(e) => Err(e.into())
     }
 }
 
@@ -277,22 +405,235 @@ pub async fn count_done_task(
 /// This endpoint returns the total number of todo items that have **not** been marked as done.
 ///
 /// # Responses
-/// - `200 OK`: Successfully counted undone tasks, returns the total number in a message string
-/// - `400 Bad Request`: Failed to retrieve the count due to an internal error
+/// - `200 OK`: Successfully counted undone tasks, `data` holds the total number
+/// - `500 Internal Server Error`: Failed to retrieve the count due to a storage error
 #[utoipa::path(
     get,
     path = "/todo/undone",
     responses(
-        (status = 200, description = "Successfully retrieved the total number of undone todo items"),
-        (status = 400, description = "Failed to retrieve undone todo count due to an internal error")
+        (status = 200, description = "Successfully retrieved the total number of undone todo items", body = ResponseBody<i32>),
+        (status = 500, description = "Failed to retrieve undone todo count due to a storage error", body = ResponseBody<String>)
     )
 )]
 #[get("/todo/undone")]
 pub async fn count_undone_task(
     state: &State<Arc<TodolistUseCase>>
-) -> Response<String> {
+) -> Response<i32> {
     match state.count_undone_task().await {
-        Ok(items) => Ok(SuccessResponse((Status::Ok, format!("all todo have {:?} task that mark as undone",items)))),
-        Err(_) => Err(ErrorResponse((Status::BadRequest, format!("Fail to count undone task"))))
+        Ok(items) => Ok(SuccessResponse::ok(items, "Number of todo items not yet done")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+
+
+/// List overdue tasks.
+///
+/// This endpoint returns every undone task whose due date has already passed,
+/// oldest due date first.
+///
+/// # Responses
+/// - `200 OK`: `data` holds the overdue tasks as `Vec<ResEntryTodoDto>`
+/// - `500 Internal Server Error`: Failed to retrieve tasks due to a storage error
+#[utoipa::path(
+    get,
+    path = "/todo/overdue",
+    responses(
+        (status = 200, description = "Overdue todos retrieved successfully", body = ResponseBody<Vec<ResEntryTodoDto>>),
+        (status = 500, description = "Failed to retrieve overdue todos due to a storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/todo/overdue")]
+pub async fn get_overdue(
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<Vec<ResEntryTodoDto>> {
+    match state.get_overdue_tasks().await {
+        Ok(items) => Ok(SuccessResponse::ok(items, "Overdue tasks retrieved successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+
+
+/// List tasks due today.
+///
+/// This endpoint returns every undone task due between 00:00 and 24:00 today,
+/// on the UTC calendar day unless the caller gives its own offset.
+///
+/// # Query Parameters
+/// - `utc_offset_minutes`: Minutes east of UTC of the caller (-840 to 840), e.g. `420` for UTC+7
+///
+/// # Responses
+/// - `200 OK`: `data` holds the tasks due today as `Vec<ResEntryTodoDto>`
+/// - `422 Unprocessable Entity`: `utc_offset_minutes` is out of range
+/// - `500 Internal Server Error`: Failed to retrieve tasks due to a storage error
+#[utoipa::path(
+    get,
+    path = "/todo/due/today",
+    params(
+        ("utc_offset_minutes" = Option<i32>, Query, description = "Minutes east of UTC of the caller (-840 to 840), UTC when omitted")
+    ),
+    responses(
+        (status = 200, description = "Todos due today retrieved successfully", body = ResponseBody<Vec<ResEntryTodoDto>>),
+        (status = 422, description = "utc_offset_minutes is out of range", body = ResponseBody<String>),
+        (status = 500, description = "Failed to retrieve todos due today due to a storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/todo/due/today?<utc_offset_minutes>")]
+pub async fn get_due_today(
+    utc_offset_minutes: Option<i32>,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<Vec<ResEntryTodoDto>> {
+    match state.get_due_today_tasks(DueToday { utc_offset_minutes }).await {
+        Ok(items) => Ok(SuccessResponse::ok(items, "Tasks due today retrieved successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+
+
+/// List tasks due within the next N days.
+///
+/// This endpoint returns every undone task due from now until `days` days ahead.
+///
+/// # Query Parameters
+/// - `days`: Number of days to look ahead (1 to 365)
+///
+/// # Responses
+/// - `200 OK`: `data` holds the matching tasks as `Vec<ResEntryTodoDto>`
+/// - `422 Unprocessable Entity`: `days` is out of range
+/// - `500 Internal Server Error`: Failed to retrieve tasks due to a storage error
+#[utoipa::path(
+    get,
+    path = "/todo/due",
+    params(
+        ("days" = i64, Query, description = "Number of days to look ahead (1 to 365)")
+    ),
+    responses(
+        (status = 200, description = "Upcoming todos retrieved successfully", body = ResponseBody<Vec<ResEntryTodoDto>>),
+        (status = 422, description = "days is out of range", body = ResponseBody<String>),
+        (status = 500, description = "Failed to retrieve upcoming todos due to a storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/todo/due?<days>")]
+pub async fn get_due_within(
+    days: i64,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<Vec<ResEntryTodoDto>> {
+    match state.get_due_within_tasks(DueWithinDays { days }).await {
+        Ok(items) => Ok(SuccessResponse::ok(items, format!("Tasks due within {} days retrieved successfully", days))),
+        Err(e) => Err(e.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use rocket::{http::{ContentType, Header, Status}, local::asynchronous::Client};
+    use serde_json::{json, Value};
+
+    use crate::test_support::{http_client, TestDb};
+
+
+    async fn create(client: &Client, title: &str) -> Value {
+        let response = client.post("/v1/todo")
+            .header(ContentType::JSON)
+            .body(json!({ "title": title, "description": "test task", "is_done": false }).to_string())
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        response.into_json::<Value>().await.expect("a JSON envelope")["data"].take()
+    }
+
+    async fn rename(client: &Client, task_id: &Value, title: &str, if_match: &str) -> Status {
+        client.put("/v1/todo")
+            .header(ContentType::JSON)
+            .header(Header::new("If-Match", if_match.to_string()))
+            .body(json!({ "id": task_id, "title": title }).to_string())
+            .dispatch()
+            .await
+            .status()
+    }
+
+    async fn list(client: &Client, query: &str) -> (Status, Value) {
+        let response = client.get(format!("/v1/todo?{}", query)).dispatch().await;
+        let status = response.status();
+        (status, response.into_json().await.expect("a JSON envelope"))
+    }
+
+
+    #[rocket::async_test]
+    async fn a_date_only_created_to_includes_that_day() {
+        let db = TestDb::new("list_created_to");
+        let client = http_client(db.use_case()).await;
+        create(&client, "Pay invoice").await;
+
+        let today = Utc::now().date_naive();
+        let (status, page) = list(&client, &format!("created_to={}", today)).await;
+        assert_eq!(status, Status::Ok);
+        assert_eq!(page["data"]["total"], 1);
+
+        let (_, page) = list(&client, &format!("created_to={}", today - Duration::days(1))).await;
+        assert_eq!(page["data"]["total"], 0);
+    }
+
+    #[rocket::async_test]
+    async fn a_cursor_pages_through_its_own_sort_only() {
+        let db = TestDb::new("list_cursor_sort");
+        let client = http_client(db.use_case()).await;
+        for title in ["b", "c", "a"] {
+            create(&client, title).await;
+        }
+
+        let (_, first) = list(&client, "sort_by=title&sort_dir=asc&limit=1").await;
+        assert_eq!(first["data"]["items"][0]["title"], "a");
+        let cursor = first["data"]["next_cursor"].as_str().expect("a next page").to_string();
+
+        let (status, second) = list(&client, &format!("sort_by=title&sort_dir=asc&limit=1&cursor={}", cursor)).await;
+        assert_eq!(status, Status::Ok);
+        assert_eq!(second["data"]["items"][0]["title"], "b");
+
+        let (status, body) = list(&client, &format!("sort_by=id&sort_dir=asc&limit=1&cursor={}", cursor)).await;
+        assert_eq!(status, Status::UnprocessableEntity);
+        assert_eq!(body["error_code"], "VALIDATION_ERROR");
+    }
+
+    #[rocket::async_test]
+    async fn a_tampered_cursor_is_rejected_with_422() {
+        let db = TestDb::new("list_cursor_tampered");
+        let client = http_client(db.use_case()).await;
+        create(&client, "Pay invoice").await;
+
+        for cursor in ["o1", "created_at:desc:-1", "created_at:desc:99999999999999999999", "created_at:desc:1:2"] {
+            let (status, body) = list(&client, &format!("cursor={}", cursor)).await;
+            assert_eq!(status, Status::UnprocessableEntity, "cursor {:?}", cursor);
+            assert_eq!(body["error_code"], "VALIDATION_ERROR");
+        }
+    }
+
+    #[rocket::async_test]
+    async fn if_match_accepts_any_listed_version() {
+        let db = TestDb::new("update_if_match_list");
+        let client = http_client(db.use_case()).await;
+        let task = create(&client, "Pay invoice").await;
+        let version = task["version"].as_i64().expect("a version");
+
+        assert_eq!(rename(&client, &task["id"], "Pay rent", &format!(r#""{}", "{}""#, version + 5, version)).await, Status::Ok);
+        assert_eq!(rename(&client, &task["id"], "Pay taxes", &format!(r#"W/"{}", "{}""#, version, version + 5)).await, Status::Conflict);
+        assert_eq!(rename(&client, &task["id"], "Pay taxes", &format!(r#""not-a-version", "{}""#, version + 1)).await, Status::Ok);
+        assert_eq!(rename(&client, &task["id"], "Pay taxes", r#""not-a-version""#).await, Status::Conflict);
+        assert_eq!(rename(&client, &task["id"], "Pay taxes", &format!(r#""{}", "#, version + 2)).await, Status::BadRequest);
     }
 }
\ No newline at end of file
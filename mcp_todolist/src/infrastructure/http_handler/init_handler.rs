@@ -1,12 +1,25 @@
 use rocket::fairing::AdHoc;
 
-use super::http_handler::todolist_routes;
+use super::{audit_handler::audit_routes, batch_handler::batch_routes, catcher::json_catchers, event_handler::event_routes, http_handler::todolist_routes, list_handler::list_routes, recurrence_handler::recurrence_routes, search_handler::search_routes, subtask_handler::subtask_routes, tag_handler::tag_routes, transfer_handler::transfer_routes, trash_handler::trash_routes, undo_handler::undo_routes, webhook_handler::webhook_routes};
 
 
 
 pub fn init_controller_setup() -> AdHoc {
     AdHoc::on_ignite("Initial Controller", |rocket| async {
         rocket
+            .register("/", json_catchers())
             .mount("/v1", todolist_routes())
+            .mount("/v1", tag_routes())
+            .mount("/v1", list_routes())
+            .mount("/v1", subtask_routes())
+            .mount("/v1", event_routes())
+            .mount("/v1", batch_routes())
+            .mount("/v1", trash_routes())
+            .mount("/v1", audit_routes())
+            .mount("/v1", undo_routes())
+            .mount("/v1", search_routes())
+            .mount("/v1", recurrence_routes())
+            .mount("/v1", webhook_routes())
+            .mount("/v1", transfer_routes())
     })
 }
\ No newline at end of file
@@ -0,0 +1,291 @@
+use std::sync::Arc;
+use rocket::{delete, get, post, put, routes, serde::json::Json, Route, State};
+use crate::application::usecase::todo_usecase::TodolistUseCase;
+use crate::domain::dto::list_dto::{ReqCreateListDto, ReqUpdateListDto, ResEntryListDto, ResListCountDto};
+use crate::domain::dto::todo_dto::ResEntryTodoDto;
+use crate::infrastructure::http_handler::response_type::Response;
+
+use super::response_type::{ErrorResponse, ResponseBody, SuccessResponse};
+
+
+
+pub fn list_routes() -> Vec<Route> {
+    routes![
+        create_list,
+        get_all_lists,
+        get_list_by_id,
+        update_list,
+        delete_list,
+        get_tasks_in_list,
+        count_tasks_in_list
+    ]
+}
+
+
+fn parse_list_id(list_id: &str) -> Result<i32, ErrorResponse> {
+    list_id
+        .parse()
+        .map_err(|_| ErrorResponse::bad_request(format!("Invalid list id : {}", list_id)))
+}
+
+
+/// Create a new todo list.
+///
+/// # Request Body
+/// - `name`: List name, unique regardless of case (String)
+/// - `description`: (Optional) What the list is about
+///
+/// # Responses
+/// - `200 OK`: List created successfully, `data` holds the new `ResEntryListDto`
+/// - `409 Conflict`: A list with the same name already exists
+/// - `422 Unprocessable Entity`: Input validation failed
+/// - `500 Internal Server Error`: List creation failed due to a storage error
+#[utoipa::path(
+    post,
+    path = "/list",
+    request_body = ReqCreateListDto,
+    responses(
+        (status = 200, description = "List created successfully", body = ResponseBody<ResEntryListDto>),
+        (status = 409, description = "List name already exists", body = ResponseBody<String>),
+        (status = 422, description = "Input validation failed", body = ResponseBody<String>),
+        (status = 500, description = "Failed to create list. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[post("/list", data = "<list_data>")]
+pub async fn create_list(
+    list_data: Json<ReqCreateListDto>,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResEntryListDto> {
+    match state.create_list(list_data.into_inner()).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "List created successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Retrieve every todo list, sorted by name.
+///
+/// # Responses
+/// - `200 OK`: `data` holds the lists as `Vec<ResEntryListDto>`
+/// - `500 Internal Server Error`: Failed to retrieve lists due to a storage error
+#[utoipa::path(
+    get,
+    path = "/list",
+    responses(
+        (status = 200, description = "Lists retrieved successfully", body = ResponseBody<Vec<ResEntryListDto>>),
+        (status = 500, description = "Failed to retrieve lists. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/list")]
+pub async fn get_all_lists(
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<Vec<ResEntryListDto>> {
+    match state.get_all_lists().await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Lists retrieved successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Retrieve a todo list by ID.
+///
+/// # Path Parameters
+/// - `list_id`: ID of the list to retrieve (as a string, parsed to i32)
+///
+/// # Responses
+/// - `200 OK`: `data` holds the `ResEntryListDto`
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No list exists with the given ID
+/// - `500 Internal Server Error`: Failed to retrieve the list due to a storage error
+#[utoipa::path(
+    get,
+    path = "/list/{list_id}",
+    params(
+        ("list_id" = i32, Path, description = "Unique identifier of the list")
+    ),
+    responses(
+        (status = 200, description = "List retrieved successfully", body = ResponseBody<ResEntryListDto>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "List not found", body = ResponseBody<String>),
+        (status = 500, description = "Failed to retrieve list. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/list/<list_id>")]
+pub async fn get_list_by_id(
+    list_id: String,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResEntryListDto> {
+    let id = parse_list_id(&list_id)?;
+    match state.get_list_by_id(id).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "List retrieved successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Rename a todo list or change its description.
+///
+/// # Request Body
+/// - `id`: ID of the list to update (i32)
+/// - `name`: (Optional) New name
+/// - `description`: (Optional) New description, `null` clears it
+///
+/// # Responses
+/// - `200 OK`: List updated successfully, `data` holds the updated `ResEntryListDto`
+/// - `404 Not Found`: No list exists with the given ID
+/// - `409 Conflict`: Another list already uses the new name
+/// - `422 Unprocessable Entity`: Input validation failed
+/// - `500 Internal Server Error`: Update failed due to a storage error
+#[utoipa::path(
+    put,
+    path = "/list",
+    request_body = ReqUpdateListDto,
+    responses(
+        (status = 200, description = "List updated successfully", body = ResponseBody<ResEntryListDto>),
+        (status = 404, description = "List not found", body = ResponseBody<String>),
+        (status = 409, description = "List name already exists", body = ResponseBody<String>),
+        (status = 422, description = "Input validation failed", body = ResponseBody<String>),
+        (status = 500, description = "Failed to update list. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[put("/list", data = "<list_data>")]
+pub async fn update_list(
+    list_data: Json<ReqUpdateListDto>,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResEntryListDto> {
+    let dto = list_data.into_inner();
+    match state.update_list(dto.id, dto).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "List updated successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Delete a todo list by ID.
+///
+/// The tasks of the list are kept and no longer belong to any list.
+///
+/// # Path Parameters
+/// - `list_id`: ID of the list to delete (as a string, parsed to i32)
+///
+/// # Responses
+/// - `200 OK`: List deleted successfully, `data` holds the deleted list ID
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No list exists with the given ID
+/// - `500 Internal Server Error`: Deletion failed due to a storage error
+#[utoipa::path(
+    delete,
+    path = "/list/{list_id}",
+    params(
+        ("list_id" = i32, Path, description = "Unique identifier of the list to delete")
+    ),
+    responses(
+        (status = 200, description = "List deleted successfully", body = ResponseBody<i32>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "List not found", body = ResponseBody<String>),
+        (status = 500, description = "Failed to delete list. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[delete("/list/<list_id>")]
+pub async fn delete_list(
+    list_id: String,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<i32> {
+    let id = parse_list_id(&list_id)?;
+    match state.delete_list(id).await {
+        Ok(_) => Ok(SuccessResponse::ok(id, format!("List id : {} has deleted", id))),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Retrieve every task of a todo list.
+///
+/// Use `GET /todo?list_id=` to page, filter or sort inside a list.
+///
+/// # Path Parameters
+/// - `list_id`: ID of the list (as a string, parsed to i32)
+///
+/// # Responses
+/// - `200 OK`: `data` holds the tasks as `Vec<ResEntryTodoDto>`
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No list exists with the given ID
+/// - `500 Internal Server Error`: Failed to retrieve tasks due to a storage error
+#[utoipa::path(
+    get,
+    path = "/list/{list_id}/todo",
+    params(
+        ("list_id" = i32, Path, description = "Unique identifier of the list")
+    ),
+    responses(
+        (status = 200, description = "Tasks of the list retrieved successfully", body = ResponseBody<Vec<ResEntryTodoDto>>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "List not found", body = ResponseBody<String>),
+        (status = 500, description = "Failed to retrieve tasks. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/list/<list_id>/todo")]
+pub async fn get_tasks_in_list(
+    list_id: String,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<Vec<ResEntryTodoDto>> {
+    let id = parse_list_id(&list_id)?;
+    match state.get_all_in_list(id).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Tasks of the list retrieved successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Count the tasks of a todo list.
+///
+/// # Path Parameters
+/// - `list_id`: ID of the list (as a string, parsed to i32)
+///
+/// # Responses
+/// - `200 OK`: `data` holds a `ResListCountDto` with the total, done and undone counts
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No list exists with the given ID
+/// - `500 Internal Server Error`: Failed to count tasks due to a storage error
+#[utoipa::path(
+    get,
+    path = "/list/{list_id}/count",
+    params(
+        ("list_id" = i32, Path, description = "Unique identifier of the list")
+    ),
+    responses(
+        (status = 200, description = "Task counts of the list", body = ResponseBody<ResListCountDto>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "List not found", body = ResponseBody<String>),
+        (status = 500, description = "Failed to count tasks. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/list/<list_id>/count")]
+pub async fn count_tasks_in_list(
+    list_id: String,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResListCountDto> {
+    let id = parse_list_id(&list_id)?;
+    match state.count_tasks_in_list(id).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Task counts of the list")),
+        Err(e) => Err(e.into())
+    }
+}
\ No newline at end of file
@@ -1,3 +1,17 @@
 pub mod http_handler;
+pub mod tag_handler;
+pub mod list_handler;
+pub mod subtask_handler;
+pub mod event_handler;
+pub mod batch_handler;
+pub mod trash_handler;
+pub mod audit_handler;
+pub mod undo_handler;
+pub mod search_handler;
+pub mod recurrence_handler;
+pub mod webhook_handler;
+pub mod transfer_handler;
+pub mod catcher;
+pub mod request_origin;
 pub mod response_type;
 pub mod init_handler;
\ No newline at end of file
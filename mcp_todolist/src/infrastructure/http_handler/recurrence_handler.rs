@@ -0,0 +1,149 @@
+use std::sync::Arc;
+use rocket::{get, post, put, routes, serde::json::Json, Route, State};
+use crate::application::usecase::todo_usecase::TodolistUseCase;
+use crate::domain::dto::audit_dto::ChangeOrigin;
+use crate::domain::dto::recurrence_dto::{ReqCreateRecurringTaskDto, ResRecurrenceDto};
+use crate::infrastructure::http_handler::response_type::Response;
+
+use super::response_type::{ErrorResponse, ResponseBody, SuccessResponse};
+
+
+
+pub fn recurrence_routes() -> Vec<Route> {
+    routes![
+        create_recurrence,
+        get_recurrence,
+        stop_recurrence
+    ]
+}
+
+
+fn parse_recurrence_id(recurrence_id: &str) -> Result<i32, ErrorResponse> {
+    recurrence_id
+        .parse()
+        .map_err(|_| ErrorResponse::bad_request(format!("Invalid recurrence id : {}", recurrence_id)))
+}
+
+
+/// Create a recurring task.
+///
+/// The task is the first occurrence of the series. Completing the latest occurrence of a series
+/// (through `PUT /v1/todo` or a batch) creates the next one with the same fields and tags,
+/// due on the schedule counted from the first due date (or from now when the task has none).
+/// Both are stored together, the completion fails when the next occurrence cannot be created.
+///
+/// # Request Body
+/// - `task`: The first occurrence, same fields as `POST /v1/todo`
+/// - `recurrence.frequency`: `daily`, `weekly`, `monthly` or `yearly`
+/// - `recurrence.interval`: (Optional) Repeat every `interval` frequency units, 1 by default
+/// - `recurrence.until`: (Optional) No occurrence is due after this RFC 3339 date
+/// - `recurrence.count`: (Optional) Number of occurrences, the first task included
+///
+/// # Responses
+/// - `200 OK`: Series created successfully, `data` holds the `ResRecurrenceDto`
+/// - `404 Not Found`: The list of the task does not exist
+/// - `422 Unprocessable Entity`: Input validation failed
+/// - `500 Internal Server Error`: Creation failed due to a storage error
+#[utoipa::path(
+    post,
+    path = "/recurrence",
+    request_body = ReqCreateRecurringTaskDto,
+    responses(
+        (status = 200, description = "Recurring task created successfully", body = ResponseBody<ResRecurrenceDto>),
+        (status = 404, description = "List not found", body = ResponseBody<String>),
+        (status = 422, description = "Input validation failed", body = ResponseBody<String>),
+        (status = 500, description = "Failed to create the recurring task. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[post("/recurrence", data = "<recurrence_data>")]
+pub async fn create_recurrence(
+    recurrence_data: Json<ReqCreateRecurringTaskDto>,
+    origin: ChangeOrigin,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResRecurrenceDto> {
+    match state.create_recurring_task(recurrence_data.into_inner(), &origin).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Recurring task created successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Retrieve a series of recurring tasks.
+///
+/// # Path Parameters
+/// - `recurrence_id`: ID of the series, the `recurrence_id` of its tasks (as a string, parsed to i32)
+///
+/// # Responses
+/// - `200 OK`: `data` holds the rule, the occurrences outside the trash and when the next one is due
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No series exists with the given ID
+/// - `500 Internal Server Error`: Failed to retrieve the series due to a storage error
+#[utoipa::path(
+    get,
+    path = "/recurrence/{recurrence_id}",
+    params(
+        ("recurrence_id" = i32, Path, description = "Unique identifier of the series")
+    ),
+    responses(
+        (status = 200, description = "Recurrence retrieved successfully", body = ResponseBody<ResRecurrenceDto>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "Recurrence not found", body = ResponseBody<String>),
+        (status = 500, description = "Failed to retrieve the recurrence. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/recurrence/<recurrence_id>")]
+pub async fn get_recurrence(
+    recurrence_id: String,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResRecurrenceDto> {
+    let id = parse_recurrence_id(&recurrence_id)?;
+    match state.get_recurrence(id).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Recurrence retrieved successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Stop a series of recurring tasks.
+///
+/// Completing its tasks no longer creates new ones, the existing tasks are kept as they are.
+/// Stopping a stopped series changes nothing.
+///
+/// # Path Parameters
+/// - `recurrence_id`: ID of the series (as a string, parsed to i32)
+///
+/// # Responses
+/// - `200 OK`: Series stopped, `data` holds the `ResRecurrenceDto`
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No series exists with the given ID
+/// - `500 Internal Server Error`: Failed to stop the series due to a storage error
+#[utoipa::path(
+    put,
+    path = "/recurrence/{recurrence_id}/stop",
+    params(
+        ("recurrence_id" = i32, Path, description = "Unique identifier of the series")
+    ),
+    responses(
+        (status = 200, description = "Recurrence stopped successfully", body = ResponseBody<ResRecurrenceDto>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "Recurrence not found", body = ResponseBody<String>),
+        (status = 500, description = "Failed to stop the recurrence. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[put("/recurrence/<recurrence_id>/stop")]
+pub async fn stop_recurrence(
+    recurrence_id: String,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResRecurrenceDto> {
+    let id = parse_recurrence_id(&recurrence_id)?;
+    match state.stop_recurrence(id).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Recurrence stopped successfully")),
+        Err(e) => Err(e.into())
+    }
+}
\ No newline at end of file
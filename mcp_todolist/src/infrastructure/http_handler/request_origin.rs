@@ -0,0 +1,21 @@
+use rocket::{request::{FromRequest, Outcome}, Request};
+use crate::domain::dto::audit_dto::ChangeOrigin;
+
+
+
+/// Requests name themselves with `X-Client-Name`, falling back to their `User-Agent`.
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ChangeOrigin {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let headers = request.headers();
+        let client_name = headers
+            .get_one("X-Client-Name")
+            .or_else(|| headers.get_one("User-Agent"))
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string);
+        Outcome::Success(ChangeOrigin::http(client_name, format!("http-{:016x}", rand::random::<u64>())))
+    }
+}
\ No newline at end of file
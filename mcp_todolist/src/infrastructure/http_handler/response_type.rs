@@ -1,27 +1,129 @@
 use std::fmt;
 
-use rocket::{http::Status, Responder};
+use rocket::{http::{ContentType, Header, Status}, serde::json::Json, Responder};
+use serde::Serialize;
+use utoipa::ToSchema;
 
+use crate::domain::error::domain_error::DomainError;
+
+
+/// JSON envelope shared by every route.
+///
+/// On success `data` holds the payload and `error_code` is `null`;
+/// on failure `data` is `null` and `error_code` holds a stable, machine readable code.
+#[derive(Serialize, Debug, Clone, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ResponseBody<T> {
+    pub data: Option<T>,
+    pub error_code: Option<String>,
+    pub message: String,
+}
 
 #[derive(Responder)]
-pub struct SuccessResponse<T>(pub (Status, T));
+pub struct SuccessResponse<T>(pub (Status, Json<ResponseBody<T>>));
 
 #[derive(Responder, Debug)]
-pub struct ErrorResponse (pub (Status, String));
+pub struct ErrorResponse (pub (Status, Json<ResponseBody<()>>));
+
+/// Success response carrying the `ETag` of the task it returns.
+#[derive(Responder)]
+pub struct TaggedResponse<T> {
+    inner: SuccessResponse<T>,
+    etag: Header<'static>,
+}
+
+/// A file to download, the body as is with a `Content-Disposition` naming it.
+#[derive(Responder)]
+pub struct FileResponse {
+    inner: (ContentType, String),
+    disposition: Header<'static>,
+}
 
 
 
 
 pub type Response<T> = Result<SuccessResponse<T>, ErrorResponse>;
 
-// impl From<DbErr> for ErrorResponse {
-//     fn from(err: DbErr) -> Self {
-//         ErrorResponse((Status::InternalServerError, format!("Database error: {:?}", err)))
-//     }
-// }
+pub type TaggedResult<T> = Result<TaggedResponse<T>, ErrorResponse>;
+
+pub type FileResult = Result<FileResponse, ErrorResponse>;
+
+
+impl<T> SuccessResponse<T> {
+    pub fn new(status: Status, data: T, message: impl Into<String>) -> Self {
+        SuccessResponse((status, Json(ResponseBody {
+            data: Some(data),
+            error_code: None,
+            message: message.into(),
+        })))
+    }
+
+    pub fn ok(data: T, message: impl Into<String>) -> Self {
+        Self::new(Status::Ok, data, message)
+    }
+}
+
+
+impl<T> TaggedResponse<T> {
+    /// The task version is the entity tag, so it can be sent back as `If-Match`.
+    pub fn new(inner: SuccessResponse<T>, version: i32) -> Self {
+        TaggedResponse {
+            inner,
+            etag: Header::new("ETag", format!("\"{}\"", version)),
+        }
+    }
+}
+
+
+impl FileResponse {
+    pub fn new(media_type: &str, body: String, file_name: &str) -> Self {
+        FileResponse {
+            inner: (ContentType::parse_flexible(media_type).unwrap_or(ContentType::Binary), body),
+            disposition: Header::new("Content-Disposition", format!("attachment; filename=\"{}\"", file_name)),
+        }
+    }
+}
+
+
+impl ErrorResponse {
+    pub fn new(status: Status, error_code: impl Into<String>, message: impl Into<String>) -> Self {
+        ErrorResponse((status, Json(ResponseBody {
+            data: None,
+            error_code: Some(error_code.into()),
+            message: message.into(),
+        })))
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(Status::BadRequest, "BAD_REQUEST", message)
+    }
+}
+
+/// Maps domain errors onto HTTP statuses:
+/// `NotFound` -> 404, `Validation` -> 422, `Conflict` -> 409, `Storage` -> 500.
+impl From<DomainError> for ErrorResponse {
+    fn from(err: DomainError) -> Self {
+        match err {
+            DomainError::NotFound(message) => Self::new(Status::NotFound, "NOT_FOUND", message),
+            DomainError::Validation(message) => Self::new(Status::UnprocessableEntity, "VALIDATION_ERROR", message),
+            DomainError::Conflict(message) => Self::new(Status::Conflict, "CONFLICT", message),
+            DomainError::Storage(message) => {
+                tracing::error!("Storage error: {}", message);
+                Self::new(Status::InternalServerError, "STORAGE_ERROR", "Internal storage error, please try again")
+            }
+        }
+    }
+}
 
 impl fmt::Display for ErrorResponse {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "ErrorResponse: status = {}, message = {}", (self.0).0, (self.0).1)
+        let body = &(self.0).1;
+        write!(
+            f,
+            "ErrorResponse: status = {}, code = {}, message = {}",
+            (self.0).0,
+            body.error_code.as_deref().unwrap_or("-"),
+            body.message
+        )
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+use rocket::{get, routes, Route, State};
+use crate::application::usecase::todo_usecase::TodolistUseCase;
+use crate::domain::dto::search_dto::{ReqSearchTodoDto, ResSearchHitDto};
+use crate::infrastructure::http_handler::response_type::Response;
+
+use super::response_type::{ResponseBody, SuccessResponse};
+
+
+
+pub fn search_routes() -> Vec<Route> {
+    routes![
+        search_todo
+    ]
+}
+
+
+/// Full-text search over task titles and descriptions.
+///
+/// Every word of the query has to appear in the title or the description, inside words too,
+/// words shorter than 3 characters are ignored. Tasks in the trash are left out.
+/// Matches come best first, a title match ranks above a description match.
+///
+/// # Query Parameters
+/// - `q`: Words to look for
+/// - `limit`: (Optional) How many matches to return, 10 by default, at most 50
+///
+/// # Responses
+/// - `200 OK`: `data` holds the matches as `Vec<ResSearchHitDto>`, snippets wrap the matched text in `**`
+/// - `422 Unprocessable Entity`: The query is blank, has no word long enough or the limit is out of range
+/// - `500 Internal Server Error`: Search failed due to a storage error
+#[utoipa::path(
+    get,
+    path = "/todo/search",
+    params(
+        ("q" = String, Query, description = "Words to look for in titles and descriptions"),
+        ("limit" = Option<u32>, Query, description = "How many matches to return, 10 by default, at most 50")
+    ),
+    responses(
+        (status = 200, description = "Search completed successfully", body = ResponseBody<Vec<ResSearchHitDto>>),
+        (status = 422, description = "Input validation failed", body = ResponseBody<String>),
+        (status = 500, description = "Failed to search. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/todo/search?<q>&<limit>")]
+pub async fn search_todo(
+    q: String,
+    limit: Option<u32>,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<Vec<ResSearchHitDto>> {
+    match state.search_tasks(ReqSearchTodoDto { query: q, limit }).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Search completed successfully")),
+        Err(e) => Err(e.into())
+    }
+}
\ No newline at end of file
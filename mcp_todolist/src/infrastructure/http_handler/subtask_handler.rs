@@ -0,0 +1,176 @@
+use std::sync::Arc;
+use rocket::{get, post, put, routes, serde::json::Json, Route, State};
+use crate::domain::dto::audit_dto::ChangeOrigin;
+use crate::application::usecase::todo_usecase::TodolistUseCase;
+use crate::domain::dto::subtask_dto::{ReqCreateSubtaskDto, ReqReorderSubtasksDto, ReqTickSubtaskDto};
+use crate::domain::dto::todo_dto::ResEntryTodoDto;
+use crate::infrastructure::http_handler::response_type::Response;
+
+use super::http_handler::parse_todo_id;
+use super::response_type::{ResponseBody, SuccessResponse};
+
+
+
+pub fn subtask_routes() -> Vec<Route> {
+    routes![
+        add_subtask,
+        get_subtasks,
+        reorder_subtasks,
+        tick_subtask
+    ]
+}
+
+
+/// Add a subtask (checklist step) at the end of a task.
+///
+/// The subtask inherits the list and the priority of its parent.
+/// Subtasks cannot have subtasks of their own.
+///
+/// # Request Body
+/// - `parent_id`: ID of the task the step belongs to (i32)
+/// - `title`: Title of the step (String)
+/// - `description`: (Optional) Details of the step
+/// - `is_done`: (Optional) Whether the step is already done, `false` by default
+///
+/// # Responses
+/// - `200 OK`: Subtask created successfully, `data` holds the new subtask
+/// - `404 Not Found`: No task exists with the given parent ID
+/// - `422 Unprocessable Entity`: Input validation failed or the parent is itself a subtask
+/// - `500 Internal Server Error`: Creation failed due to a storage error
+#[utoipa::path(
+    post,
+    path = "/todo/subtask",
+    request_body = ReqCreateSubtaskDto,
+    responses(
+        (status = 200, description = "Subtask created successfully", body = ResponseBody<ResEntryTodoDto>),
+        (status = 404, description = "Parent task not found", body = ResponseBody<String>),
+        (status = 422, description = "Input validation failed", body = ResponseBody<String>),
+        (status = 500, description = "Failed to create subtask. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[post("/todo/subtask", data = "<subtask_data>")]
+pub async fn add_subtask(
+    subtask_data: Json<ReqCreateSubtaskDto>,
+    origin: ChangeOrigin,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResEntryTodoDto> {
+    match state.add_subtask(subtask_data.into_inner(), &origin).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Subtask created successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Retrieve the subtasks of a task, in checklist order.
+///
+/// # Path Parameters
+/// - `todo_id`: ID of the parent task (as a string, parsed to i32)
+///
+/// # Responses
+/// - `200 OK`: `data` holds the subtasks as `Vec<ResEntryTodoDto>`
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No task exists with the given ID
+/// - `500 Internal Server Error`: Failed to retrieve subtasks due to a storage error
+#[utoipa::path(
+    get,
+    path = "/todo/{todo_id}/subtask",
+    params(
+        ("todo_id" = i32, Path, description = "Unique identifier of the parent task")
+    ),
+    responses(
+        (status = 200, description = "Subtasks retrieved successfully", body = ResponseBody<Vec<ResEntryTodoDto>>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "Task not found", body = ResponseBody<String>),
+        (status = 500, description = "Failed to retrieve subtasks. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/todo/<todo_id>/subtask")]
+pub async fn get_subtasks(
+    todo_id: String,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<Vec<ResEntryTodoDto>> {
+    let id = parse_todo_id(&todo_id)?;
+    match state.get_subtasks(id).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Subtasks retrieved successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Reorder the subtasks of a task.
+///
+/// # Request Body
+/// - `parent_id`: ID of the parent task (i32)
+/// - `subtask_ids`: Every subtask ID of the parent exactly once, in the new order
+///
+/// # Responses
+/// - `200 OK`: `data` holds the subtasks in their new order
+/// - `404 Not Found`: No task exists with the given parent ID
+/// - `422 Unprocessable Entity`: `subtask_ids` does not match the subtasks of the parent
+/// - `500 Internal Server Error`: Reordering failed due to a storage error
+#[utoipa::path(
+    put,
+    path = "/todo/subtask/order",
+    request_body = ReqReorderSubtasksDto,
+    responses(
+        (status = 200, description = "Subtasks reordered successfully", body = ResponseBody<Vec<ResEntryTodoDto>>),
+        (status = 404, description = "Parent task not found", body = ResponseBody<String>),
+        (status = 422, description = "Input validation failed", body = ResponseBody<String>),
+        (status = 500, description = "Failed to reorder subtasks. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[put("/todo/subtask/order", data = "<order_data>")]
+pub async fn reorder_subtasks(
+    order_data: Json<ReqReorderSubtasksDto>,
+    origin: ChangeOrigin,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<Vec<ResEntryTodoDto>> {
+    match state.reorder_subtasks(order_data.into_inner(), &origin).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Subtasks reordered successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Tick a subtask off, or reopen it.
+///
+/// # Request Body
+/// - `subtask_id`: ID of the subtask (i32)
+/// - `is_done`: (Optional) `true` (default) ticks it off, `false` reopens it
+///
+/// # Responses
+/// - `200 OK`: `data` holds the parent task with its updated `subtasks_done` / `subtasks_total`
+/// - `404 Not Found`: No task exists with the given ID
+/// - `422 Unprocessable Entity`: The task is not a subtask
+/// - `500 Internal Server Error`: Update failed due to a storage error
+#[utoipa::path(
+    put,
+    path = "/todo/subtask/done",
+    request_body = ReqTickSubtaskDto,
+    responses(
+        (status = 200, description = "Subtask updated successfully", body = ResponseBody<ResEntryTodoDto>),
+        (status = 404, description = "Subtask not found", body = ResponseBody<String>),
+        (status = 422, description = "The task is not a subtask", body = ResponseBody<String>),
+        (status = 500, description = "Failed to update subtask. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[put("/todo/subtask/done", data = "<tick_data>")]
+pub async fn tick_subtask(
+    tick_data: Json<ReqTickSubtaskDto>,
+    origin: ChangeOrigin,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResEntryTodoDto> {
+    match state.tick_subtask(tick_data.into_inner(), &origin).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Subtask updated successfully")),
+        Err(e) => Err(e.into())
+    }
+}
\ No newline at end of file
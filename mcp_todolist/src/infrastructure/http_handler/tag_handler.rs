@@ -0,0 +1,267 @@
+use std::sync::Arc;
+use rocket::{delete, get, post, put, routes, serde::json::Json, Route, State};
+use crate::domain::dto::audit_dto::ChangeOrigin;
+use crate::application::usecase::todo_usecase::TodolistUseCase;
+use crate::domain::dto::tag_dto::{ReqCreateTagDto, ReqTagNamesDto, ReqTaskTagsDto, ReqUpdateTagDto, ResEntryTagDto};
+use crate::domain::dto::todo_dto::ResEntryTodoDto;
+use crate::infrastructure::http_handler::response_type::Response;
+
+use super::http_handler::parse_todo_id;
+use super::response_type::{ErrorResponse, ResponseBody, SuccessResponse};
+
+
+
+pub fn tag_routes() -> Vec<Route> {
+    routes![
+        create_tag,
+        get_all_tags,
+        update_tag,
+        delete_tag,
+        attach_tags,
+        detach_tag
+    ]
+}
+
+
+fn parse_tag_id(tag_id: &str) -> Result<i32, ErrorResponse> {
+    tag_id
+        .parse()
+        .map_err(|_| ErrorResponse::bad_request(format!("Invalid tag id : {}", tag_id)))
+}
+
+
+/// Create a new tag.
+///
+/// # Request Body
+/// - `name`: Tag name, unique regardless of case (String)
+/// - `color`: (Optional) Free-form color, e.g. `#ff8800`
+///
+/// # Responses
+/// - `200 OK`: Tag created successfully, `data` holds the new `ResEntryTagDto`
+/// - `409 Conflict`: A tag with the same name already exists
+/// - `422 Unprocessable Entity`: Input validation failed
+/// - `500 Internal Server Error`: Tag creation failed due to a storage error
+#[utoipa::path(
+    post,
+    path = "/tag",
+    request_body = ReqCreateTagDto,
+    responses(
+        (status = 200, description = "Tag created successfully", body = ResponseBody<ResEntryTagDto>),
+        (status = 409, description = "Tag name already exists", body = ResponseBody<String>),
+        (status = 422, description = "Input validation failed", body = ResponseBody<String>),
+        (status = 500, description = "Failed to create tag. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[post("/tag", data = "<tag_data>")]
+pub async fn create_tag(
+    tag_data: Json<ReqCreateTagDto>,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResEntryTagDto> {
+    match state.create_tag(tag_data.into_inner()).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Tag created successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Retrieve every tag, sorted by name.
+///
+/// # Responses
+/// - `200 OK`: `data` holds the tags as `Vec<ResEntryTagDto>`
+/// - `500 Internal Server Error`: Failed to retrieve tags due to a storage error
+#[utoipa::path(
+    get,
+    path = "/tag",
+    responses(
+        (status = 200, description = "Tags retrieved successfully", body = ResponseBody<Vec<ResEntryTagDto>>),
+        (status = 500, description = "Failed to retrieve tags. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/tag")]
+pub async fn get_all_tags(
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<Vec<ResEntryTagDto>> {
+    match state.get_all_tags().await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Tags retrieved successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Rename or recolor a tag.
+///
+/// # Request Body
+/// - `id`: ID of the tag to update (i32)
+/// - `name`: (Optional) New name
+/// - `color`: (Optional) New color, `null` clears it
+///
+/// # Responses
+/// - `200 OK`: Tag updated successfully, `data` holds the updated `ResEntryTagDto`
+/// - `404 Not Found`: No tag exists with the given ID
+/// - `409 Conflict`: Another tag already uses the new name
+/// - `422 Unprocessable Entity`: Input validation failed
+/// - `500 Internal Server Error`: Update failed due to a storage error
+#[utoipa::path(
+    put,
+    path = "/tag",
+    request_body = ReqUpdateTagDto,
+    responses(
+        (status = 200, description = "Tag updated successfully", body = ResponseBody<ResEntryTagDto>),
+        (status = 404, description = "Tag not found", body = ResponseBody<String>),
+        (status = 409, description = "Tag name already exists", body = ResponseBody<String>),
+        (status = 422, description = "Input validation failed", body = ResponseBody<String>),
+        (status = 500, description = "Failed to update tag. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[put("/tag", data = "<tag_data>")]
+pub async fn update_tag(
+    tag_data: Json<ReqUpdateTagDto>,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResEntryTagDto> {
+    let dto = tag_data.into_inner();
+    match state.update_tag(dto.id, dto).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Tag updated successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Delete a tag by ID.
+///
+/// The tag is removed from every task carrying it, the tasks themselves are kept.
+///
+/// # Path Parameters
+/// - `tag_id`: ID of the tag to delete (as a string, parsed to i32)
+///
+/// # Responses
+/// - `200 OK`: Tag deleted successfully, `data` holds the deleted tag ID
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No tag exists with the given ID
+/// - `500 Internal Server Error`: Deletion failed due to a storage error
+#[utoipa::path(
+    delete,
+    path = "/tag/{tag_id}",
+    params(
+        ("tag_id" = i32, Path, description = "Unique identifier of the tag to delete")
+    ),
+    responses(
+        (status = 200, description = "Tag deleted successfully", body = ResponseBody<i32>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "Tag not found", body = ResponseBody<String>),
+        (status = 500, description = "Failed to delete tag. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[delete("/tag/<tag_id>")]
+pub async fn delete_tag(
+    tag_id: String,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<i32> {
+    let id = parse_tag_id(&tag_id)?;
+    match state.delete_tag(id).await {
+        Ok(_) => Ok(SuccessResponse::ok(id, format!("Tag id : {} has deleted", id))),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Attach tags to a task.
+///
+/// Tags are matched by name regardless of case; names that do not exist yet are created.
+/// Tags the task already carries are left untouched.
+///
+/// # Request Body
+/// - `tags`: Tag names to attach (Vec<String>)
+///
+/// # Responses
+/// - `200 OK`: `data` holds the task with its updated tags
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No task exists with the given ID
+/// - `422 Unprocessable Entity`: No tag given or a tag name is invalid
+/// - `500 Internal Server Error`: Failed due to a storage error
+#[utoipa::path(
+    post,
+    path = "/todo/{todo_id}/tags",
+    params(
+        ("todo_id" = i32, Path, description = "Unique identifier of the todo task")
+    ),
+    request_body = ReqTagNamesDto,
+    responses(
+        (status = 200, description = "Tags attached successfully", body = ResponseBody<ResEntryTodoDto>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "Task not found", body = ResponseBody<String>),
+        (status = 422, description = "Input validation failed", body = ResponseBody<String>),
+        (status = 500, description = "Failed to attach tags. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[post("/todo/<todo_id>/tags", data = "<tag_data>")]
+pub async fn attach_tags(
+    todo_id: String,
+    tag_data: Json<ReqTagNamesDto>,
+    origin: ChangeOrigin,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResEntryTodoDto> {
+    let id = parse_todo_id(&todo_id)?;
+    let dto = ReqTaskTagsDto { task_id: id, tags: tag_data.into_inner().tags };
+    match state.attach_tags(dto, &origin).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Tags attached successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Detach a tag from a task.
+///
+/// Detaching a tag the task does not carry is not an error.
+///
+/// # Path Parameters
+/// - `todo_id`: ID of the todo task (as a string, parsed to i32)
+/// - `tag_name`: Name of the tag to detach
+///
+/// # Responses
+/// - `200 OK`: `data` holds the task with its remaining tags
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No task exists with the given ID
+/// - `500 Internal Server Error`: Failed due to a storage error
+#[utoipa::path(
+    delete,
+    path = "/todo/{todo_id}/tags/{tag_name}",
+    params(
+        ("todo_id" = i32, Path, description = "Unique identifier of the todo task"),
+        ("tag_name" = String, Path, description = "Name of the tag to detach")
+    ),
+    responses(
+        (status = 200, description = "Tag detached successfully", body = ResponseBody<ResEntryTodoDto>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "Task not found", body = ResponseBody<String>),
+        (status = 500, description = "Failed to detach tag. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[delete("/todo/<todo_id>/tags/<tag_name>")]
+pub async fn detach_tag(
+    todo_id: String,
+    tag_name: String,
+    origin: ChangeOrigin,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResEntryTodoDto> {
+    let id = parse_todo_id(&todo_id)?;
+    let dto = ReqTaskTagsDto { task_id: id, tags: vec![tag_name] };
+    match state.detach_tags(dto, &origin).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Tag detached successfully")),
+        Err(e) => Err(e.into())
+    }
+}
\ No newline at end of file
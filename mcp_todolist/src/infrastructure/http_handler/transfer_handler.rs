@@ -0,0 +1,179 @@
+use std::sync::Arc;
+use rocket::{data::{Data, ToByteUnit}, get, post, routes, Route, State};
+use crate::application::usecase::todo_usecase::TodolistUseCase;
+use crate::domain::dto::audit_dto::ChangeOrigin;
+use crate::domain::dto::transfer_dto::{ReqImportTasksDto, ResImportReportDto, TransferFormat};
+use crate::domain::error::domain_error::DomainError;
+use crate::infrastructure::http_handler::response_type::Response;
+
+use super::response_type::{ErrorResponse, FileResponse, FileResult, ResponseBody, SuccessResponse};
+
+
+
+/// Largest file `POST /import` reads.
+const MAX_IMPORT_MIB: u32 = 5;
+
+
+pub fn transfer_routes() -> Vec<Route> {
+    routes![
+        export_tasks,
+        import_tasks,
+        calendar_feed
+    ]
+}
+
+
+/// Download every live task as a file.
+///
+/// `json` writes every field along with the lists, the tags and their colour, the recurring series
+/// and the trash, and reads back what was written, tasks getting new ids.
+/// `csv`, `markdown` and `ics` only hold the live tasks.
+/// `csv` writes one row per task with the tags joined by `;`, a `;` or `\` inside a name escaped with `\`.
+/// `markdown` writes a `- [ ]` / `- [x]` checklist with subtasks indented under their task and
+/// one `##` section per list, dropping descriptions, priorities, dates and tags.
+/// `ics` writes an iCalendar file with one VTODO per task.
+///
+/// # Query Parameters
+/// - `format`: (Optional) `json`, `csv`, `markdown` or `ics`, `json` by default
+///
+/// # Responses
+/// - `200 OK`: The file, named `todolist.<extension>` in `Content-Disposition`
+/// - `500 Internal Server Error`: Export failed due to a storage error
+#[utoipa::path(
+    get,
+    path = "/export",
+    params(
+        ("format" = Option<TransferFormat>, Query, description = "File format, json by default")
+    ),
+    responses(
+        (status = 200, description = "Tasks exported successfully", content(
+            (String = "application/json"),
+            (String = "text/csv"),
+            (String = "text/markdown"),
+            (String = "text/calendar")
+        )),
+        (status = 500, description = "Failed to export tasks. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/export?<format>")]
+pub async fn export_tasks(
+    format: Option<TransferFormat>,
+    state: &State<Arc<TodolistUseCase>>
+) -> FileResult {
+    let format = format.unwrap_or_default();
+    match state.export_tasks(format).await {
+        Ok(body) => Ok(FileResponse::new(format.media_type(), body, &format!("todolist.{}", format.file_extension()))),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Create tasks from a file, as written by `GET /v1/export` or by hand.
+///
+/// A task matching a stored task (same title ignoring case, same due date and same parent, live or
+/// trashed alike) or an earlier task of the file is reported as a duplicate and skipped. Trashed tasks
+/// of a JSON export go back to the trash. Lists are matched
+/// by name and created when missing. When any record is invalid nothing is imported.
+///
+/// # Query Parameters
+/// - `format`: (Optional) `json`, `csv`, `markdown` or `ics`, `json` by default
+/// - `dry_run`: (Optional) `true` to only report what would be imported
+///
+/// # Request Body
+/// The file content, 5 MiB at most. A JSON file may also be a bare array of tasks,
+/// a CSV file needs a header row with at least a `title` column and an iCalendar file
+/// is read for its VTODO components, other components being ignored. Its local times need
+/// a `TZID` or an `X-WR-TIMEZONE` for the calendar, both an IANA zone name such as `Europe/Paris`.
+/// Records are checked like tasks created by hand: every task but a subtask needs a description,
+/// checklist items and VTODOs without one take their title.
+///
+/// # Responses
+/// - `200 OK`: `data` holds the `ResImportReportDto`, one entry per task of the file
+/// - `400 Bad Request`: The body could not be read as UTF-8 text
+/// - `422 Unprocessable Entity`: The file is too large, malformed or holds an invalid record
+/// - `500 Internal Server Error`: Import failed due to a storage error
+#[utoipa::path(
+    post,
+    path = "/import",
+    params(
+        ("format" = Option<TransferFormat>, Query, description = "File format, json by default"),
+        ("dry_run" = Option<bool>, Query, description = "Only report what would be imported")
+    ),
+    request_body(description = "The file content", content(
+        ("application/json"),
+        ("text/csv"),
+        ("text/markdown"),
+        ("text/calendar")
+    )),
+    responses(
+        (status = 200, description = "Tasks imported successfully", body = ResponseBody<ResImportReportDto>),
+        (status = 400, description = "Unreadable body", body = ResponseBody<String>),
+        (status = 422, description = "Input validation failed", body = ResponseBody<String>),
+        (status = 500, description = "Failed to import tasks. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[post("/import?<format>&<dry_run>", data = "<file>")]
+pub async fn import_tasks(
+    format: Option<TransferFormat>,
+    dry_run: Option<bool>,
+    file: Data<'_>,
+    origin: ChangeOrigin,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResImportReportDto> {
+    let content = file
+        .open(MAX_IMPORT_MIB.mebibytes())
+        .into_string()
+        .await
+        .map_err(|e| ErrorResponse::bad_request(format!("Failed to read the file : {}", e)))?;
+    if !content.is_complete() {
+        return Err(DomainError::validation(format!("the file is larger than {} MiB", MAX_IMPORT_MIB)).into());
+    }
+
+    let dto = ReqImportTasksDto {
+        format: format.unwrap_or_default(),
+        content: content.into_inner(),
+        dry_run: dry_run.unwrap_or_default(),
+    };
+    let message = if dto.dry_run { "Import checked, nothing was stored" } else { "Tasks imported successfully" };
+    match state.import_tasks(dto, &origin).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, message)),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Subscribe a calendar app to the tasks with a due date.
+///
+/// Every live task with a due date is a VTODO: `STATUS:COMPLETED` once done, `DUE`, `PRIORITY`
+/// (1 urgent, 3 high, 5 normal, 9 low), the tags as `CATEGORIES`, the reminder as a `VALARM`
+/// and subtasks `RELATED-TO` their task. Read only, changes made in the calendar app are not synced back.
+///
+/// # Responses
+/// - `200 OK`: The iCalendar file
+/// - `500 Internal Server Error`: Failed to build the feed due to a storage error
+#[utoipa::path(
+    get,
+    path = "/calendar.ics",
+    responses(
+        (status = 200, description = "Calendar retrieved successfully", content(
+            (String = "text/calendar")
+        )),
+        (status = 500, description = "Failed to build the calendar. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/calendar.ics")]
+pub async fn calendar_feed(
+    state: &State<Arc<TodolistUseCase>>
+) -> FileResult {
+    let format = TransferFormat::Ics;
+    match state.export_calendar().await {
+        Ok(body) => Ok(FileResponse::new(format.media_type(), body, &format!("todolist.{}", format.file_extension()))),
+        Err(e) => Err(e.into())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+use rocket::{delete, get, put, routes, Route, State};
+use crate::domain::dto::audit_dto::ChangeOrigin;
+use crate::application::usecase::todo_usecase::TodolistUseCase;
+use crate::domain::dto::todo_dto::ResEntryTodoDto;
+use crate::domain::dto::trash_dto::ResPurgeDto;
+use crate::infrastructure::http_handler::response_type::Response;
+
+use super::http_handler::parse_todo_id;
+use super::response_type::{ResponseBody, SuccessResponse};
+
+
+
+pub fn trash_routes() -> Vec<Route> {
+    routes![
+        get_trash,
+        restore_todo,
+        purge_todo,
+        empty_trash
+    ]
+}
+
+
+/// Retrieve the tasks in the trash, most recently deleted first.
+///
+/// Subtasks deleted together with their parent are listed as well, `deleted_at` tells when.
+///
+/// # Responses
+/// - `200 OK`: `data` holds the trashed tasks as `Vec<ResEntryTodoDto>`
+/// - `500 Internal Server Error`: Failed to retrieve the trash due to a storage error
+#[utoipa::path(
+    get,
+    path = "/todo/trash",
+    responses(
+        (status = 200, description = "Trash retrieved successfully", body = ResponseBody<Vec<ResEntryTodoDto>>),
+        (status = 500, description = "Failed to retrieve the trash. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/todo/trash")]
+pub async fn get_trash(
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<Vec<ResEntryTodoDto>> {
+    match state.list_trash().await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Trash retrieved successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Restore a task from the trash.
+///
+/// The subtasks deleted together with the task come back too.
+/// A subtask can only be restored on its own while its parent is not in the trash.
+///
+/// # Path Parameters
+/// - `todo_id`: ID of the trashed task (as a string, parsed to i32)
+///
+/// # Responses
+/// - `200 OK`: `data` holds the restored task
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No task with the given ID is in the trash
+/// - `422 Unprocessable Entity`: The task is a subtask whose parent is in the trash
+/// - `500 Internal Server Error`: Restoring failed due to a storage error
+#[utoipa::path(
+    put,
+    path = "/todo/trash/{todo_id}/restore",
+    params(
+        ("todo_id" = i32, Path, description = "Unique identifier of the trashed task")
+    ),
+    responses(
+        (status = 200, description = "Task restored successfully", body = ResponseBody<ResEntryTodoDto>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "Task not found in the trash", body = ResponseBody<String>),
+        (status = 422, description = "The parent task is in the trash", body = ResponseBody<String>),
+        (status = 500, description = "Failed to restore task. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[put("/todo/trash/<todo_id>/restore")]
+pub async fn restore_todo(
+    todo_id: String,
+    origin: ChangeOrigin,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResEntryTodoDto> {
+    let id = parse_todo_id(&todo_id)?;
+    match state.restore_task(id, &origin).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Task restored successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Permanently delete a task from the trash, together with its subtasks.
+///
+/// # Path Parameters
+/// - `todo_id`: ID of the trashed task (as a string, parsed to i32)
+///
+/// # Responses
+/// - `200 OK`: `data` holds the IDs removed for good
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No task with the given ID is in the trash
+/// - `500 Internal Server Error`: Purging failed due to a storage error
+#[utoipa::path(
+    delete,
+    path = "/todo/trash/{todo_id}",
+    params(
+        ("todo_id" = i32, Path, description = "Unique identifier of the trashed task")
+    ),
+    responses(
+        (status = 200, description = "Task purged successfully", body = ResponseBody<ResPurgeDto>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "Task not found in the trash", body = ResponseBody<String>),
+        (status = 500, description = "Failed to purge task. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[delete("/todo/trash/<todo_id>")]
+pub async fn purge_todo(
+    todo_id: String,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResPurgeDto> {
+    let id = parse_todo_id(&todo_id)?;
+    match state.purge_task(id).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Task purged successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Permanently delete every task in the trash.
+///
+/// # Responses
+/// - `200 OK`: `data` holds the IDs removed for good
+/// - `500 Internal Server Error`: Emptying the trash failed due to a storage error
+#[utoipa::path(
+    delete,
+    path = "/todo/trash",
+    responses(
+        (status = 200, description = "Trash emptied successfully", body = ResponseBody<ResPurgeDto>),
+        (status = 500, description = "Failed to empty the trash. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[delete("/todo/trash")]
+pub async fn empty_trash(
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResPurgeDto> {
+    match state.empty_trash().await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Trash emptied successfully")),
+        Err(e) => Err(e.into())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+use rocket::{post, routes, serde::json::Json, Route, State};
+use crate::application::usecase::todo_usecase::TodolistUseCase;
+use crate::domain::dto::audit_dto::ChangeOrigin;
+use crate::domain::dto::undo_dto::{ReqUndoDto, ResUndoDto};
+use crate::infrastructure::http_handler::response_type::Response;
+
+use super::response_type::{ResponseBody, SuccessResponse};
+
+
+
+pub fn undo_routes() -> Vec<Route> {
+    routes![
+        undo_session
+    ]
+}
+
+
+/// Undo the last operations made by an MCP session.
+///
+/// An operation is one MCP tool call, every task it created, updated or deleted is reverted:
+/// created tasks go to the trash, deleted tasks come back from it and updated tasks get their
+/// previous fields and tags back. Everything is reverted in one transaction, or nothing is.
+/// The session id is shown in the `origin` of the task history entries.
+///
+/// # Path Parameters
+/// - `session_id`: ID of the MCP session to undo
+///
+/// # Request Body
+/// - `operations`: (Optional) How many of the last operations to revert, 1 by default, at most 50
+///
+/// # Responses
+/// - `200 OK`: `data` holds the reverted changes
+/// - `409 Conflict`: Another client changed one of the tasks since, nothing was reverted
+/// - `422 Unprocessable Entity`: Input validation failed or the session has nothing left to undo
+/// - `500 Internal Server Error`: Undo failed due to a storage error
+#[utoipa::path(
+    post,
+    path = "/session/{session_id}/undo",
+    params(
+        ("session_id" = String, Path, description = "Identifier of the MCP session")
+    ),
+    request_body = ReqUndoDto,
+    responses(
+        (status = 200, description = "Operations undone successfully", body = ResponseBody<ResUndoDto>),
+        (status = 409, description = "A task was changed by another client since", body = ResponseBody<String>),
+        (status = 422, description = "Input validation failed", body = ResponseBody<String>),
+        (status = 500, description = "Failed to undo. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[post("/session/<session_id>/undo", data = "<undo_data>")]
+pub async fn undo_session(
+    session_id: String,
+    undo_data: Json<ReqUndoDto>,
+    origin: ChangeOrigin,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResUndoDto> {
+    match state.undo_session(&session_id, undo_data.into_inner(), &origin).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Operations undone successfully")),
+        Err(e) => Err(e.into())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,262 @@
+use std::sync::Arc;
+use rocket::{delete, get, post, put, routes, serde::json::Json, Route, State};
+use crate::application::usecase::todo_usecase::TodolistUseCase;
+use crate::domain::dto::webhook_dto::{ReqCreateWebhookDto, ReqUpdateWebhookDto, ReqWebhookDeliveryLogDto, ResWebhookDeliveryDto, ResWebhookDto};
+use crate::infrastructure::http_handler::response_type::Response;
+
+use super::response_type::{ErrorResponse, ResponseBody, SuccessResponse};
+
+
+
+pub fn webhook_routes() -> Vec<Route> {
+    routes![
+        create_webhook,
+        get_all_webhooks,
+        get_webhook_by_id,
+        update_webhook,
+        delete_webhook,
+        get_webhook_deliveries
+    ]
+}
+
+
+fn parse_webhook_id(webhook_id: &str) -> Result<i32, ErrorResponse> {
+    webhook_id
+        .parse()
+        .map_err(|_| ErrorResponse::bad_request(format!("Invalid webhook id : {}", webhook_id)))
+}
+
+
+/// Register a webhook receiving task lifecycle events.
+///
+/// Every task created, updated, completed, deleted or restored afterwards is POSTed to the URL
+/// as a `ResWebhookPayloadDto`, with the `X-Todolist-Event` and `X-Todolist-Delivery` headers and
+/// `X-Todolist-Signature-256: sha256=<hex HMAC-SHA256 of the body keyed with the secret>`.
+/// A delivery not answered with a 2xx status is retried with an increasing delay, 6 attempts at most.
+/// Redirects are not followed, and a delivery fails when the host only resolves to private addresses.
+///
+/// # Request Body
+/// - `url`: http(s) URL the events are POSTed to (String), loopback, link-local and private addresses
+///   are refused unless the server runs with `WEBHOOK_ALLOW_PRIVATE_TARGETS=true`
+/// - `events`: (Optional) Events to send among `created`, `updated`, `completed`, `deleted` and `restored`, every event when empty
+/// - `secret`: (Optional) Signing key of 16 to 256 characters, a random one is generated when omitted
+/// - `active`: (Optional) `false` to register the webhook without sending anything yet, `true` by default
+///
+/// # Responses
+/// - `200 OK`: Webhook created successfully, `data` holds the new `ResWebhookDto`, the only response including the secret
+/// - `422 Unprocessable Entity`: Input validation failed, or the URL targets a private address
+/// - `500 Internal Server Error`: Webhook creation failed due to a storage error
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    request_body = ReqCreateWebhookDto,
+    responses(
+        (status = 200, description = "Webhook created successfully", body = ResponseBody<ResWebhookDto>),
+        (status = 422, description = "Input validation failed", body = ResponseBody<String>),
+        (status = 500, description = "Failed to create webhook. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[post("/webhooks", data = "<webhook_data>")]
+pub async fn create_webhook(
+    webhook_data: Json<ReqCreateWebhookDto>,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResWebhookDto> {
+    match state.create_webhook(webhook_data.into_inner()).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Webhook created successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Retrieve every registered webhook, secrets left out.
+///
+/// # Responses
+/// - `200 OK`: `data` holds the webhooks as `Vec<ResWebhookDto>`
+/// - `500 Internal Server Error`: Failed to retrieve webhooks due to a storage error
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    responses(
+        (status = 200, description = "Webhooks retrieved successfully", body = ResponseBody<Vec<ResWebhookDto>>),
+        (status = 500, description = "Failed to retrieve webhooks. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/webhooks")]
+pub async fn get_all_webhooks(
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<Vec<ResWebhookDto>> {
+    match state.get_all_webhooks().await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Webhooks retrieved successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Retrieve a webhook by ID, its secret left out.
+///
+/// # Path Parameters
+/// - `webhook_id`: ID of the webhook to retrieve (as a string, parsed to i32)
+///
+/// # Responses
+/// - `200 OK`: `data` holds the `ResWebhookDto`
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No webhook exists with the given ID
+/// - `500 Internal Server Error`: Failed to retrieve the webhook due to a storage error
+#[utoipa::path(
+    get,
+    path = "/webhooks/{webhook_id}",
+    params(
+        ("webhook_id" = i32, Path, description = "Unique identifier of the webhook")
+    ),
+    responses(
+        (status = 200, description = "Webhook retrieved successfully", body = ResponseBody<ResWebhookDto>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "Webhook not found", body = ResponseBody<String>),
+        (status = 500, description = "Failed to retrieve webhook. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/webhooks/<webhook_id>")]
+pub async fn get_webhook_by_id(
+    webhook_id: String,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResWebhookDto> {
+    let id = parse_webhook_id(&webhook_id)?;
+    match state.get_webhook(id).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Webhook retrieved successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Change the URL, events, secret or state of a webhook.
+///
+/// Deliveries already queued keep the payload they were queued with and go to the current URL.
+///
+/// # Request Body
+/// - `id`: ID of the webhook to update (i32)
+/// - `url`: (Optional) New http(s) URL, private addresses are refused as on creation
+/// - `events`: (Optional) New event filter, an empty list sends every event
+/// - `secret`: (Optional) New signing key of 16 to 256 characters
+/// - `active`: (Optional) `false` stops queuing new deliveries, the log is kept
+///
+/// # Responses
+/// - `200 OK`: Webhook updated successfully, `data` holds the updated `ResWebhookDto`
+/// - `404 Not Found`: No webhook exists with the given ID
+/// - `422 Unprocessable Entity`: Input validation failed
+/// - `500 Internal Server Error`: Update failed due to a storage error
+#[utoipa::path(
+    put,
+    path = "/webhooks",
+    request_body = ReqUpdateWebhookDto,
+    responses(
+        (status = 200, description = "Webhook updated successfully", body = ResponseBody<ResWebhookDto>),
+        (status = 404, description = "Webhook not found", body = ResponseBody<String>),
+        (status = 422, description = "Input validation failed", body = ResponseBody<String>),
+        (status = 500, description = "Failed to update webhook. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[put("/webhooks", data = "<webhook_data>")]
+pub async fn update_webhook(
+    webhook_data: Json<ReqUpdateWebhookDto>,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<ResWebhookDto> {
+    let dto = webhook_data.into_inner();
+    match state.update_webhook(dto.id, dto).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Webhook updated successfully")),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Delete a webhook by ID, along with its pending deliveries and delivery log.
+///
+/// # Path Parameters
+/// - `webhook_id`: ID of the webhook to delete (as a string, parsed to i32)
+///
+/// # Responses
+/// - `200 OK`: Webhook deleted successfully, `data` holds the deleted webhook ID
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No webhook exists with the given ID
+/// - `500 Internal Server Error`: Deletion failed due to a storage error
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{webhook_id}",
+    params(
+        ("webhook_id" = i32, Path, description = "Unique identifier of the webhook to delete")
+    ),
+    responses(
+        (status = 200, description = "Webhook deleted successfully", body = ResponseBody<i32>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "Webhook not found", body = ResponseBody<String>),
+        (status = 500, description = "Failed to delete webhook. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[delete("/webhooks/<webhook_id>")]
+pub async fn delete_webhook(
+    webhook_id: String,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<i32> {
+    let id = parse_webhook_id(&webhook_id)?;
+    match state.delete_webhook(id).await {
+        Ok(_) => Ok(SuccessResponse::ok(id, format!("Webhook id : {} has deleted", id))),
+        Err(e) => Err(e.into())
+    }
+}
+
+
+
+
+
+/// Retrieve the delivery log of a webhook, newest first.
+///
+/// # Path Parameters
+/// - `webhook_id`: ID of the webhook (as a string, parsed to i32)
+///
+/// # Query Parameters
+/// - `limit`: (Optional) How many deliveries to return, 20 by default, at most 200
+///
+/// # Responses
+/// - `200 OK`: `data` holds the deliveries as `Vec<ResWebhookDeliveryDto>`
+/// - `400 Bad Request`: The ID is not a valid integer
+/// - `404 Not Found`: No webhook exists with the given ID
+/// - `422 Unprocessable Entity`: The limit is out of range
+/// - `500 Internal Server Error`: Failed to retrieve deliveries due to a storage error
+#[utoipa::path(
+    get,
+    path = "/webhooks/{webhook_id}/deliveries",
+    params(
+        ("webhook_id" = i32, Path, description = "Unique identifier of the webhook"),
+        ("limit" = Option<u32>, Query, description = "How many deliveries to return, 20 by default, at most 200")
+    ),
+    responses(
+        (status = 200, description = "Deliveries retrieved successfully", body = ResponseBody<Vec<ResWebhookDeliveryDto>>),
+        (status = 400, description = "Invalid ID format", body = ResponseBody<String>),
+        (status = 404, description = "Webhook not found", body = ResponseBody<String>),
+        (status = 422, description = "Input validation failed", body = ResponseBody<String>),
+        (status = 500, description = "Failed to retrieve deliveries. Internal storage error", body = ResponseBody<String>)
+    )
+)]
+#[get("/webhooks/<webhook_id>/deliveries?<limit>")]
+pub async fn get_webhook_deliveries(
+    webhook_id: String,
+    limit: Option<u32>,
+    state: &State<Arc<TodolistUseCase>>
+) -> Response<Vec<ResWebhookDeliveryDto>> {
+    let id = parse_webhook_id(&webhook_id)?;
+    match state.get_webhook_deliveries(ReqWebhookDeliveryLogDto { webhook_id: id, limit }).await {
+        Ok(data) => Ok(SuccessResponse::ok(data, "Deliveries retrieved successfully")),
+        Err(e) => Err(e.into())
+    }
+}
\ No newline at end of file
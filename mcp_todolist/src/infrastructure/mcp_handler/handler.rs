@@ -1,14 +1,22 @@
 
 
-use std::sync::Arc;
-use rmcp::{const_string, model::{AnnotateAble, CallToolResult, ConstString, Content, GetPromptRequestParam, GetPromptResult, Implementation, ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult, PaginatedRequestParam, Prompt, PromptArgument, PromptMessage, PromptMessageContent, PromptMessageRole, ProtocolVersion, RawResource, ReadResourceRequestParam, ReadResourceResult, Resource, ResourceContents, ServerCapabilities, ServerInfo}, service::RequestContext, tool, Error as McpError, RoleServer, ServerHandler};
-use serde_json::json;
-use crate::{application::usecase::todo_usecase::TodolistUseCase, domain::dto::todo_dto::{ReqCreateTodoDto, GetTaskById, ReqUpdateTodoDto}};
+use std::sync::{atomic::{AtomicU64, Ordering}, Arc};
+use rmcp::{const_string, model::{CallToolResult, ConstString, Content, GetPromptRequestParam, GetPromptResult, Implementation, ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult, PaginatedRequestParam, ProtocolVersion, ReadResourceRequestParam, ReadResourceResult, ServerCapabilities, ServerInfo, SetLevelRequestParam, SubscribeRequestParam, UnsubscribeRequestParam}, service::RequestContext, tool, Error as McpError, Peer, RoleServer, ServerHandler};
+use super::{prompts, reminders::{spawn_reminder_forwarder, SessionLogLevel}, resources, subscriptions::{spawn_change_forwarder, ResourceSubscriptions}};
+use crate::{application::usecase::todo_usecase::TodolistUseCase, domain::dto::{audit_dto::ChangeOrigin, transfer_dto::{ReqExportTasksDto, ReqImportTasksDto}, undo_dto::ReqUndoDto, search_dto::ReqSearchTodoDto, recurrence_dto::{GetRecurrenceById, ReqCreateRecurringTaskDto}, batch_dto::{ReqCreateTasksDto, ReqTaskIdsDto}, query_dto::TodoQuery, list_dto::{GetListById, ReqCreateListDto, ReqUpdateListDto}, subtask_dto::{ReqCreateSubtaskDto, ReqReorderSubtasksDto, ReqTickSubtaskDto}, tag_dto::{GetTagById, ReqCreateTagDto, ReqTaskTagsDto, ReqUpdateTagDto}, todo_dto::{DueToday, DueWithinDays, ReqCreateTodoDto, GetTaskById, ReqUpdateTodoDto}}};
 
 #[allow(dead_code)]
 #[derive(Clone)]
 pub struct MCPHandler {
-    todo_use_case: Arc<TodolistUseCase>
+    todo_use_case: Arc<TodolistUseCase>,
+    subscriptions: ResourceSubscriptions,
+    /// lowest level of the reminder notifications this session receives
+    log_level: SessionLogLevel,
+    peer: Option<Peer<RoleServer>>,
+    /// identifies this connection in the audit log
+    session_id: String,
+    /// tool calls made so far, numbers the operations of the session
+    operations: Arc<AtomicU64>
 }
 
 
@@ -16,11 +24,14 @@ pub struct MCPHandler {
 impl MCPHandler {
     
     pub fn new(use_case: Arc<TodolistUseCase>) -> Self {
-        Self { todo_use_case: use_case }
-    }
-
-    fn _create_resource_text(&self, uri: &str, name: &str) -> Resource {
-        RawResource::new(uri, name.to_string()).no_annotation()
+        Self {
+            todo_use_case: use_case,
+            subscriptions: ResourceSubscriptions::default(),
+            log_level: SessionLogLevel::default(),
+            peer: None,
+            session_id: format!("{:016x}", rand::random::<u64>()),
+            operations: Arc::new(AtomicU64::new(0))
+        }
     }
 
     
@@ -34,7 +45,10 @@ impl MCPHandler {
     {
       "title": "Buy groceries",
       "description": "Milk, eggs, and bread",
-      "is_done": false
+      "is_done": false,
+      "priority": "high",
+      "due_date": "2025-05-10T18:00:00Z",
+      "tags": ["home", "shopping"]
     }
     
     🧾 รายละเอียดฟิลด์ / Field Descriptions:
@@ -44,6 +58,16 @@ impl MCPHandler {
       Description of the task (required, must not be empty)
     - is_done (boolean): สถานะของงานว่าเสร็จแล้วหรือไม่  
       Whether the task is completed
+    - priority (string, optional): ความสำคัญ "low", "normal" (ค่าเริ่มต้น), "high", "urgent"  
+      Priority of the task, defaults to "normal"
+    - due_date (string, optional): กำหนดส่ง รูปแบบ RFC 3339  
+      Due date in RFC 3339 format
+    - remind_at (string, optional): เวลาที่ต้องการให้เตือน รูปแบบ RFC 3339  
+      When to send a reminder, RFC 3339 format
+    - list_id (integer, optional): รหัสรายการ (โปรเจกต์) ที่งานนี้อยู่ ดูได้จาก `get_all_lists`  
+      ID of the list (project) the task belongs to, see `get_all_lists`
+    - tags (array of string, optional): แท็กของงาน ถ้ายังไม่มีจะสร้างให้อัตโนมัติ  
+      Tag names to attach, unknown tags are created automatically
     
     📤 ผลลัพธ์ / Response:
     - ✅ หากสำเร็จ: ระบบจะส่งข้อมูลของงานที่ถูกสร้างกลับมา  
@@ -55,7 +79,7 @@ impl MCPHandler {
         &self,
         #[tool(aggr)] dto: ReqCreateTodoDto
     ) -> Result<CallToolResult, McpError> {
-        match self.todo_use_case.create_task(dto).await {
+        match self.todo_use_case.create_task(dto, &self.origin()).await {
             Ok(data) => {
                 if let Ok(convert) = Content::json(data) {
                     Ok(CallToolResult::success(vec![convert]))
@@ -63,7 +87,7 @@ impl MCPHandler {
                     Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
                 }
             },
-            Err(e) => Err(McpError::internal_error(e.to_string(), None))
+            Err(e) => Err(e.into())
         }
     }
 
@@ -108,7 +132,7 @@ impl MCPHandler {
                     Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
                 }
             },
-            Err(e) => Err(McpError::internal_error(e.to_string(), None))
+            Err(e) => Err(e.into())
         }
     }
 
@@ -141,6 +165,7 @@ impl MCPHandler {
         - ใช้สำหรับดึงข้อมูลของงานทั้งหมด / Used to retrieve all task entries
         - ข้อมูลที่ได้จะอยู่ในรูปแบบของรายการ (array) ที่ประกอบด้วย `ResEntryTodoDto`
         - สามารถนำไปใช้แสดงใน UI หรือการวิเคราะห์ต่อไปได้
+        - ⚠️ หากมีงานจำนวนมาก ให้ใช้ `list_tasks` แทน / When there are many tasks, prefer `list_tasks` which pages the results
     "#)]
     pub async fn get_all(
         &self
@@ -153,7 +178,60 @@ impl MCPHandler {
                     Err(McpError::internal_error("Failed to convert results to Json", None))
                 }
             },
-            Err(e) => Err(McpError::internal_error(e.to_string(), None))
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อค้นหาและแบ่งหน้ารายการงาน / Use this command to list tasks page by page with filters and sorting.
+
+        📥 JSON Payload ตัวอย่าง / Example Request (every field is optional):
+        {
+        "is_done": false,
+        "tags": ["work"],
+        "search": "invoice",
+        "created_from": "2025-05-01T00:00:00Z",
+        "created_to": "2025-05-31T23:59:59Z",
+        "sort_by": "updated_at",
+        "sort_dir": "desc",
+        "limit": 20
+        }
+
+        🧾 รายละเอียดฟิลด์ / Field Descriptions:
+        - is_done (boolean): กรองตามสถานะ / Only tasks with this completion status
+        - priority (string): "low", "normal", "high", "urgent"
+        - list_id (integer): เฉพาะงานในรายการนี้ / Only tasks of this list
+        - tags (array of string): เฉพาะงานที่มีทุกแท็กที่ระบุ / Only tasks carrying every listed tag
+        - search (string): ค้นหาในชื่อและรายละเอียด / Text searched in title and description
+        - created_from, created_to, updated_from, updated_to (RFC 3339 string): ช่วงเวลา / Date range filters
+        - sort_by (string): "created_at" (default), "updated_at", "title", "id", "priority", "due_date"
+        - sort_dir (string): "desc" (default) หรือ / or "asc"
+        - limit (integer): จำนวนต่อหน้า 1-200 (ค่าเริ่มต้น 50) / Page size 1-200 (default 50)
+        - offset (integer): ข้ามกี่รายการ / Number of tasks to skip
+        - cursor (string): ค่า `next_cursor` จากหน้าก่อน ใช้กับ sort_by/sort_dir เดิมเท่านั้น / The `next_cursor` returned by the previous call, only valid with the same sort_by and sort_dir
+
+        📤 JSON Response Example:
+        {
+        "items": [ { "id": 1, "title": "Pay invoice", "description": "...", "is_done": false, "tags": ["work"], "created_at": "...", "updated_at": "..." } ],
+        "total": 42,
+        "next_cursor": "created_at:desc:20"
+        }
+        - หาก `next_cursor` เป็น null แสดงว่าเป็นหน้าสุดท้าย / `next_cursor` is null on the last page
+    "#)]
+    pub async fn list_tasks(
+        &self,
+        #[tool(aggr)] query: TodoQuery
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.list_tasks(query).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
         }
     }
 
@@ -168,20 +246,22 @@ impl MCPHandler {
 
         🧾 รายละเอียด / Details:
         - ฟิลด์ `id` (integer): รหัสของงานที่ต้องการลบ / The ID of the task to be deleted
-        - หากพบงานที่มี `id` ดังกล่าว จะทำการลบทันที / If a task with the given ID is found, it will be deleted
+        - หากพบงานที่มี `id` ดังกล่าว งานจะถูกย้ายไปที่ถังขยะ / If a task with the given ID is found, it is moved to the trash
         - ไม่คืนข้อมูลเนื้อหา นอกจากข้อความแจ้งสถานะการลบ / Does not return data, only a status message
         - คำตอบจะอยู่ในรูปแบบ: `"Task delete successful!!!"`
 
         🛑 หมายเหตุ / Note:
         - หากไม่พบงานที่มี ID ดังกล่าว ระบบจะส่งข้อผิดพลาดกลับ / If no task is found with the given ID, an error will be returned
+        - งานย่อยทั้งหมดของงานนี้จะถูกย้ายไปที่ถังขยะด้วย / Every subtask of the task is moved to the trash as well
+        - กู้คืนได้ด้วย `restore_task` หรือลบถาวรด้วย `purge_task` / Bring it back with `restore_task`, remove it for good with `purge_task`
     "#)]
     pub async fn delete_task(
         &self,
         #[tool(aggr)] dto: GetTaskById
     ) -> Result<CallToolResult, McpError> {
-        match self.todo_use_case.delete_task(dto.id as i32).await {
+        match self.todo_use_case.delete_task(dto.id as i32, &self.origin()).await {
             Ok(_) => Ok(CallToolResult::success(vec![Content::text(format!("Task delete succesfull!!!"))])),
-            Err(e) => Err(McpError::internal_error(e.to_string(), None))
+            Err(e) => Err(e.into())
         }
     }
 
@@ -209,19 +289,31 @@ impl MCPHandler {
         New description for the task
         - is_done (boolean, optional): สถานะความสำเร็จของงาน  
         Updated completion status
+        - priority (string, optional): "low", "normal", "high", "urgent"  
+        New priority for the task
+        - due_date (string | null, optional): กำหนดส่งใหม่ (RFC 3339) ส่ง null เพื่อลบ  
+        New due date in RFC 3339 format, send null to clear it
+        - remind_at (string | null, optional): เวลาเตือนใหม่ (RFC 3339) ส่ง null เพื่อยกเลิก  
+        New reminder time in RFC 3339 format, send null to cancel it
+        - list_id (integer | null, optional): ย้ายงานไปยังรายการนี้ ส่ง null เพื่อนำออกจากรายการ  
+        Move the task to this list, send null to take it out of its list
+        - expected_version (integer, optional): เวอร์ชันของงานที่อ่านมาล่าสุด (ฟิลด์ version)  
+        The `version` of the task you last read, the update is refused when someone changed the task since
 
         📤 ผลลัพธ์ / Response:
         - ✅ หากสำเร็จ: `"Task update successful!!!"`  
         On success, returns `"Task update successful!!!"`
         - ❌ หากไม่พบงาน: ระบบจะส่งข้อความข้อผิดพลาดกลับ  
         If the task is not found, an error will be returned
+        - ⚠️ หากเวอร์ชันไม่ตรง: ได้รับข้อผิดพลาด conflict ให้อ่านงานใหม่แล้วลองอีกครั้ง  
+        If `expected_version` is stale a conflict error is returned, read the task again and retry
     "#)]
     pub async fn update_task(
         &self,
         #[tool(aggr)] dto: ReqUpdateTodoDto
     ) -> Result<CallToolResult, McpError>
     {
-        match self.todo_use_case.update_task(dto.id as i32, dto).await {
+        match self.todo_use_case.update_task(dto.id as i32, dto, &self.origin()).await {
             Ok(data) => {
                 if let Ok(convert) = Content::json(data) {
                     Ok(CallToolResult::success(vec![convert]))
@@ -229,7 +321,7 @@ impl MCPHandler {
                     Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
                 }
             },
-            Err(e) => Err(McpError::internal_error(e.to_string(), None))
+            Err(e) => Err(e.into())
         }
     }
 
@@ -249,7 +341,7 @@ impl MCPHandler {
     {
         match self.todo_use_case.count_all_task().await {
             Ok(data) => Ok(CallToolResult::success(vec![Content::text(format!("Task have: {} items", data))])),
-            Err(e) => Err(McpError::internal_error(e.to_string(), None))
+            Err(e) => Err(e.into())
         }
     }
 
@@ -268,7 +360,7 @@ impl MCPHandler {
     {
         match self.todo_use_case.count_done_task().await {
             Ok(data) => Ok(CallToolResult::success(vec![Content::text(format!("You have {} tasks, mark as done", data))])),
-            Err(e) => Err(McpError::internal_error(e.to_string(), None))
+            Err(e) => Err(e.into())
         }
     }
 
@@ -288,118 +380,1261 @@ impl MCPHandler {
     {
         match self.todo_use_case.count_undone_task().await {
             Ok(data) => Ok(CallToolResult::success(vec![Content::text(format!("You have {} tasks, mark as undone", data))])),
-            Err(e) => Err(McpError::internal_error(e.to_string(), None))
+            Err(e) => Err(e.into())
         }
     }
-}
 
 
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อดึงงานที่เลยกำหนดส่งแล้วแต่ยังไม่เสร็จ /  
+        Use this command to retrieve tasks that are past their due date and not done yet.
+
+        📤 ผลลัพธ์ / Response:
+        - รายการ `ResEntryTodoDto` เรียงตามกำหนดส่งที่เก่าที่สุดก่อน /  
+        A list of `ResEntryTodoDto`, oldest due date first
+    "#)]
+    pub async fn get_overdue_tasks(
+        &self
+    ) -> Result<CallToolResult, McpError>
+    {
+        match self.todo_use_case.get_overdue_tasks().await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
 
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อดึงงานที่ครบกำหนดวันนี้และยังไม่เสร็จ /  
+        Use this command to retrieve undone tasks due today.
 
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "utc_offset_minutes": 420
+        }
 
+        Field:
+        - utc_offset_minutes (integer, optional): เขตเวลาของผู้ใช้เป็นนาทีจาก UTC -840 ถึง 840 เช่น 420 สำหรับ UTC+7 ถ้าไม่ระบุจะใช้วันตามเวลา UTC /
+          The user's offset from UTC in minutes, -840 to 840, e.g. 420 for UTC+7; the UTC day is used when omitted
 
-const_string!(Echo = "echo");
-#[tool(tool_box)]
-impl ServerHandler for MCPHandler {
-    
-    fn get_info(&self) -> ServerInfo {
-        ServerInfo {
-            protocol_version: ProtocolVersion::V_2024_11_05,
-            capabilities: ServerCapabilities::builder()
-                .enable_prompts()
-                .enable_resources()
-                .enable_tools()
-                .build(),
-            server_info: Implementation::from_build_env(),
-            instructions: Some("Real personal financial analysis".to_string()),
+        📤 ผลลัพธ์ / Response:
+        - รายการ `ResEntryTodoDto` เรียงตามกำหนดส่ง / A list of `ResEntryTodoDto` ordered by due date
+    "#)]
+    pub async fn get_due_today_tasks(
+        &self,
+        #[tool(aggr)] dto: DueToday
+    ) -> Result<CallToolResult, McpError>
+    {
+        match self.todo_use_case.get_due_today_tasks(dto).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
         }
     }
 
-    async fn list_resources(
+
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อดึงงานที่ยังไม่เสร็จและครบกำหนดภายใน N วันข้างหน้า /  
+        Use this command to retrieve undone tasks due within the next N days.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "days": 7
+        }
+
+        Field:
+        - days (integer): จำนวนวันที่ต้องการดูล่วงหน้า 1-365 / Number of days to look ahead, 1-365
+
+        📤 ผลลัพธ์ / Response:
+        - รายการ `ResEntryTodoDto` เรียงตามกำหนดส่ง / A list of `ResEntryTodoDto` ordered by due date
+    "#)]
+    pub async fn get_tasks_due_within(
         &self,
-        _request: Option<PaginatedRequestParam>,
-        _: RequestContext<RoleServer>,
-    ) -> Result<ListResourcesResult, McpError> {
-        Ok(ListResourcesResult {
-            resources: vec![
-                self._create_resource_text("str:////Users/to/some/path/", "cwd"),
-                self._create_resource_text("memo://insights", "memo-name"),
-            ],
-            next_cursor: None,
-        })
+        #[tool(aggr)] dto: DueWithinDays
+    ) -> Result<CallToolResult, McpError>
+    {
+        match self.todo_use_case.get_due_within_tasks(dto).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
     }
 
-    async fn read_resource(
+
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อสร้างแท็กใหม่ / Use this command to create a new tag.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "name": "work",
+        "color": "blue"
+        }
+
+        🧾 รายละเอียดฟิลด์ / Field Descriptions:
+        - name (string): ชื่อแท็ก 1-50 ตัวอักษร ห้ามซ้ำ (ไม่สนตัวพิมพ์เล็กใหญ่) / Tag name, 1-50 characters, unique regardless of case
+        - color (string, optional): สีของแท็ก / Free-form color
+
+        📤 ผลลัพธ์ / Response:
+        - ✅ แท็กที่สร้างใหม่ / The newly created tag
+        - ❌ หากชื่อซ้ำจะได้ข้อผิดพลาด / An error is returned when the name is already used
+    "#)]
+    pub async fn create_tag(
         &self,
-        ReadResourceRequestParam { uri }: ReadResourceRequestParam,
-        _: RequestContext<RoleServer>,
-    ) -> Result<ReadResourceResult, McpError> {
-        match uri.as_str() {
-            "str:////Users/to/some/path/" => {
-                let cwd = "/Users/to/some/path/";
-                Ok(ReadResourceResult {
-                    contents: vec![ResourceContents::text(cwd, uri)],
-                })
-            }
-            "memo://insights" => {
-                let memo = "Business Intelligence Memo\n\nAnalysis has revealed 5 key insights ...";
-                Ok(ReadResourceResult {
-                    contents: vec![ResourceContents::text(memo, uri)],
-                })
-            }
-            _ => Err(McpError::resource_not_found(
-                "resource_not_found",
-                Some(json!({
-                    "uri": uri
-                })),
-            )),
+        #[tool(aggr)] dto: ReqCreateTagDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.create_tag(dto).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
         }
     }
 
-    async fn list_prompts(
+
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อดึงแท็กทั้งหมด เรียงตามชื่อ / Use this command to retrieve every tag, sorted by name.
+
+        📤 JSON Response Example:
+        [
+        { "id": 1, "name": "home", "color": null },
+        { "id": 2, "name": "work", "color": "blue" }
+        ]
+    "#)]
+    pub async fn get_all_tags(
+        &self
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.get_all_tags().await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อเปลี่ยนชื่อหรือสีของแท็ก / Use this command to rename or recolor a tag.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "id": 2,
+        "name": "office",
+        "color": null
+        }
+
+        🧾 รายละเอียดฟิลด์ / Field Descriptions:
+        - id (integer): รหัสแท็ก / ID of the tag (required)
+        - name (string, optional): ชื่อใหม่ / New name
+        - color (string, optional): สีใหม่ ส่ง null เพื่อล้างค่า / New color, null clears it
+    "#)]
+    pub async fn update_tag(
         &self,
-        _request: Option<PaginatedRequestParam>,
-        _: RequestContext<RoleServer>,
-    ) -> Result<ListPromptsResult, McpError> {
-        Ok(ListPromptsResult {
-            next_cursor: None,
-            prompts: vec![Prompt::new(
-                "example_prompt",
-                Some("This is an example prompt that takes one required argument, message"),
-                Some(vec![PromptArgument {
-                    name: "message".to_string(),
-                    description: Some("A message to put in the prompt".to_string()),
-                    required: Some(true),
-                }]),
-            )],
-        })
+        #[tool(aggr)] dto: ReqUpdateTagDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.update_tag(dto.id, dto).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
     }
 
-    async fn get_prompt(
+
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อลบแท็ก งานที่ติดแท็กนี้จะไม่ถูกลบ / Use this command to delete a tag, tasks carrying it are kept.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "id": 2
+        }
+    "#)]
+    pub async fn delete_tag(
         &self,
-        GetPromptRequestParam { name, arguments }: GetPromptRequestParam,
-        _: RequestContext<RoleServer>,
-    ) -> Result<GetPromptResult, McpError> {
-        match name.as_str() {
-            "example_prompt" => {
-                let message = arguments
-                    .and_then(|json| json.get("message")?.as_str().map(|s| s.to_string()))
-                    .ok_or_else(|| {
-                        McpError::invalid_params("No message provided to example_prompt", None)
-                    })?;
-
-                let prompt =
-                    format!("This is an example prompt with your message here: '{message}'");
-                Ok(GetPromptResult {
-                    description: None,
-                    messages: vec![PromptMessage {
-                        role: PromptMessageRole::User,
-                        content: PromptMessageContent::text(prompt),
-                    }],
-                })
-            }
-            _ => Err(McpError::invalid_params("prompt not found", None)),
+        #[tool(aggr)] dto: GetTagById
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.delete_tag(dto.id as i32).await {
+            Ok(_) => Ok(CallToolResult::success(vec![Content::text(format!("Tag id {} deleted", dto.id))])),
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อติดแท็กให้งาน ถ้าแท็กยังไม่มีจะสร้างให้อัตโนมัติ /  
+        Use this command to attach tags to a task, unknown tags are created automatically.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "task_id": 1,
+        "tags": ["work", "urgent"]
         }
+
+        📤 ผลลัพธ์ / Response:
+        - งานพร้อมแท็กทั้งหมดหลังอัปเดต / The task with its full, updated list of tags
+    "#)]
+    pub async fn tag_task(
+        &self,
+        #[tool(aggr)] dto: ReqTaskTagsDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.attach_tags(dto, &self.origin()).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อเอาแท็กออกจากงาน / Use this command to detach tags from a task.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "task_id": 1,
+        "tags": ["urgent"]
+        }
+
+        📤 ผลลัพธ์ / Response:
+        - งานพร้อมแท็กที่เหลืออยู่ / The task with its remaining tags
+    "#)]
+    pub async fn untag_task(
+        &self,
+        #[tool(aggr)] dto: ReqTaskTagsDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.detach_tags(dto, &self.origin()).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        📁 ใช้คำสั่งนี้เพื่อสร้างรายการงาน (โปรเจกต์) ใหม่ เช่น "Groceries" หรือ "Release 1.2" /  
+        📁 Use this command to create a new todo list (project), e.g. "Groceries" or "Release 1.2".
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "name": "Groceries",
+        "description": "Things to buy this week"
+        }
+
+        🧾 รายละเอียดฟิลด์ / Field Descriptions:
+        - name (string): ชื่อรายการ 1-100 ตัวอักษร ห้ามซ้ำ / List name, 1-100 characters, unique regardless of case
+        - description (string, optional): คำอธิบาย / What the list is about
+
+        💡 หากต้องการเพิ่มงานเข้ารายการ ให้ส่ง `list_id` ใน `create_task` หรือ `update_task` /  
+        To put a task in a list, pass `list_id` to `create_task` or `update_task`.
+    "#)]
+    pub async fn create_list(
+        &self,
+        #[tool(aggr)] dto: ReqCreateListDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.create_list(dto).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อดึงรายการงาน (โปรเจกต์) ทั้งหมด เรียงตามชื่อ /  
+        Use this command to retrieve every todo list (project), sorted by name.
+        ใช้หา `id` ของรายการจากชื่อ เช่น "Groceries" / Use it to find the `id` of a list by its name, e.g. "Groceries".
+
+        📤 JSON Response Example:
+        [
+        { "id": 1, "name": "Groceries", "description": null, "created_at": "...", "updated_at": "..." }
+        ]
+    "#)]
+    pub async fn get_all_lists(
+        &self
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.get_all_lists().await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อเปลี่ยนชื่อหรือคำอธิบายของรายการ / Use this command to rename a list or change its description.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "id": 1,
+        "name": "Weekly groceries",
+        "description": null
+        }
+
+        🧾 รายละเอียดฟิลด์ / Field Descriptions:
+        - id (integer): รหัสรายการ / ID of the list (required)
+        - name (string, optional): ชื่อใหม่ / New name
+        - description (string, optional): คำอธิบายใหม่ ส่ง null เพื่อล้างค่า / New description, null clears it
+    "#)]
+    pub async fn update_list(
+        &self,
+        #[tool(aggr)] dto: ReqUpdateListDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.update_list(dto.id, dto).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อลบรายการ งานในรายการจะไม่ถูกลบแต่จะไม่อยู่ในรายการใดอีก /  
+        Use this command to delete a list, its tasks are kept but no longer belong to any list.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "id": 1
+        }
+    "#)]
+    pub async fn delete_list(
+        &self,
+        #[tool(aggr)] dto: GetListById
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.delete_list(dto.id as i32).await {
+            Ok(_) => Ok(CallToolResult::success(vec![Content::text(format!("List id {} deleted", dto.id))])),
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อดึงงานทั้งหมดในรายการ / Use this command to retrieve every task of a list.
+        ต้องการกรองหรือแบ่งหน้า ให้ใช้ `list_tasks` กับ `list_id` / To filter or page, use `list_tasks` with `list_id`.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "id": 1
+        }
+    "#)]
+    pub async fn get_tasks_in_list(
+        &self,
+        #[tool(aggr)] dto: GetListById
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.get_all_in_list(dto.id as i32).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อนับงานในรายการ / Use this command to count the tasks of a list.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "id": 1
+        }
+
+        📤 JSON Response Example:
+        { "list_id": 1, "total": 5, "done": 2, "undone": 3 }
+    "#)]
+    pub async fn count_tasks_in_list(
+        &self,
+        #[tool(aggr)] dto: GetListById
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.count_tasks_in_list(dto.id as i32).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ☑️ ใช้คำสั่งนี้เพื่อเพิ่มงานย่อย (ขั้นตอนในเช็กลิสต์) ต่อท้ายงานหลัก /  
+        ☑️ Use this command to add a subtask (checklist step) at the end of a task.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "parent_id": 1,
+        "title": "Tag the release",
+        "description": "git tag v1.2.0"
+        }
+
+        🧾 รายละเอียดฟิลด์ / Field Descriptions:
+        - parent_id (integer): รหัสงานหลัก / ID of the parent task (required)
+        - title (string): ชื่อขั้นตอน / Title of the step (required)
+        - description (string, optional): รายละเอียด / Details of the step
+        - is_done (boolean, optional): ทำเสร็จแล้วหรือยัง ค่าเริ่มต้น false / Already done, false by default
+
+        🛑 หมายเหตุ / Note:
+        - งานย่อยซ้อนกันไม่ได้ / A subtask cannot have subtasks of its own
+    "#)]
+    pub async fn add_subtask(
+        &self,
+        #[tool(aggr)] dto: ReqCreateSubtaskDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.add_subtask(dto, &self.origin()).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อดึงงานย่อยของงานหลักตามลำดับ / Use this command to retrieve the subtasks of a task, in checklist order.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "id": 1
+        }
+    "#)]
+    pub async fn get_subtasks(
+        &self,
+        #[tool(aggr)] dto: GetTaskById
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.get_subtasks(dto.id as i32).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ใช้คำสั่งนี้เพื่อจัดลำดับงานย่อยใหม่ / Use this command to reorder the subtasks of a task.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "parent_id": 1,
+        "subtask_ids": [7, 5, 6]
+        }
+
+        🧾 รายละเอียดฟิลด์ / Field Descriptions:
+        - parent_id (integer): รหัสงานหลัก / ID of the parent task
+        - subtask_ids (array of integer): รหัสงานย่อยทุกตัวตามลำดับใหม่ อย่างละหนึ่งครั้ง /  
+          Every subtask ID of the parent exactly once, in the new order
+    "#)]
+    pub async fn reorder_subtasks(
+        &self,
+        #[tool(aggr)] dto: ReqReorderSubtasksDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.reorder_subtasks(dto, &self.origin()).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ✅ ใช้คำสั่งนี้เพื่อติ๊กงานย่อยว่าเสร็จ หรือยกเลิกการติ๊ก /  
+        ✅ Use this command to tick a subtask off, or reopen it.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "subtask_id": 5,
+        "is_done": true
+        }
+
+        📤 ผลลัพธ์ / Response:
+        - งานหลักพร้อม `subtasks_done` / `subtasks_total` ที่อัปเดตแล้ว /  
+          The parent task with its updated `subtasks_done` / `subtasks_total`
+    "#)]
+    pub async fn complete_subtask(
+        &self,
+        #[tool(aggr)] dto: ReqTickSubtaskDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.tick_subtask(dto, &self.origin()).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+    #[tool(description = r#"
+        🧩 ใช้คำสั่งนี้เพื่อสร้างงานหลายงานพร้อมกันในคำสั่งเดียว /  
+        🧩 Use this command to create several tasks in a single call, e.g. when breaking a goal down.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "tasks": [
+            { "title": "Draft the outline", "description": "Sections and key points", "is_done": false },
+            { "title": "Write the intro", "description": "One page", "is_done": false, "priority": "high", "tags": ["writing"] }
+        ]
+        }
+
+        🧾 รายละเอียดฟิลด์ / Field Descriptions:
+        - tasks (array): งานที่จะสร้าง รูปแบบเดียวกับ create_task / Tasks to create, same fields as create_task
+
+        📤 JSON Response Example:
+        {
+        "committed": true,
+        "items": [
+            { "operation": "create", "index": 0, "task_id": 12, "status": "created", "error": null, "task": { ... } }
+        ]
+        }
+
+        🛑 หมายเหตุ / Note:
+        - ทั้งหมดหรือไม่มีเลย: ถ้ารายการใดล้มเหลว จะไม่มีการเปลี่ยนแปลงใด ๆ และ committed เป็น false /  
+          All or nothing: if any item fails nothing is applied and committed is false
+        - รายการที่ล้มเหลวมี status "failed" พร้อม error ส่วนที่เหลือเป็น "rolled_back" /  
+          The failing items have status "failed" with an error, the others are "rolled_back"
+        - ครั้งละไม่เกิน 100 รายการ / At most 100 items per call
+    "#)]
+    pub async fn create_tasks(
+        &self,
+        #[tool(aggr)] dto: ReqCreateTasksDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.create_tasks(dto, &self.origin()).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ✅ ใช้คำสั่งนี้เพื่อทำเครื่องหมายว่างานหลายงานเสร็จแล้วพร้อมกัน /  
+        ✅ Use this command to mark several tasks as done in a single call.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "ids": [3, 4, 7]
+        }
+
+        🧾 รายละเอียดฟิลด์ / Field Descriptions:
+        - ids (array of integer): รหัสงานที่เสร็จแล้ว / IDs of the tasks to complete
+        - งานที่เสร็จอยู่แล้วได้ status "already_done" / Tasks already done get status "already_done"
+
+        📤 JSON Response Example:
+        {
+        "committed": true,
+        "items": [
+            { "operation": "complete", "index": 0, "task_id": 12, "status": "completed", "error": null, "task": { ... } }
+        ]
+        }
+
+        🛑 หมายเหตุ / Note:
+        - ทั้งหมดหรือไม่มีเลย: ถ้ารายการใดล้มเหลว จะไม่มีการเปลี่ยนแปลงใด ๆ และ committed เป็น false /  
+          All or nothing: if any item fails nothing is applied and committed is false
+        - รายการที่ล้มเหลวมี status "failed" พร้อม error ส่วนที่เหลือเป็น "rolled_back" /  
+          The failing items have status "failed" with an error, the others are "rolled_back"
+        - ครั้งละไม่เกิน 100 รายการ / At most 100 items per call
+    "#)]
+    pub async fn complete_tasks(
+        &self,
+        #[tool(aggr)] dto: ReqTaskIdsDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.complete_tasks(dto, &self.origin()).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        🗑️ ใช้คำสั่งนี้เพื่อลบงานหลายงานพร้อมกัน /  
+        🗑️ Use this command to delete several tasks in a single call.
+
+        📥 JSON Payload ตัวอย่าง / Example:
+        {
+        "ids": [3, 4, 7]
+        }
+
+        🧾 รายละเอียดฟิลด์ / Field Descriptions:
+        - ids (array of integer): รหัสงานที่จะย้ายไปถังขยะ งานย่อยจะถูกย้ายไปด้วย / IDs of the tasks to move to the trash, their subtasks go with them
+
+        📤 JSON Response Example:
+        {
+        "committed": true,
+        "items": [
+            { "operation": "delete", "index": 0, "task_id": 12, "status": "deleted", "error": null, "task": { ... } }
+        ]
+        }
+
+        🛑 หมายเหตุ / Note:
+        - ทั้งหมดหรือไม่มีเลย: ถ้ารายการใดล้มเหลว จะไม่มีการเปลี่ยนแปลงใด ๆ และ committed เป็น false /  
+          All or nothing: if any item fails nothing is applied and committed is false
+        - รายการที่ล้มเหลวมี status "failed" พร้อม error ส่วนที่เหลือเป็น "rolled_back" /  
+          The failing items have status "failed" with an error, the others are "rolled_back"
+        - ครั้งละไม่เกิน 100 รายการ / At most 100 items per call
+    "#)]
+    pub async fn delete_tasks(
+        &self,
+        #[tool(aggr)] dto: ReqTaskIdsDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.delete_tasks(dto, &self.origin()).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        🗑️ ใช้คำสั่งนี้เพื่อดูงานที่อยู่ในถังขยะ เรียงจากที่ลบล่าสุด /  
+        🗑️ Use this command to list the tasks in the trash, most recently deleted first.
+
+        📤 JSON Response Example:
+        [
+        {
+            "id": 5,
+            "title": "Old idea",
+            "is_done": false,
+            "parent_id": null,
+            "deleted_at": "2026-10-18T09:15:00Z",
+            ...
+        }
+        ]
+
+        📝 Description:
+        - งานย่อยที่ถูกลบพร้อมงานหลักก็แสดงด้วย / Subtasks deleted together with their parent are listed too
+        - งานในถังขยะจะไม่ปรากฏในคำสั่งอื่น ๆ / Trashed tasks do not show up in any other command
+    "#)]
+    pub async fn list_trash(&self) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.list_trash().await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ♻️ ใช้คำสั่งนี้เพื่อกู้คืนงานจากถังขยะ /  
+        ♻️ Use this command to restore a task from the trash.
+
+        📥 JSON Payload ตัวอย่าง / Example Request:
+        {
+        "id": 5
+        }
+
+        🧾 รายละเอียด / Details:
+        - id (integer): รหัสของงานในถังขยะ ดูได้จาก `list_trash` / The ID of the trashed task, see `list_trash`
+        - งานย่อยที่ถูกลบพร้อมกันจะกลับมาด้วย / The subtasks deleted together with it come back too
+        - คืนค่างานที่กู้คืนแล้ว / Returns the restored task
+
+        🛑 หมายเหตุ / Note:
+        - งานย่อยกู้คืนเดี่ยว ๆ ไม่ได้ถ้างานหลักยังอยู่ในถังขยะ ให้กู้คืนงานหลักแทน /  
+          A subtask cannot be restored on its own while its parent is in the trash, restore the parent instead
+    "#)]
+    pub async fn restore_task(
+        &self,
+        #[tool(aggr)] dto: GetTaskById
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.restore_task(dto.id as i32, &self.origin()).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        🔥 ใช้คำสั่งนี้เพื่อลบงานในถังขยะแบบถาวร /  
+        🔥 Use this command to permanently delete a task that is in the trash.
+
+        📥 JSON Payload ตัวอย่าง / Example Request:
+        {
+        "id": 5
+        }
+
+        📤 JSON Response Example:
+        {
+        "purged": 2,
+        "task_ids": [5, 6]
+        }
+
+        🛑 หมายเหตุ / Note:
+        - ลบได้เฉพาะงานที่อยู่ในถังขยะ ใช้ `delete_task` ก่อน / Only trashed tasks can be purged, call `delete_task` first
+        - งานย่อยถูกลบถาวรไปด้วย และกู้คืนไม่ได้อีก / Its subtasks are purged too and nothing can be restored afterwards
+    "#)]
+    pub async fn purge_task(
+        &self,
+        #[tool(aggr)] dto: GetTaskById
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.purge_task(dto.id as i32).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        🔥 ใช้คำสั่งนี้เพื่อล้างถังขยะ ลบทุกงานในถังขยะแบบถาวร /  
+        🔥 Use this command to empty the trash, permanently deleting every task in it.
+
+        📤 JSON Response Example:
+        {
+        "purged": 3,
+        "task_ids": [5, 6, 9]
+        }
+
+        🛑 หมายเหตุ / Note:
+        - กู้คืนไม่ได้อีก ควรยืนยันกับผู้ใช้ก่อน / Nothing can be restored afterwards, confirm with the user first
+    "#)]
+    pub async fn empty_trash(&self) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.empty_trash().await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        📜 ใช้คำสั่งนี้เพื่อดูประวัติการเปลี่ยนแปลงของงาน เรียงจากเก่าไปใหม่ /  
+        📜 Use this command to see the change history of a task, oldest first.
+
+        📥 JSON Payload ตัวอย่าง / Example Request:
+        {
+        "id": 1
+        }
+
+        📤 JSON Response Example:
+        [
+        {
+            "id": 10,
+            "task_id": 1,
+            "action": "updated",
+            "before": { "title": "Buy milk", "is_done": false, ... },
+            "after": { "title": "Buy milk", "is_done": true, ... },
+            "origin": { "source": "mcp", "client_name": "claude-ai", "session_id": "5f0c2a9e1b7d4c33" },
+            "changed_at": "2026-10-18T09:15:00Z"
+        }
+        ]
+
+        🧾 รายละเอียด / Details:
+        - action: "created", "updated", "completed", "deleted" (ย้ายไปถังขยะ / moved to the trash) หรือ / or "restored"
+        - before เป็น null ตอนสร้าง, after เป็น null ตอนลบ / before is null on create, after is null on delete
+        - origin.source: "http" หรือ / or "mcp" พร้อมชื่อ client / with the client name
+        - ใช้ได้กับงานที่อยู่ในถังขยะหรือถูกลบถาวรแล้ว / Works for trashed and purged tasks too
+    "#)]
+    pub async fn task_history(
+        &self,
+        #[tool(aggr)] dto: GetTaskById
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.get_task_history(dto.id as i32).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ↩️ ใช้คำสั่งนี้เพื่อย้อนกลับคำสั่งล่าสุดที่ทำในการเชื่อมต่อนี้ /  
+        ↩️ Use this command to undo the last operations made in this session (connection).
+
+        📥 JSON Payload ตัวอย่าง / Example Request:
+        {
+        "operations": 2
+        }
+
+        🧾 รายละเอียด / Details:
+        - operations (integer, optional): จำนวนคำสั่งล่าสุดที่จะย้อนกลับ ค่าเริ่มต้น 1 สูงสุด 50 /  
+          How many of the last tool calls to undo, 1 by default, at most 50
+        - งานที่สร้างจะถูกย้ายไปถังขยะ งานที่ลบจะถูกกู้คืน งานที่แก้ไขจะกลับเป็นค่าเดิม /  
+          Created tasks go to the trash, deleted tasks are restored, updated tasks get their previous fields and tags back
+        - คำสั่ง batch นับเป็นหนึ่งคำสั่ง / A batch tool call counts as one operation
+
+        📤 JSON Response Example:
+        {
+        "session_id": "5f0c2a9e1b7d4c33",
+        "operations": 1,
+        "changes": [
+            { "audit_id": 12, "task_id": 4, "undone_action": "updated", "action": "updated", "before": { ... }, "after": { ... } }
+        ]
+        }
+
+        🛑 หมายเหตุ / Note:
+        - ทั้งหมดหรือไม่มีเลย / All or nothing
+        - ถ้ามีผู้อื่นแก้ไขงานเหล่านั้นหลังจากนี้ จะปฏิเสธการย้อนกลับ /  
+          Refused when someone else changed one of those tasks afterwards
+        - ย้อนกลับการ undo ไม่ได้ / An undo cannot be undone
+    "#)]
+    pub async fn undo(
+        &self,
+        #[tool(aggr)] dto: ReqUndoDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.undo_session(&self.session_id, dto, &self.origin()).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        🔍 ใช้คำสั่งนี้เพื่อค้นหางานจากคำในชื่อหรือรายละเอียด เรียงจากตรงที่สุด /  
+        🔍 Use this command to find tasks by words in their title or description, best matches first.
+
+        📥 JSON Payload ตัวอย่าง / Example Request:
+        {
+        "query": "invoice march",
+        "limit": 5
+        }
+
+        🧾 รายละเอียด / Details:
+        - query (string): คำที่ต้องการค้นหา ทุกคำต้องพบ (ค้นหาภายในคำได้) /  
+          Words to look for, every word has to match, also inside longer words
+        - คำที่สั้นกว่า 3 ตัวอักษรจะถูกข้าม / Words shorter than 3 characters are ignored
+        - limit (integer, optional): จำนวนผลลัพธ์ ค่าเริ่มต้น 10 สูงสุด 50 / How many matches, 10 by default, at most 50
+        - งานในถังขยะจะไม่ถูกค้นหา / Tasks in the trash are left out
+
+        📤 JSON Response Example:
+        [
+        {
+            "score": 4.2,
+            "title_snippet": "Pay the **invoice** for **March**",
+            "description_snippet": "Send the **invoice** to accounting…",
+            "task": { "id": 7, "title": "Pay the invoice for March", "is_done": false, ... }
+        }
+        ]
+
+        ✅ ใช้ task.id กับคำสั่งอื่นต่อได้ / Use task.id with the other tools
+    "#)]
+    pub async fn search_tasks(
+        &self,
+        #[tool(aggr)] dto: ReqSearchTodoDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.search_tasks(dto).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        🔁 ใช้คำสั่งนี้เพื่อสร้างงานที่ทำซ้ำ เช่น ทุกวัน ทุกสัปดาห์ ทุกเดือน /  
+        🔁 Use this command to create a recurring task, e.g. daily, weekly or monthly.
+
+        📥 JSON Payload ตัวอย่าง / Example Request:
+        {
+        "task": {
+            "title": "Send monthly invoices",
+            "description": "Invoice every client for last month",
+            "is_done": false,
+            "due_date": "2026-11-01T09:00:00Z",
+            "tags": ["work"]
+        },
+        "recurrence": { "frequency": "monthly", "interval": 1, "count": 12 }
+        }
+
+        🧾 รายละเอียด / Details:
+        - task: งานแรกของชุด ใช้ฟิลด์เดียวกับ `create_task` / The first occurrence, same fields as `create_task`
+        - recurrence.frequency: "daily", "weekly", "monthly" หรือ / or "yearly"
+        - recurrence.interval (integer, optional): ทุก ๆ กี่หน่วย ค่าเริ่มต้น 1 / Repeat every N units, 1 by default (weekly + 2 = every other week)
+        - recurrence.until (string, optional): ไม่สร้างงานที่ครบกำหนดหลังวันนี้ (RFC 3339) / No occurrence due after this date
+        - recurrence.count (integer, optional): จำนวนครั้งทั้งหมดรวมงานแรก / Number of occurrences, the first one included
+
+        🔄 การทำงาน / How it works:
+        - เมื่อทำงานล่าสุดของชุดเสร็จด้วย `update_task` หรือ `complete_tasks` ระบบจะสร้างงานถัดไปให้อัตโนมัติ /  
+          Completing the latest task of the series with `update_task` or `complete_tasks` creates the next one
+        - งานถัดไปครบกำหนดตามตาราง นับจากวันครบกำหนดของงานแรก (หรือเวลาที่สร้างถ้าไม่มี) และมีแท็กเดียวกัน /  
+          The next task is due on the schedule counted from the first due date (or creation time) and keeps the tags
+
+        📤 ผลลัพธ์ / Response: ชุดงาน (id, กฎ, next_due, occurrences) / The series with its id, rule, next_due and occurrences
+    "#)]
+    pub async fn create_recurring_task(
+        &self,
+        #[tool(aggr)] dto: ReqCreateRecurringTaskDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.create_recurring_task(dto, &self.origin()).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        🔁 ใช้คำสั่งนี้เพื่อดูชุดงานที่ทำซ้ำ กฎ งานทั้งหมดในชุด และวันครบกำหนดถัดไป /  
+        🔁 Use this command to inspect a recurring series: its rule, its tasks and when the next one is due.
+
+        📥 JSON Payload ตัวอย่าง / Example Request:
+        {
+        "id": 1
+        }
+
+        🧾 รายละเอียด / Details:
+        - id: ค่า recurrence_id ของงานในชุด / The recurrence_id shown on the tasks of the series
+        - next_due เป็น null เมื่อชุดจบหรือถูกหยุดแล้ว / next_due is null once the series is over or stopped
+    "#)]
+    pub async fn get_recurrence(
+        &self,
+        #[tool(aggr)] dto: GetRecurrenceById
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.get_recurrence(dto.id as i32).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        ⏹️ ใช้คำสั่งนี้เพื่อหยุดชุดงานที่ทำซ้ำ งานที่มีอยู่จะยังอยู่ แต่จะไม่สร้างงานใหม่อีก /  
+        ⏹️ Use this command to stop a recurring series, existing tasks are kept but no new ones are created.
+
+        📥 JSON Payload ตัวอย่าง / Example Request:
+        {
+        "id": 1
+        }
+    "#)]
+    pub async fn stop_recurrence(
+        &self,
+        #[tool(aggr)] dto: GetRecurrenceById
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.stop_recurrence(dto.id as i32).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        📤 ใช้คำสั่งนี้เพื่อส่งออกงานทั้งหมดเป็นไฟล์ JSON, CSV, Markdown checklist หรือ iCalendar /  
+        📤 Use this command to export every task as JSON, CSV, a Markdown checklist or iCalendar.
+
+        📥 JSON Payload ตัวอย่าง / Example Request:
+        {
+        "format": "markdown"
+        }
+
+        🧾 รายละเอียด / Details:
+        - format: "json" (ค่าเริ่มต้น ครบทุกฟิลด์ รวมแท็ก งานที่เกิดซ้ำ และถังขยะ / default, every field with tags, recurring series and the trash), "csv", "markdown" หรือ / or "ics"
+        - markdown เก็บเฉพาะชื่องาน สถานะ งานย่อย และลิสต์ / markdown only keeps titles, completion, subtasks and lists
+        - ics เขียนงานละหนึ่ง VTODO สำหรับแอปปฏิทิน / ics writes one VTODO per task for calendar apps
+
+        📤 ผลลัพธ์ / Response: เนื้อหาไฟล์ นำไปใช้กับ import_tasks ได้ / The file content, which import_tasks reads back
+    "#)]
+    pub async fn export_tasks(
+        &self,
+        #[tool(aggr)] dto: ReqExportTasksDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.export_tasks(dto.format).await {
+            Ok(data) => Ok(CallToolResult::success(vec![Content::text(data)])),
+            Err(e) => Err(e.into())
+        }
+    }
+
+
+    #[tool(description = r#"
+        📥 ใช้คำสั่งนี้เพื่อนำเข้างานจากไฟล์ JSON, CSV, Markdown checklist หรือ iCalendar /  
+        📥 Use this command to import tasks from JSON, CSV, a Markdown checklist or iCalendar.
+
+        📥 JSON Payload ตัวอย่าง / Example Request:
+        {
+        "format": "markdown",
+        "content": "- [ ] Buy milk\n  - [x] Check the fridge\n- [ ] Buy bread",
+        "dry_run": true
+        }
+
+        🧾 รายละเอียด / Details:
+        - format: "json" (ค่าเริ่มต้น / default), "csv", "markdown" หรือ / or "ics"
+        - content: เนื้อหาไฟล์ เช่นที่ได้จาก export_tasks / The file content, e.g. from export_tasks
+        - dry_run: true เพื่อดูผลก่อนโดยไม่บันทึก / true to preview without storing anything
+        - งานที่ชื่อ วันครบกำหนด และงานหลักตรงกับงานที่มีอยู่จะถูกข้าม (duplicate) / Tasks matching an existing task's title, due date and parent are skipped as duplicates, trashed tasks are matched against the trash
+        - CSV ต้องมีแถวหัวตารางที่มีคอลัมน์ title / CSV needs a header row with a title column
+        - งานต้องมี description เหมือนตอนสร้างงาน ยกเว้นงานย่อย / tasks need a description like create_task does, subtasks excepted
+        - ics อ่านเฉพาะ VTODO ส่วนอื่นจะถูกข้าม / ics reads the VTODO components, other components are ignored
+        - เวลาท้องถิ่นใน ics ต้องมี TZID หรือ X-WR-TIMEZONE ของปฏิทิน เช่น Europe/Paris / local ics times need a TZID or a calendar X-WR-TIMEZONE, an IANA zone such as Europe/Paris
+        - ลิสต์ที่ยังไม่มีจะถูกสร้างให้ / Missing lists are created
+
+        📤 ผลลัพธ์ / Response: จำนวนงานที่สร้างและที่ซ้ำ พร้อมผลของแต่ละรายการ / Counts of created and duplicate tasks with one entry per record
+    "#)]
+    pub async fn import_tasks(
+        &self,
+        #[tool(aggr)] dto: ReqImportTasksDto
+    ) -> Result<CallToolResult, McpError> {
+        match self.todo_use_case.import_tasks(dto, &self.origin()).await {
+            Ok(data) => {
+                if let Ok(convert) = Content::json(data) {
+                    Ok(CallToolResult::success(vec![convert]))
+                } else {
+                    Err(McpError::internal_error("Failed to convert results to JSON".to_string(), None))
+                }
+            },
+            Err(e) => Err(e.into())
+        }
+    }
+}
+
+
+
+
+
+
+
+impl MCPHandler {
+    /// Origin of a new operation of this session, call it once per tool call.
+    /// The client name is known once the `initialize` request has been handled.
+    fn origin(&self) -> ChangeOrigin {
+        let client_name = self.peer.as_ref().map(|peer| peer.peer_info().client_info.name.clone());
+        let operation = self.operations.fetch_add(1, Ordering::Relaxed) + 1;
+        ChangeOrigin::mcp(client_name, self.session_id.as_str(), format!("{}-{}", self.session_id, operation))
+    }
+}
+
+
+const_string!(Echo = "echo");
+#[tool(tool_box)]
+impl ServerHandler for MCPHandler {
+    
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            protocol_version: ProtocolVersion::V_2024_11_05,
+            capabilities: ServerCapabilities::builder()
+                .enable_prompts()
+                .enable_resources()
+                .enable_resources_subscribe()
+                .enable_resources_list_changed()
+                .enable_tools()
+                .enable_logging()
+                .build(),
+            server_info: Implementation::from_build_env(),
+            instructions: Some(format!(
+                "Todo list server. Tools manage tasks, tags, lists and subtasks; resources under todo:// expose tasks, stats, lists and tags as JSON. \
+                 This session is {}, `undo` reverts its last tool calls. \
+                 Task reminders and due dates arrive as logging notifications from the `reminders` logger.",
+                self.session_id
+            )),
+        }
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        Ok(ListResourcesResult {
+            resources: resources::list_resources(&self.todo_use_case).await?,
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        ReadResourceRequestParam { uri }: ReadResourceRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        Ok(ReadResourceResult {
+            contents: vec![resources::read_resource(&self.todo_use_case, &uri).await?],
+        })
+    }
+
+    async fn subscribe(
+        &self,
+        SubscribeRequestParam { uri }: SubscribeRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        if !uri.starts_with("todo://") {
+            return Err(McpError::invalid_params(format!("Cannot subscribe to {}, only todo:// resources", uri), None));
+        }
+        self.subscriptions.subscribe(uri);
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        UnsubscribeRequestParam { uri }: UnsubscribeRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        self.subscriptions.unsubscribe(&uri);
+        Ok(())
+    }
+
+    fn get_peer(&self) -> Option<Peer<RoleServer>> {
+        self.peer.clone()
+    }
+
+    /// Called once per session right after the handshake, which is when the client can start receiving notifications.
+    fn set_peer(&mut self, peer: Peer<RoleServer>) {
+        spawn_change_forwarder(&self.todo_use_case, peer.clone(), &self.subscriptions);
+        spawn_reminder_forwarder(&self.todo_use_case, peer.clone(), &self.log_level);
+        self.peer = Some(peer);
+    }
+
+    async fn set_level(
+        &self,
+        SetLevelRequestParam { level }: SetLevelRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        self.log_level.set(level);
+        Ok(())
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        Ok(ListPromptsResult {
+            next_cursor: None,
+            prompts: prompts::prompt_list(),
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        GetPromptRequestParam { name, arguments }: GetPromptRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        prompts::get_prompt(&self.todo_use_case, &name, arguments).await
     }
 
     async fn list_resource_templates(
@@ -409,7 +1644,7 @@ impl ServerHandler for MCPHandler {
     ) -> Result<ListResourceTemplatesResult, McpError> {
         Ok(ListResourceTemplatesResult {
             next_cursor: None,
-            resource_templates: Vec::new(),
+            resource_templates: resources::resource_templates(),
         })
     }
 
@@ -0,0 +1,23 @@
+use rmcp::Error as McpError;
+use serde_json::json;
+
+use crate::domain::error::domain_error::DomainError;
+
+
+
+/// Maps domain errors onto MCP error codes:
+/// `NotFound` -> resource_not_found, `Validation` -> invalid_params,
+/// `Conflict` -> invalid_request, `Storage` -> internal_error.
+impl From<DomainError> for McpError {
+    fn from(err: DomainError) -> Self {
+        match err {
+            DomainError::NotFound(message) => McpError::resource_not_found(message, None),
+            DomainError::Validation(message) => McpError::invalid_params(message, None),
+            DomainError::Conflict(message) => McpError::invalid_request(message, Some(json!({ "reason": "conflict" }))),
+            DomainError::Storage(message) => {
+                tracing::error!("Storage error: {}", message);
+                McpError::internal_error("Internal storage error, please try again", None)
+            }
+        }
+    }
+}
\ No newline at end of file
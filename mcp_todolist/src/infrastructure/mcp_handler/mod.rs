@@ -1 +1,6 @@
-pub mod handler;
\ No newline at end of file
+pub mod handler;
+pub mod mcp_error;
+pub mod prompts;
+pub mod resources;
+pub mod subscriptions;
+pub mod reminders;
\ No newline at end of file
@@ -0,0 +1,338 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rmcp::{model::{GetPromptResult, JsonObject, Prompt, PromptArgument, PromptMessage, PromptMessageContent, PromptMessageRole}, Error as McpError};
+
+use crate::{application::usecase::todo_usecase::TodolistUseCase, domain::dto::{query_dto::{parse_datetime, SortDirection, TodoQuery, TodoSortField, MAX_PAGE_LIMIT}, todo_dto::{ResEntryTodoDto, TaskPriority}}};
+
+
+
+fn argument(name: &str, description: &str, required: bool) -> PromptArgument {
+    PromptArgument {
+        name: name.to_string(),
+        description: Some(description.to_string()),
+        required: Some(required),
+    }
+}
+
+fn list_argument() -> PromptArgument {
+    argument("list", "Only tasks of this list, by id or name", false)
+}
+
+fn tag_argument() -> PromptArgument {
+    argument("tag", "Only tasks carrying this tag", false)
+}
+
+
+pub fn prompt_list() -> Vec<Prompt> {
+    vec![
+        Prompt::new(
+            "daily_plan",
+            Some("Plan a day from the overdue, due and high priority open tasks"),
+            Some(vec![
+                argument("date", "Day to plan, YYYY-MM-DD, today (UTC) by default", false),
+                list_argument(),
+                tag_argument(),
+            ]),
+        ),
+        Prompt::new(
+            "weekly_review",
+            Some("Review a week: what got done, what slipped and what is coming next"),
+            Some(vec![
+                argument("week_start", "First day of the week, YYYY-MM-DD, 6 days ago by default", false),
+                list_argument(),
+                tag_argument(),
+            ]),
+        ),
+        Prompt::new(
+            "triage_inbox",
+            Some("Sort open tasks that have no list, no due date or no tags"),
+            None::<Vec<PromptArgument>>,
+        ),
+        Prompt::new(
+            "summarize_done_since",
+            Some("Summarize the tasks completed since a date"),
+            Some(vec![
+                argument("since", "RFC 3339 date time or YYYY-MM-DD", true),
+                list_argument(),
+                tag_argument(),
+            ]),
+        ),
+    ]
+}
+
+pub async fn get_prompt(use_case: &TodolistUseCase, name: &str, arguments: Option<JsonObject>) -> Result<GetPromptResult, McpError> {
+    let arguments = arguments.unwrap_or_default();
+    let scope = PromptScope::resolve(use_case, &arguments).await?;
+
+    match name {
+        "daily_plan" => daily_plan(use_case, &arguments, scope).await,
+        "weekly_review" => weekly_review(use_case, &arguments, scope).await,
+        "triage_inbox" => triage_inbox(use_case).await,
+        "summarize_done_since" => summarize_done_since(use_case, &arguments, scope).await,
+        _ => Err(McpError::invalid_params("prompt not found", None)),
+    }
+}
+
+
+/// The optional `list` / `tag` arguments shared by the prompts.
+#[derive(Default)]
+struct PromptScope {
+    list_id: Option<i32>,
+    tag: Option<String>,
+    label: String,
+}
+
+impl PromptScope {
+    async fn resolve(use_case: &TodolistUseCase, arguments: &JsonObject) -> Result<Self, McpError> {
+        let mut scope = PromptScope::default();
+        let mut labels = Vec::new();
+
+        if let Some(list) = string_argument(arguments, "list") {
+            let found = match list.parse::<i32>() {
+                Ok(list_id) => use_case.get_list_by_id(list_id).await?,
+                Err(_) => use_case
+                    .get_all_lists()
+                    .await?
+                    .into_iter()
+                    .find(|entry| entry.name.eq_ignore_ascii_case(&list))
+                    .ok_or_else(|| McpError::invalid_params(format!("List {} not found", list), None))?,
+            };
+            labels.push(format!("list \"{}\"", found.name));
+            scope.list_id = Some(found.id as i32);
+        }
+        if let Some(tag) = string_argument(arguments, "tag") {
+            labels.push(format!("tag \"{}\"", tag));
+            scope.tag = Some(tag);
+        }
+
+        scope.label = if labels.is_empty() { "all tasks".to_string() } else { labels.join(", ") };
+        Ok(scope)
+    }
+
+    fn query(&self) -> TodoQuery {
+        TodoQuery {
+            list_id: self.list_id,
+            tags: self.tag.clone().map(|tag| vec![tag]),
+            sort_by: TodoSortField::DueDate,
+            sort_dir: SortDirection::Asc,
+            limit: Some(MAX_PAGE_LIMIT),
+            ..Default::default()
+        }
+    }
+}
+
+
+fn string_argument(arguments: &JsonObject, name: &str) -> Option<String> {
+    arguments
+        .get(name)
+        .and_then(|value| match value {
+            serde_json::Value::String(text) => Some(text.trim().to_string()),
+            serde_json::Value::Number(number) => Some(number.to_string()),
+            _ => None,
+        })
+        .filter(|text| !text.is_empty())
+}
+
+fn date_argument(arguments: &JsonObject, name: &str) -> Result<Option<NaiveDate>, McpError> {
+    let raw = string_argument(arguments, name);
+    Ok(parse_datetime(raw.as_deref(), name)?.map(|date_time| date_time.date_naive()))
+}
+
+fn start_of(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).expect("midnight is always a valid time").and_utc()
+}
+
+/// Walk every page of the query, top-level tasks only since subtasks are rolled up into their parent.
+async fn fetch_tasks(use_case: &TodolistUseCase, mut query: TodoQuery) -> Result<Vec<ResEntryTodoDto>, McpError> {
+    let mut tasks = Vec::new();
+    loop {
+        let page = use_case.list_tasks(query.clone()).await?;
+        tasks.extend(page.items.into_iter().filter(|task| task.parent_id.is_none()));
+        match page.next_cursor {
+            Some(cursor) => query.cursor = Some(cursor),
+            None => return Ok(tasks),
+        }
+    }
+}
+
+async fn list_names(use_case: &TodolistUseCase) -> Result<HashMap<u32, String>, McpError> {
+    Ok(use_case
+        .get_all_lists()
+        .await?
+        .into_iter()
+        .map(|list| (list.id, list.name))
+        .collect())
+}
+
+fn priority_label(priority: TaskPriority) -> &'static str {
+    match priority {
+        TaskPriority::Low => "low",
+        TaskPriority::Normal => "normal",
+        TaskPriority::High => "high",
+        TaskPriority::Urgent => "urgent",
+    }
+}
+
+fn task_line(task: &ResEntryTodoDto, lists: &HashMap<u32, String>) -> String {
+    let mut details = vec![format!("priority {}", priority_label(task.priority))];
+    if let Some(due_date) = task.due_date {
+        details.push(format!("due {}", due_date.format("%Y-%m-%d %H:%M UTC")));
+    }
+    if let Some(list) = task.list_id.and_then(|list_id| lists.get(&list_id)) {
+        details.push(format!("list {}", list));
+    }
+    if !task.tags.is_empty() {
+        details.push(format!("tags {}", task.tags.join(", ")));
+    }
+    if task.subtasks_total > 0 {
+        details.push(format!("subtasks {}/{} done", task.subtasks_done, task.subtasks_total));
+    }
+
+    let mut line = format!(
+        "- #{} [{}] {} ({})",
+        task.id,
+        if task.is_done { "x" } else { " " },
+        task.title,
+        details.join(", ")
+    );
+    if !task.description.trim().is_empty() {
+        line.push_str(&format!("\n  {}", task.description.trim()));
+    }
+    line
+}
+
+fn section(title: &str, tasks: &[&ResEntryTodoDto], lists: &HashMap<u32, String>) -> String {
+    let body = if tasks.is_empty() {
+        "(none)".to_string()
+    } else {
+        tasks.iter().map(|task| task_line(task, lists)).collect::<Vec<_>>().join("\n")
+    };
+    format!("## {} ({})\n{}", title, tasks.len(), body)
+}
+
+fn user_prompt(description: String, text: String) -> GetPromptResult {
+    GetPromptResult {
+        description: Some(description),
+        messages: vec![PromptMessage {
+            role: PromptMessageRole::User,
+            content: PromptMessageContent::text(text),
+        }],
+    }
+}
+
+
+async fn daily_plan(use_case: &TodolistUseCase, arguments: &JsonObject, scope: PromptScope) -> Result<GetPromptResult, McpError> {
+    let date = date_argument(arguments, "date")?.unwrap_or_else(|| Utc::now().date_naive());
+    let day_start = start_of(date);
+    let day_end = day_start + Duration::days(1);
+
+    let lists = list_names(use_case).await?;
+    let open = fetch_tasks(use_case, TodoQuery { is_done: Some(false), ..scope.query() }).await?;
+
+    let overdue: Vec<_> = open.iter().filter(|task| task.due_date.is_some_and(|due| due < day_start)).collect();
+    let due: Vec<_> = open.iter().filter(|task| task.due_date.is_some_and(|due| due >= day_start && due < day_end)).collect();
+    let important: Vec<_> = open
+        .iter()
+        .filter(|task| task.priority >= TaskPriority::High && task.due_date.is_none_or(|due| due >= day_end))
+        .collect();
+
+    let text = format!(
+        "Help me plan {} ({}).\n\
+        Propose a realistic order of work for the day: clear what is overdue or due first, \
+        fit in the important tasks after that, and point out anything that should be \
+        rescheduled or split into subtasks.\n\n{}\n\n{}\n\n{}",
+        date,
+        scope.label,
+        section("Overdue", &overdue, &lists),
+        section("Due that day", &due, &lists),
+        section("High priority, not due that day", &important, &lists),
+    );
+    Ok(user_prompt(format!("Daily plan for {}", date), text))
+}
+
+async fn weekly_review(use_case: &TodolistUseCase, arguments: &JsonObject, scope: PromptScope) -> Result<GetPromptResult, McpError> {
+    let week_start = date_argument(arguments, "week_start")?.unwrap_or_else(|| Utc::now().date_naive() - Duration::days(6));
+    let from = start_of(week_start);
+    let to = from + Duration::days(7);
+
+    let lists = list_names(use_case).await?;
+    let done = fetch_tasks(use_case, TodoQuery {
+        is_done: Some(true),
+        updated_from: Some(from),
+        updated_to: Some(to),
+        ..scope.query()
+    })
+    .await?;
+    let open = fetch_tasks(use_case, TodoQuery { is_done: Some(false), ..scope.query() }).await?;
+
+    let done: Vec<_> = done.iter().collect();
+    let slipped: Vec<_> = open.iter().filter(|task| task.due_date.is_some_and(|due| due < to)).collect();
+    let upcoming: Vec<_> = open
+        .iter()
+        .filter(|task| task.due_date.is_some_and(|due| due >= to && due < to + Duration::days(7)))
+        .collect();
+
+    let text = format!(
+        "Run a weekly review of the week starting {} ({}).\n\
+        Summarize what was accomplished, call out what slipped and why it might have, \
+        and suggest the priorities for the coming week.\n\n{}\n\n{}\n\n{}",
+        week_start,
+        scope.label,
+        section("Done during the week", &done, &lists),
+        section("Open and due by the end of the week", &slipped, &lists),
+        section("Due the following week", &upcoming, &lists),
+    );
+    Ok(user_prompt(format!("Weekly review of the week starting {}", week_start), text))
+}
+
+async fn triage_inbox(use_case: &TodolistUseCase) -> Result<GetPromptResult, McpError> {
+    let lists = list_names(use_case).await?;
+    let open = fetch_tasks(use_case, TodoQuery { is_done: Some(false), ..PromptScope::default().query() }).await?;
+
+    let inbox: Vec<_> = open
+        .iter()
+        .filter(|task| task.list_id.is_none() || task.due_date.is_none() || task.tags.is_empty())
+        .collect();
+    let list_catalog = if lists.is_empty() {
+        "(no lists yet)".to_string()
+    } else {
+        let mut names: Vec<_> = lists.iter().map(|(list_id, name)| format!("- #{} {}", list_id, name)).collect();
+        names.sort();
+        names.join("\n")
+    };
+
+    let text = format!(
+        "Triage my inbox.\n\
+        For each task below, suggest the list it belongs to, tags, a priority and a due date \
+        where one makes sense. Flag tasks that look like duplicates, are too vague to act on, \
+        or could be dropped.\n\n## Existing lists\n{}\n\n{}",
+        list_catalog,
+        section("Tasks missing a list, a due date or tags", &inbox, &lists),
+    );
+    Ok(user_prompt("Triage of unsorted open tasks".to_string(), text))
+}
+
+async fn summarize_done_since(use_case: &TodolistUseCase, arguments: &JsonObject, scope: PromptScope) -> Result<GetPromptResult, McpError> {
+    let raw_since = string_argument(arguments, "since")
+        .ok_or_else(|| McpError::invalid_params("No since provided to summarize_done_since", None))?;
+    let since = parse_datetime(Some(&raw_since), "since")?.expect("a present argument always parses to a value");
+
+    let lists = list_names(use_case).await?;
+    let done = fetch_tasks(use_case, TodoQuery {
+        is_done: Some(true),
+        updated_from: Some(since),
+        ..scope.query()
+    })
+    .await?;
+    let done: Vec<_> = done.iter().collect();
+
+    let text = format!(
+        "Summarize what I completed since {} ({}).\n\
+        Group the work by theme, highlight the most significant results and keep it short \
+        enough to paste into a status update.\n\n{}",
+        raw_since,
+        scope.label,
+        section("Completed tasks", &done, &lists),
+    );
+    Ok(user_prompt(format!("Tasks completed since {}", raw_since), text))
+}
\ No newline at end of file
@@ -0,0 +1,115 @@
+use std::sync::{Arc, Mutex, Weak};
+use anyhow::Result;
+use rmcp::{model::{LoggingLevel, LoggingMessageNotificationParam}, Peer, RoleServer};
+use tokio::{sync::broadcast::error::RecvError, task::JoinHandle};
+
+use crate::{application::{reminder::notification_sink::NotificationSink, usecase::todo_usecase::TodolistUseCase}, domain::dto::reminder_dto::{ReminderKind, ResReminderDto}};
+
+
+
+/// Sends reminders to the connected MCP clients as `notifications/message` (logger `reminders`).
+pub struct McpLoggingSink {
+    use_case: Arc<TodolistUseCase>,
+}
+
+impl McpLoggingSink {
+    pub fn new(use_case: Arc<TodolistUseCase>) -> Self {
+        Self { use_case }
+    }
+}
+
+#[async_trait::async_trait]
+impl NotificationSink for McpLoggingSink {
+    fn name(&self) -> &'static str {
+        "mcp"
+    }
+
+    async fn notify(&self, reminder: &ResReminderDto) -> Result<()> {
+        self.use_case.publish_reminder(reminder);
+        Ok(())
+    }
+}
+
+
+/// Lowest level one MCP session wants to receive, set through `logging/setLevel`.
+#[derive(Clone)]
+pub struct SessionLogLevel {
+    level: Arc<Mutex<LoggingLevel>>,
+}
+
+impl Default for SessionLogLevel {
+    fn default() -> Self {
+        Self { level: Arc::new(Mutex::new(LoggingLevel::Info)) }
+    }
+}
+
+impl SessionLogLevel {
+    pub fn set(&self, level: LoggingLevel) {
+        *self.level.lock().expect("log level lock poisoned") = level;
+    }
+
+    fn downgrade(&self) -> Weak<Mutex<LoggingLevel>> {
+        Arc::downgrade(&self.level)
+    }
+}
+
+fn severity(level: &LoggingLevel) -> u8 {
+    match level {
+        LoggingLevel::Debug => 0,
+        LoggingLevel::Info => 1,
+        LoggingLevel::Notice => 2,
+        LoggingLevel::Warning => 3,
+        LoggingLevel::Error => 4,
+        LoggingLevel::Critical => 5,
+        LoggingLevel::Alert => 6,
+        LoggingLevel::Emergency => 7,
+    }
+}
+
+/// A reminder is a notice, reaching the due date is a warning.
+fn reminder_level(reminder: &ResReminderDto) -> LoggingLevel {
+    match reminder.kind {
+        ReminderKind::Reminder => LoggingLevel::Notice,
+        ReminderKind::Due => LoggingLevel::Warning,
+    }
+}
+
+
+/// Forward fired reminders to one MCP client as logging notifications.
+///
+/// Like the change forwarder it stops at the first reminder after the session is dropped.
+pub fn spawn_reminder_forwarder(use_case: &TodolistUseCase, peer: Peer<RoleServer>, log_level: &SessionLogLevel) -> JoinHandle<()> {
+    let mut reminders = use_case.subscribe_reminders();
+    let session = log_level.downgrade();
+
+    tokio::spawn(async move {
+        loop {
+            let reminder = match reminders.recv().await {
+                Ok(reminder) => reminder,
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::warn!("MCP reminder forwarder lagged behind, {} reminders skipped", missed);
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            };
+            let Some(minimum) = session.upgrade() else {
+                return;
+            };
+
+            let level = reminder_level(&reminder);
+            if severity(&level) < severity(&minimum.lock().expect("log level lock poisoned")) {
+                continue;
+            }
+            let Ok(data) = serde_json::to_value(&reminder) else {
+                continue;
+            };
+            let _ = peer
+                .notify_logging_message(LoggingMessageNotificationParam {
+                    level,
+                    logger: Some("reminders".to_string()),
+                    data,
+                })
+                .await;
+        }
+    })
+}
\ No newline at end of file
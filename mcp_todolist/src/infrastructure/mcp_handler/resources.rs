@@ -0,0 +1,157 @@
+use rmcp::{model::{AnnotateAble, RawResource, RawResourceTemplate, Resource, ResourceContents, ResourceTemplate}, Error as McpError};
+use rocket::http::RawStr;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::{application::usecase::todo_usecase::TodolistUseCase, domain::dto::{event_dto::{ChangeEntity, ChangeKind, ResChangeEventDto}, query_dto::{TodoQuery, MAX_PAGE_LIMIT}}};
+
+
+
+pub const TASKS_URI: &str = "todo://tasks";
+pub const STATS_URI: &str = "todo://stats";
+pub const LISTS_URI: &str = "todo://lists";
+pub const TAGS_URI: &str = "todo://tags";
+
+const JSON_MIME: &str = "application/json";
+
+
+fn json_resource(uri: impl Into<String>, name: impl Into<String>, description: impl Into<String>) -> Resource {
+    let mut resource = RawResource::new(uri, name);
+    resource.description = Some(description.into());
+    resource.mime_type = Some(JSON_MIME.to_string());
+    resource.no_annotation()
+}
+
+fn json_template(uri_template: &str, name: &str, description: &str) -> ResourceTemplate {
+    RawResourceTemplate {
+        uri_template: uri_template.to_string(),
+        name: name.to_string(),
+        description: Some(description.to_string()),
+        mime_type: Some(JSON_MIME.to_string()),
+    }
+    .no_annotation()
+}
+
+fn json_contents(uri: &str, data: &impl Serialize) -> Result<ResourceContents, McpError> {
+    let text = serde_json::to_string_pretty(data)
+        .map_err(|_| McpError::internal_error("Failed to convert results to JSON".to_string(), None))?;
+
+    Ok(ResourceContents::TextResourceContents {
+        uri: uri.to_string(),
+        mime_type: Some(JSON_MIME.to_string()),
+        text,
+    })
+}
+
+fn not_found(uri: &str) -> McpError {
+    McpError::resource_not_found("resource_not_found", Some(json!({ "uri": uri })))
+}
+
+fn tag_uri(name: &str) -> String {
+    format!("{}/{}", TAGS_URI, RawStr::new(name).percent_encode())
+}
+
+fn parse_id(uri: &str, raw: &str) -> Result<i32, McpError> {
+    raw.parse().map_err(|_| not_found(uri))
+}
+
+
+/// The fixed resources plus one view per list and per tag currently in the database.
+pub async fn list_resources(use_case: &TodolistUseCase) -> Result<Vec<Resource>, McpError> {
+    let mut resources = vec![
+        json_resource(TASKS_URI, "All tasks", "Every task with its tags, list and subtask roll-up"),
+        json_resource(STATS_URI, "Task statistics", "Total, done, undone, overdue and due today (UTC day) counters"),
+        json_resource(LISTS_URI, "Todo lists", "Every todo list (project)"),
+        json_resource(TAGS_URI, "Tags", "Every tag"),
+    ];
+
+    for list in use_case.get_all_lists().await? {
+        resources.push(json_resource(
+            format!("{}/{}", LISTS_URI, list.id),
+            format!("List: {}", list.name),
+            format!("The \"{}\" list with its task counts and tasks", list.name),
+        ));
+    }
+    for tag in use_case.get_all_tags().await? {
+        resources.push(json_resource(
+            tag_uri(&tag.name),
+            format!("Tag: {}", tag.name),
+            format!("Tasks tagged \"{}\"", tag.name),
+        ));
+    }
+
+    Ok(resources)
+}
+
+pub fn resource_templates() -> Vec<ResourceTemplate> {
+    vec![
+        json_template("todo://tasks/{id}", "Task by id", "A single task with its tags and subtask roll-up"),
+        json_template("todo://lists/{id}", "List by id", "A todo list with its task counts and tasks"),
+        json_template("todo://tags/{name}", "Tasks by tag", "Tasks carrying the tag, up to 200 of them"),
+    ]
+}
+
+pub async fn read_resource(use_case: &TodolistUseCase, uri: &str) -> Result<ResourceContents, McpError> {
+    match uri {
+        TASKS_URI => json_contents(uri, &use_case.get_all().await?),
+        STATS_URI => json_contents(uri, &use_case.get_stats().await?),
+        LISTS_URI => json_contents(uri, &use_case.get_all_lists().await?),
+        TAGS_URI => json_contents(uri, &use_case.get_all_tags().await?),
+        _ => {
+            if let Some(raw_id) = uri.strip_prefix("todo://tasks/") {
+                let task = use_case.get_by_id(parse_id(uri, raw_id)?).await?;
+                return json_contents(uri, &task);
+            }
+            if let Some(raw_id) = uri.strip_prefix("todo://lists/") {
+                let list_id = parse_id(uri, raw_id)?;
+                let view = json!({
+                    "list": use_case.get_list_by_id(list_id).await?,
+                    "counts": use_case.count_tasks_in_list(list_id).await?,
+                    "tasks": use_case.get_all_in_list(list_id).await?,
+                });
+                return json_contents(uri, &view);
+            }
+            if let Some(raw_name) = uri.strip_prefix("todo://tags/") {
+                let name = RawStr::new(raw_name).percent_decode().map_err(|_| not_found(uri))?;
+                let query = TodoQuery {
+                    tags: Some(vec![name.into_owned()]),
+                    limit: Some(MAX_PAGE_LIMIT),
+                    ..Default::default()
+                };
+                return json_contents(uri, &use_case.list_tasks(query).await?);
+            }
+            Err(not_found(uri))
+        }
+    }
+}
+
+
+/// Every resource whose content may differ after the change.
+pub fn affected_uris(event: &ResChangeEventDto) -> Vec<String> {
+    let mut uris = Vec::new();
+    match event.entity {
+        ChangeEntity::Task => {
+            uris.extend([TASKS_URI.to_string(), STATS_URI.to_string(), format!("{}/{}", TASKS_URI, event.entity_id)]);
+            uris.extend(event.parent_id.map(|parent_id| format!("{}/{}", TASKS_URI, parent_id)));
+        }
+        ChangeEntity::List => {
+            uris.push(LISTS_URI.to_string());
+            if event.kind == ChangeKind::Deleted {
+                uris.push(TASKS_URI.to_string());
+            }
+        }
+        ChangeEntity::Tag => uris.extend([TAGS_URI.to_string(), TASKS_URI.to_string()]),
+    }
+    uris.extend(event.list_ids.iter().map(|list_id| format!("{}/{}", LISTS_URI, list_id)));
+    uris.extend(event.tags.iter().map(|name| tag_uri(name)));
+    uris
+}
+
+/// `list_resources` enumerates lists and tags, so adding, renaming or removing one changes it.
+/// Tasks can create tags on the fly, hence task changes carrying tags count too.
+pub fn changes_resource_list(event: &ResChangeEventDto) -> bool {
+    match event.entity {
+        ChangeEntity::List | ChangeEntity::Tag => true,
+        ChangeEntity::Task => event.kind != ChangeKind::Deleted && !event.tags.is_empty(),
+    }
+}
\ No newline at end of file
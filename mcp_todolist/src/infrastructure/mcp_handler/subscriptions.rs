@@ -0,0 +1,73 @@
+use std::{collections::HashSet, sync::{Arc, Mutex, Weak}};
+use rmcp::{model::ResourceUpdatedNotificationParam, Peer, RoleServer};
+use tokio::{sync::broadcast::error::RecvError, task::JoinHandle};
+
+use crate::application::usecase::todo_usecase::TodolistUseCase;
+
+use super::resources;
+
+
+
+/// Resource URIs one MCP session subscribed to through `resources/subscribe`.
+#[derive(Clone, Default)]
+pub struct ResourceSubscriptions {
+    uris: Arc<Mutex<HashSet<String>>>,
+}
+
+impl ResourceSubscriptions {
+    pub fn subscribe(&self, uri: String) {
+        self.uris.lock().expect("subscription lock poisoned").insert(uri);
+    }
+
+    pub fn unsubscribe(&self, uri: &str) {
+        self.uris.lock().expect("subscription lock poisoned").remove(uri);
+    }
+
+    fn downgrade(&self) -> Weak<Mutex<HashSet<String>>> {
+        Arc::downgrade(&self.uris)
+    }
+}
+
+fn subscribed(uris: &Mutex<HashSet<String>>, affected: Vec<String>) -> Vec<String> {
+    let subscribed = uris.lock().expect("subscription lock poisoned");
+    let mut matching: Vec<String> = affected.into_iter().filter(|uri| subscribed.contains(uri)).collect();
+    matching.sort();
+    matching.dedup();
+    matching
+}
+
+
+/// Forward the change bus to one MCP client: `notifications/resources/updated` for the
+/// subscribed URIs a change touched and `notifications/resources/list_changed` when the
+/// set of resources itself moved.
+///
+/// The session owns the subscriptions, so the forwarder stops at the first change after
+/// the session (and with it the handler) is dropped. Send errors cannot be used for that:
+/// this rmcp revision reports every notification as failed, delivered or not.
+pub fn spawn_change_forwarder(use_case: &TodolistUseCase, peer: Peer<RoleServer>, subscriptions: &ResourceSubscriptions) -> JoinHandle<()> {
+    let mut changes = use_case.subscribe_changes();
+    let session = subscriptions.downgrade();
+
+    tokio::spawn(async move {
+        loop {
+            let event = match changes.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::warn!("MCP change forwarder lagged behind, {} events skipped", missed);
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            };
+            let Some(uris) = session.upgrade() else {
+                return;
+            };
+
+            if resources::changes_resource_list(&event) {
+                let _ = peer.notify_resource_list_changed().await;
+            }
+            for uri in subscribed(&uris, resources::affected_uris(&event)) {
+                let _ = peer.notify_resource_updated(ResourceUpdatedNotificationParam { uri }).await;
+            }
+        }
+    })
+}
\ No newline at end of file
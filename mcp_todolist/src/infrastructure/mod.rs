@@ -1,4 +1,5 @@
 pub mod mcp_handler;
 pub mod sqlite;
 pub mod http_handler;
-pub mod faring;
\ No newline at end of file
+pub mod faring;
+pub mod notification;
\ No newline at end of file
@@ -0,0 +1,226 @@
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use anyhow::Result;
+use hmac::{Hmac, Mac};
+use reqwest::{dns::{Addrs, Name, Resolve, Resolving}, header::CONTENT_TYPE, redirect};
+use sha2::Sha256;
+
+use crate::{application::webhook::{webhook_sender::WebhookSender, webhook_target::{check_webhook_url, is_public_ip}}, domain::dto::webhook_dto::WebhookDeliveryJob};
+
+
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub const EVENT_HEADER: &str = "X-Todolist-Event";
+pub const DELIVERY_HEADER: &str = "X-Todolist-Delivery";
+/// `sha256=` followed by the hex HMAC-SHA256 of the raw body, keyed with the secret of the webhook.
+pub const SIGNATURE_HEADER: &str = "X-Todolist-Signature-256";
+
+
+fn sign(secret: &str, body: &str) -> Result<String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())?;
+    mac.update(body.as_bytes());
+    Ok(format!("sha256={}", hex::encode(mac.finalize().into_bytes())))
+}
+
+
+/// Resolves host names as the system does, leaving out the addresses webhooks may not target.
+struct PublicAddressResolver;
+
+impl Resolve for PublicAddressResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} only resolves to loopback, link-local or private addresses", host).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+
+/// POSTs the deliveries of the registered webhooks, signed so receivers can check they come from here.
+///
+/// Unless `allow_private_targets` is set, deliveries never reach loopback, link-local or private
+/// addresses, whether the URL names one or its host resolves to one, and redirects are not followed.
+pub struct HttpWebhookSender {
+    client: reqwest::Client,
+    allow_private_targets: bool,
+}
+
+impl HttpWebhookSender {
+    pub fn new(allow_private_targets: bool) -> Result<Self> {
+        let mut builder = reqwest::Client::builder()
+            .timeout(DELIVERY_TIMEOUT)
+            .user_agent(concat!("mcp_todolist-webhooks/", env!("CARGO_PKG_VERSION")))
+            .redirect(redirect::Policy::none());
+        if !allow_private_targets {
+            builder = builder.dns_resolver(Arc::new(PublicAddressResolver));
+        }
+        Ok(Self { client: builder.build()?, allow_private_targets })
+    }
+}
+
+#[async_trait::async_trait]
+impl WebhookSender for HttpWebhookSender {
+    async fn send(&self, delivery: &WebhookDeliveryJob) -> Result<u16> {
+        // registered before the rule or while private targets were allowed
+        check_webhook_url(&delivery.url, self.allow_private_targets)?;
+
+        let response = self.client
+            .post(&delivery.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, &delivery.event)
+            .header(DELIVERY_HEADER, delivery.delivery_id.to_string())
+            .header(SIGNATURE_HEADER, sign(&delivery.secret, &delivery.payload)?)
+            .body(delivery.payload.clone())
+            .send()
+            .await?;
+        Ok(response.status().as_u16())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tokio::{io::{AsyncReadExt, AsyncWriteExt}, net::TcpListener, task::JoinHandle};
+
+    use super::*;
+
+
+    /// A request as the receiver saw it, header names lowercased.
+    struct Received {
+        request_line: String,
+        headers: HashMap<String, String>,
+        body: String,
+    }
+
+    /// Accept a single request on a local port and answer it with `status`.
+    async fn receiver(status: &'static str) -> (String, JoinHandle<Received>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut raw = Vec::new();
+            let mut buf = [0u8; 4096];
+            let head_end = loop {
+                let read = socket.read(&mut buf).await.unwrap();
+                assert!(read > 0, "connection closed before the headers ended");
+                raw.extend_from_slice(&buf[..read]);
+                if let Some(pos) = raw.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break pos;
+                }
+            };
+
+            let head = String::from_utf8(raw[..head_end].to_vec()).unwrap();
+            let mut lines = head.split("\r\n");
+            let request_line = lines.next().unwrap().to_string();
+            let headers: HashMap<String, String> = lines
+                .filter_map(|line| line.split_once(':'))
+                .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+                .collect();
+
+            let length: usize = headers.get("content-length").map_or(0, |v| v.parse().unwrap());
+            let mut body = raw[head_end + 4..].to_vec();
+            while body.len() < length {
+                let read = socket.read(&mut buf).await.unwrap();
+                assert!(read > 0, "connection closed before the body ended");
+                body.extend_from_slice(&buf[..read]);
+            }
+
+            let response = format!("HTTP/1.1 {}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n", status);
+            socket.write_all(response.as_bytes()).await.unwrap();
+            Received { request_line, headers, body: String::from_utf8(body).unwrap() }
+        });
+        (url, handle)
+    }
+
+    fn job(url: String) -> WebhookDeliveryJob {
+        WebhookDeliveryJob {
+            delivery_id: 42,
+            webhook_id: 7,
+            url,
+            secret: "0123456789abcdef0123".to_string(),
+            event: "task.created".to_string(),
+            payload: r#"{"event":"task.created","task":{"id":1,"title":"Water the plants"}}"#.to_string(),
+            attempts: 0,
+        }
+    }
+
+
+    #[test]
+    fn sign_matches_the_rfc_4231_vector() {
+        assert_eq!(
+            sign("Jefe", "what do ya want for nothing?").unwrap(),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[tokio::test]
+    async fn send_posts_the_signed_payload() {
+        let (url, received) = receiver("202 Accepted").await;
+        let delivery = job(url);
+
+        let status = HttpWebhookSender::new(true).unwrap().send(&delivery).await.unwrap();
+        assert_eq!(status, 202);
+
+        let request = received.await.unwrap();
+        assert_eq!(request.request_line, "POST /hook HTTP/1.1");
+        assert_eq!(request.body, delivery.payload);
+        assert_eq!(request.headers["content-type"], "application/json");
+        assert_eq!(request.headers["x-todolist-event"], "task.created");
+        assert_eq!(request.headers["x-todolist-delivery"], "42");
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(delivery.secret.as_bytes()).unwrap();
+        mac.update(request.body.as_bytes());
+        let signature = request.headers["x-todolist-signature-256"].strip_prefix("sha256=").unwrap();
+        mac.verify_slice(&hex::decode(signature).unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn send_returns_error_statuses() {
+        let (url, received) = receiver("503 Service Unavailable").await;
+
+        let status = HttpWebhookSender::new(true).unwrap().send(&job(url)).await.unwrap();
+        assert_eq!(status, 503);
+        received.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn send_fails_when_nobody_listens() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        drop(listener);
+
+        assert!(HttpWebhookSender::new(true).unwrap().send(&job(url)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn redirects_are_not_followed() {
+        let (url, received) = receiver("307 Temporary Redirect\r\nlocation: http://169.254.169.254/latest").await;
+
+        let status = HttpWebhookSender::new(true).unwrap().send(&job(url)).await.unwrap();
+        assert_eq!(status, 307);
+        received.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn private_targets_are_refused_before_connecting() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+
+        assert!(HttpWebhookSender::new(false).unwrap().send(&job(url)).await.is_err());
+        assert!(tokio::time::timeout(Duration::from_millis(200), listener.accept()).await.is_err(), "the sender connected");
+    }
+
+    #[tokio::test]
+    async fn host_names_resolving_to_private_addresses_are_left_out() {
+        let resolved = PublicAddressResolver.resolve("localhost".parse().unwrap()).await;
+        assert!(resolved.is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,26 @@
+use anyhow::Result;
+
+use crate::{application::reminder::notification_sink::NotificationSink, domain::dto::reminder_dto::ResReminderDto};
+
+
+
+/// Writes every reminder to the application log.
+pub struct LogSink;
+
+#[async_trait::async_trait]
+impl NotificationSink for LogSink {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    async fn notify(&self, reminder: &ResReminderDto) -> Result<()> {
+        tracing::info!(
+            "{} for task {} \"{}\" at {}",
+            reminder.kind.as_str(),
+            reminder.task.id,
+            reminder.task.title,
+            reminder.fire_at.to_rfc3339()
+        );
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+pub mod log_sink;
+pub mod webhook_sink;
+pub mod http_webhook_sender;
\ No newline at end of file
@@ -0,0 +1,39 @@
+use std::time::Duration;
+use anyhow::Result;
+
+use crate::{application::reminder::notification_sink::NotificationSink, domain::dto::reminder_dto::ResReminderDto};
+
+
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+
+/// POSTs every reminder as JSON (`ResReminderDto`) to a fixed URL, set with `REMINDER_WEBHOOK_URL`.
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookSink {
+    pub fn new(url: String) -> Result<Self> {
+        let client = reqwest::Client::builder().timeout(WEBHOOK_TIMEOUT).build()?;
+        Ok(Self { client, url })
+    }
+}
+
+#[async_trait::async_trait]
+impl NotificationSink for WebhookSink {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    async fn notify(&self, reminder: &ResReminderDto) -> Result<()> {
+        self.client
+            .post(&self.url)
+            .json(reminder)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -1,15 +1,51 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use diesel::{
+    connection::SimpleConnection,
     prelude::*,
-    r2d2::{ConnectionManager, Pool},
+    r2d2::{ConnectionManager, CustomizeConnection, Error as R2d2Error, Pool},
 };
+use diesel_migrations::MigrationHarness;
+
+use crate::{configuration::config::MigrationMode, domain::migration::MIGRATIONS};
 
 
 pub type SqlitePoolSquad = Pool<ConnectionManager<SqliteConnection>>;
 
-pub fn conn(database_url: &str) -> Result<SqlitePoolSquad> {
+
+/// SQLite keeps these settings per connection, so apply them to every pooled one.
+#[derive(Debug)]
+struct SqlitePragmas;
+
+impl CustomizeConnection<SqliteConnection, R2d2Error> for SqlitePragmas {
+    fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), R2d2Error> {
+        conn.batch_execute("PRAGMA foreign_keys = ON; PRAGMA busy_timeout = 5000;")
+            .map_err(R2d2Error::QueryError)
+    }
+}
+
+pub fn conn(database_url: &str, migration_mode: MigrationMode) -> Result<SqlitePoolSquad> {
     let manager = ConnectionManager::<SqliteConnection>::new(database_url);
-    let pool = Pool::builder().build(manager)?;
+    let pool = Pool::builder()
+        .connection_customizer(Box::new(SqlitePragmas))
+        .build(manager)?;
+
+    if migration_mode != MigrationMode::Skip {
+        run_migrations(&pool)?;
+    }
+
     Ok(pool)
 }
 
+pub fn run_migrations(pool: &SqlitePoolSquad) -> Result<()> {
+    let conn = &mut pool.get()?;
+
+    let applied = conn
+        .run_pending_migrations(MIGRATIONS)
+        .map_err(|e| anyhow!("Failed to run database migrations: {}", e))?;
+
+    for version in applied {
+        tracing::info!("Applied migration {}", version);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use chrono::Utc;
+use crate::{domain::{dto::{audit_dto::{ChangeOrigin, ReqTaskAuditDto, ResTaskAuditDto, TaskChangeDto}, event_dto::ChangeKind, todo_dto::ResEntryTodoDto, undo_dto::ResUndoneChangeDto}, entities::audit_entity::{EntryTaskAuditEntity, NewTaskAuditEntity}, error::domain_error::{DomainError, DomainResult}, repository::audit_repository::AuditRepository, schema::schema::{task_audit, todolist}}, infrastructure::sqlite::repository_impl::{todolist::TodoListSqliteRepository, undo::undo_session_changes}};
+use diesel::{dsl::insert_into, Connection, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl, SqliteConnection};
+
+
+
+fn to_snapshot(task: Option<impl serde::Serialize>) -> DomainResult<Option<String>> {
+    task
+        .map(|task| serde_json::to_string(&task))
+        .transpose()
+        .map_err(|e| DomainError::storage(format!("Failed to serialize audit snapshot: {}", e)))
+}
+
+/// Append an entry to the audit log, meant to run inside the transaction of the change when there is one.
+pub(super) fn insert_audit(conn: &mut SqliteConnection, dto: ReqTaskAuditDto) -> DomainResult<EntryTaskAuditEntity> {
+    let entity = NewTaskAuditEntity {
+        todo_id: dto.task_id as i32,
+        action: dto.action.as_str().to_string(),
+        before_snapshot: to_snapshot(dto.before)?,
+        after_snapshot: to_snapshot(dto.after)?,
+        origin: dto.origin.source.as_str().to_string(),
+        client_name: dto.origin.client_name,
+        session_id: dto.origin.session_id,
+        operation_id: dto.origin.operation_id,
+        changed_at: Utc::now().naive_utc(),
+    };
+
+    Ok(insert_into(task_audit::table)
+        .values(entity)
+        .returning(task_audit::all_columns)
+        .get_result(conn)?)
+}
+
+/// Audit a task change inside its transaction, so a failing write rolls the change back with it.
+///
+/// `task` is the task after the change, or as it was before being deleted.
+pub(super) fn record_change(conn: &mut SqliteConnection, origin: &ChangeOrigin, kind: ChangeKind, task: ResEntryTodoDto, previous: Option<ResEntryTodoDto>) -> DomainResult<TaskChangeDto> {
+    let (before, after) = match kind {
+        ChangeKind::Created => (None, Some(&task)),
+        ChangeKind::Deleted => (Some(&task), None),
+        _ => (previous.as_ref(), Some(&task)),
+    };
+    insert_audit(conn, ReqTaskAuditDto {
+        task_id: task.id,
+        action: kind,
+        before: before.cloned(),
+        after: after.cloned(),
+        origin: origin.clone(),
+    })?;
+
+    Ok(TaskChangeDto { kind, task, previous })
+}
+
+
+#[async_trait::async_trait]
+impl AuditRepository for TodoListSqliteRepository {
+
+    async fn get_task_history(&self, task_id: i32) -> DomainResult<Vec<ResTaskAuditDto>> {
+        let conn = &mut self.db_pool.get()?;
+
+        let entries: Vec<EntryTaskAuditEntity> = task_audit::table
+            .filter(task_audit::todo_id.eq(task_id))
+            .order(task_audit::id.asc())
+            .load(conn)?;
+
+        // tasks created before the audit log existed have no entries yet
+        if entries.is_empty() {
+            todolist::table
+                .filter(todolist::id.eq(task_id))
+                .select(todolist::id)
+                .first::<i32>(conn)
+                .optional()?
+                .ok_or_else(|| DomainError::not_found(format!("Todo with id {} not found", task_id)))?;
+        }
+
+        entries.into_iter().map(ResTaskAuditDto::try_from).collect()
+    }
+
+    async fn undo_session(&self, session_id: String, operations: u32, origin: ChangeOrigin) -> DomainResult<Vec<ResUndoneChangeDto>> {
+        let conn = &mut self.db_pool.get()?;
+
+        conn.transaction(|conn| undo_session_changes(conn, &session_id, operations, origin))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,84 @@
+use crate::{domain::{dto::list_dto::{ReqCreateListDto, ReqUpdateListDto, ResEntryListDto}, entities::list_entity::{EntryListEntity, NewListEntity, UpdateListEntity}, error::domain_error::{DomainError, DomainResult}, repository::list_repository::ListRepository, schema::schema::lists}, infrastructure::sqlite::repository_impl::todolist::TodoListSqliteRepository};
+use diesel::{dsl::{exists, insert_into, select}, update, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl, SqliteConnection};
+
+
+
+/// Fail with `NotFound` unless the list exists, so a bad `list_id` is not reported as a conflict.
+pub(super) fn ensure_list_exists(conn: &mut SqliteConnection, list_id: i32) -> DomainResult<()> {
+    let found: bool = select(exists(lists::table.filter(lists::id.eq(list_id))))
+        .get_result(conn)?;
+
+    if !found {
+        return Err(DomainError::not_found(format!("List with id {} not found", list_id)));
+    }
+    Ok(())
+}
+
+
+#[async_trait::async_trait]
+impl ListRepository for TodoListSqliteRepository {
+
+    async fn create_list(&self, dto: ReqCreateListDto) -> DomainResult<ResEntryListDto> {
+        let conn = &mut self.db_pool.get()?;
+
+        let entity: NewListEntity = dto.into();
+
+        let inserted: EntryListEntity = insert_into(lists::table)
+            .values(entity)
+            .returning(lists::all_columns)
+            .get_result(conn)?;
+
+        Ok(inserted.into())
+    }
+
+    async fn update_list(&self, list_id: i32, dto: ReqUpdateListDto) -> DomainResult<ResEntryListDto> {
+        let conn = &mut self.db_pool.get()?;
+
+        let the_entity: UpdateListEntity = dto.into();
+
+        let updated: EntryListEntity = update(lists::table.filter(lists::id.eq(list_id)))
+            .set(the_entity)
+            .returning(lists::all_columns)
+            .get_result(conn)
+            .optional()?
+            .ok_or_else(|| DomainError::not_found(format!("No list found with id {}", list_id)))?;
+
+        Ok(updated.into())
+    }
+
+    async fn get_list_by_id(&self, list_id: i32) -> DomainResult<ResEntryListDto> {
+        let conn = &mut self.db_pool.get()?;
+
+        let list: EntryListEntity = lists::table
+            .filter(lists::id.eq(list_id))
+            .first(conn)
+            .optional()?
+            .ok_or_else(|| DomainError::not_found(format!("List with id {} not found", list_id)))?;
+
+        Ok(list.into())
+    }
+
+    async fn get_all_lists(&self) -> DomainResult<Vec<ResEntryListDto>> {
+        let conn = &mut self.db_pool.get()?;
+
+        let all_lists: Vec<EntryListEntity> = lists::table
+            .order(lists::name.asc())
+            .load(conn)?;
+
+        Ok(all_lists.into_iter().map(|list| list.into()).collect())
+    }
+
+    async fn delete_list(&self, list_id: i32) -> DomainResult<()> {
+        let conn = &mut self.db_pool.get()?;
+
+        // tasks of the list get `list_id = NULL` through ON DELETE SET NULL
+        let deleted_rows = diesel::delete(lists::table.filter(lists::id.eq(list_id)))
+            .execute(conn)?;
+
+        if deleted_rows == 0 {
+            return Err(DomainError::not_found(format!("No list found with id {}", list_id)));
+        }
+
+        Ok(())
+    }
+}
\ No newline at end of file
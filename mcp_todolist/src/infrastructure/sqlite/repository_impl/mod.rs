@@ -1 +1,12 @@
-pub mod todolist;
\ No newline at end of file
+pub mod todolist;
+pub mod tag;
+pub mod list;
+pub mod subtask;
+pub mod trash;
+pub mod audit;
+pub mod undo;
+pub mod search;
+pub mod recurrence;
+pub mod reminder;
+pub mod webhook;
+pub mod transfer;
\ No newline at end of file
@@ -0,0 +1,159 @@
+use chrono::Utc;
+use crate::{domain::{dto::{audit_dto::{ChangeOrigin, TaskChangeDto}, event_dto::ChangeKind, recurrence_dto::{ReqRecurrenceRuleDto, ResRecurrenceDto}, todo_dto::ReqCreateTodoDto}, entities::{recurrence_entity::{EntryRecurrenceEntity, NewRecurrenceEntity}, todo_entity::{EntryTodoEntity, NewTodoEntity}}, error::domain_error::{DomainError, DomainResult}, repository::recurrence_repository::RecurrenceRepository, schema::schema::{recurrences, todolist}}, infrastructure::sqlite::repository_impl::{audit::record_change, tag::{attach_tag_names, tag_names_by_todo}, todolist::{insert_task, into_res_dto, into_res_dtos, load_todo, TodoListSqliteRepository}}};
+use diesel::{dsl::{insert_into, max}, update, Connection, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl, SqliteConnection};
+
+
+
+fn load_recurrence(conn: &mut SqliteConnection, recurrence_id: i32) -> DomainResult<EntryRecurrenceEntity> {
+    recurrences::table
+        .filter(recurrences::id.eq(recurrence_id))
+        .first(conn)
+        .optional()?
+        .ok_or_else(|| DomainError::not_found(format!("Recurrence with id {} not found", recurrence_id)))
+}
+
+/// The series with its live occurrences and when the next one is due.
+fn into_res_recurrence(conn: &mut SqliteConnection, series: EntryRecurrenceEntity) -> DomainResult<ResRecurrenceDto> {
+    let tasks: Vec<EntryTodoEntity> = todolist::table
+        .filter(todolist::recurrence_id.eq(series.id))
+        .filter(todolist::deleted_at.is_null())
+        .order(todolist::occurrence.asc())
+        .load(conn)?;
+
+    // trashed occurrences keep their number, the next one follows them
+    let last_occurrence: Option<i32> = todolist::table
+        .filter(todolist::recurrence_id.eq(series.id))
+        .select(max(todolist::occurrence))
+        .first(conn)?;
+    let last_occurrence = last_occurrence.unwrap_or(0);
+    let next_due = match series.stopped_at {
+        Some(_) => None,
+        None => series.occurrence_due(last_occurrence + 1)?,
+    };
+
+    Ok(ResRecurrenceDto {
+        id: series.id as u32,
+        frequency: series.frequency()?,
+        interval: series.interval as u32,
+        starts_at: series.starts_at.and_utc(),
+        until: series.until.map(|until| until.and_utc()),
+        count: series.max_occurrences.map(|count| count as u32),
+        stopped_at: series.stopped_at.map(|stopped| stopped.and_utc()),
+        next_due: next_due.map(|due| due.and_utc()),
+        occurrences: into_res_dtos(conn, tasks)?,
+    })
+}
+
+
+/// Create the occurrence following a completed task of a series, meant to run in the transaction completing it
+/// so the series cannot stop silently, recorded under the same origin so undoing the completion removes it too.
+/// `None` when the task is not done, not recurring, already followed or the series is over.
+pub(super) fn spawn_next_occurrence(conn: &mut SqliteConnection, task_id: i32, origin: &ChangeOrigin) -> DomainResult<Option<TaskChangeDto>> {
+    let done = load_todo(conn, task_id)?;
+    let (Some(recurrence_id), Some(occurrence)) = (done.recurrence_id, done.occurrence) else {
+        return Ok(None);
+    };
+    if !done.is_done {
+        return Ok(None);
+    }
+
+    let series = load_recurrence(conn, recurrence_id)?;
+    if series.stopped_at.is_some() {
+        return Ok(None);
+    }
+
+    // only the latest occurrence spawns, so completing a task again after reopening it adds nothing.
+    // trashed occurrences count too, restoring one must not leave two tasks with the same number
+    let latest: Option<i32> = todolist::table
+        .filter(todolist::recurrence_id.eq(recurrence_id))
+        .select(max(todolist::occurrence))
+        .first(conn)?;
+    if latest.is_some_and(|latest| latest > occurrence) {
+        return Ok(None);
+    }
+
+    let next = occurrence + 1;
+    let Some(due) = series.occurrence_due(next)? else {
+        return Ok(None);
+    };
+
+    let now = Utc::now().naive_utc();
+    let entity = NewTodoEntity {
+        title: done.title,
+        description: done.description,
+        is_done: false,
+        priority: done.priority,
+        due_date: Some(due),
+        list_id: done.list_id,
+        parent_id: None,
+        position: 0,
+        // the reminder keeps its distance to the due date
+        remind_at: done.remind_at.zip(done.due_date).map(|(remind, previous_due)| due - (previous_due - remind)),
+    };
+    let spawned: EntryTodoEntity = insert_into(todolist::table)
+        .values((
+            entity,
+            todolist::created_at.eq(now),
+            todolist::updated_at.eq(now),
+            todolist::recurrence_id.eq(recurrence_id),
+            todolist::occurrence.eq(next),
+        ))
+        .returning(todolist::all_columns)
+        .get_result(conn)?;
+
+    // the next occurrence carries the same tags
+    let tag_names = tag_names_by_todo(conn, &[task_id])?.remove(&task_id).unwrap_or_default();
+    attach_tag_names(conn, spawned.id, &tag_names)?;
+
+    let spawned = into_res_dto(conn, spawned)?;
+    record_change(conn, origin, ChangeKind::Created, spawned, None).map(Some)
+}
+
+#[async_trait::async_trait]
+impl RecurrenceRepository for TodoListSqliteRepository {
+
+    async fn create_recurring_task(&self, task: ReqCreateTodoDto, rule: ReqRecurrenceRuleDto, origin: ChangeOrigin) -> DomainResult<(ResRecurrenceDto, TaskChangeDto)> {
+        let conn = &mut self.db_pool.get()?;
+
+        conn.transaction(|conn| {
+            let now = Utc::now().naive_utc();
+            let starts_at = task.due_date.map_or(now, |due| due.naive_utc());
+
+            let series: EntryRecurrenceEntity = insert_into(recurrences::table)
+                .values(NewRecurrenceEntity::new(&rule, starts_at, now))
+                .returning(recurrences::all_columns)
+                .get_result(conn)?;
+
+            let first = insert_task(conn, task)?;
+            let first: EntryTodoEntity = update(todolist::table.filter(todolist::id.eq(first.id as i32)))
+                .set((todolist::recurrence_id.eq(series.id), todolist::occurrence.eq(1)))
+                .returning(todolist::all_columns)
+                .get_result(conn)?;
+            let first = into_res_dto(conn, first)?;
+            let change = record_change(conn, &origin, ChangeKind::Created, first, None)?;
+
+            Ok((into_res_recurrence(conn, series)?, change))
+        })
+    }
+
+    async fn get_recurrence(&self, recurrence_id: i32) -> DomainResult<ResRecurrenceDto> {
+        let conn = &mut self.db_pool.get()?;
+
+        let series = load_recurrence(conn, recurrence_id)?;
+        into_res_recurrence(conn, series)
+    }
+
+    async fn stop_recurrence(&self, recurrence_id: i32) -> DomainResult<ResRecurrenceDto> {
+        let conn = &mut self.db_pool.get()?;
+
+        conn.transaction(|conn| {
+            // stopping twice keeps the first stop date
+            update(recurrences::table.filter(recurrences::id.eq(recurrence_id)).filter(recurrences::stopped_at.is_null()))
+                .set(recurrences::stopped_at.eq(Utc::now().naive_utc()))
+                .execute(conn)?;
+
+            let series = load_recurrence(conn, recurrence_id)?;
+            into_res_recurrence(conn, series)
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use crate::{domain::{dto::reminder_dto::{ReminderKind, ResReminderDto}, entities::{reminder_entity::NewFiredReminderEntity, todo_entity::EntryTodoEntity}, error::domain_error::DomainResult, repository::reminder_repository::ReminderRepository, schema::schema::fired_reminders}, infrastructure::sqlite::repository_impl::todolist::{into_res_dtos, TodoListSqliteRepository}};
+use diesel::{dsl::insert_or_ignore_into, Connection, ExpressionMethods, QueryDsl, RunQueryDsl};
+
+
+
+#[async_trait::async_trait]
+impl ReminderRepository for TodoListSqliteRepository {
+
+    async fn claim_due_reminders(&self, since: DateTime<Utc>, now: DateTime<Utc>) -> DomainResult<Vec<ResReminderDto>> {
+        use crate::domain::schema::schema::todolist::dsl::*;
+
+        let conn = &mut self.db_pool.get()?;
+        let (since, now) = (since.naive_utc(), now.naive_utc());
+
+        conn.transaction(|conn| {
+            let open_tasks = || todolist.filter(deleted_at.is_null()).filter(is_done.eq(false));
+            let reminders: Vec<EntryTodoEntity> = open_tasks()
+                .filter(remind_at.between(since, now))
+                .load(conn)?;
+            let due: Vec<EntryTodoEntity> = open_tasks()
+                .filter(due_date.between(since, now))
+                .load(conn)?;
+
+            let candidates = reminders
+                .into_iter()
+                .filter_map(|task| task.remind_at.map(|fire_at| (ReminderKind::Reminder, fire_at, task)))
+                .chain(due.into_iter().filter_map(|task| task.due_date.map(|fire_at| (ReminderKind::Due, fire_at, task))));
+
+            // the unique index on (todo_id, kind, fire_at) skips what was sent before a restart
+            let mut claimed: Vec<(ReminderKind, NaiveDateTime, i32)> = Vec::new();
+            let mut tasks: Vec<EntryTodoEntity> = Vec::new();
+            for (kind, fire_at, task) in candidates {
+                let inserted = insert_or_ignore_into(fired_reminders::table)
+                    .values(NewFiredReminderEntity {
+                        todo_id: task.id,
+                        kind: kind.as_str().to_string(),
+                        fire_at,
+                        fired_at: now,
+                    })
+                    .execute(conn)?;
+                if inserted == 0 {
+                    continue;
+                }
+                claimed.push((kind, fire_at, task.id));
+                if !tasks.iter().any(|known| known.id == task.id) {
+                    tasks.push(task);
+                }
+            }
+
+            let by_id: HashMap<u32, _> = into_res_dtos(conn, tasks)?
+                .into_iter()
+                .map(|task| (task.id, task))
+                .collect();
+            let mut fired: Vec<ResReminderDto> = claimed
+                .into_iter()
+                .filter_map(|(kind, fire_at, task_id)| {
+                    by_id.get(&(task_id as u32)).map(|task| ResReminderDto {
+                        kind,
+                        fire_at: fire_at.and_utc(),
+                        fired_at: now.and_utc(),
+                        task: task.clone(),
+                    })
+                })
+                .collect();
+            fired.sort_by_key(|reminder| reminder.fire_at);
+            Ok(fired)
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+use crate::{domain::{dto::search_dto::ResSearchHitDto, entities::{search_entity::SearchMatchEntity, todo_entity::EntryTodoEntity}, error::domain_error::{DomainError, DomainResult}, repository::search_repository::SearchRepository, schema::schema::todolist}, infrastructure::sqlite::repository_impl::todolist::{into_res_dtos, TodoListSqliteRepository}};
+use diesel::{sql_query, sql_types::{Integer, Text}, ExpressionMethods, QueryDsl, RunQueryDsl};
+
+
+
+/// Words shorter than a trigram cannot be looked up in the index.
+const MIN_WORD_CHARS: usize = 3;
+
+/// Turn free text into an FTS5 expression where every word is a quoted phrase,
+/// so operators and punctuation typed by the user are matched literally.
+fn match_expression(query: &str) -> Option<String> {
+    let phrases: Vec<String> = query
+        .split_whitespace()
+        .filter(|word| word.chars().count() >= MIN_WORD_CHARS)
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect();
+    (!phrases.is_empty()).then(|| phrases.join(" "))
+}
+
+
+#[async_trait::async_trait]
+impl SearchRepository for TodoListSqliteRepository {
+
+    async fn search_tasks(&self, query: String, limit: u32) -> DomainResult<Vec<ResSearchHitDto>> {
+        let expression = match_expression(&query).ok_or_else(|| {
+            DomainError::validation(format!("Search words need at least {} characters", MIN_WORD_CHARS))
+        })?;
+
+        let conn = &mut self.db_pool.get()?;
+
+        // a title match weighs ten times a description match,
+        // snippet sizes count trigrams so 64 is roughly 64 characters
+        let matches: Vec<SearchMatchEntity> = sql_query(
+            "SELECT todo_search.rowid AS todo_id, \
+                    bm25(todo_search, 10.0, 1.0) AS rank, \
+                    snippet(todo_search, 0, '**', '**', '…', 64) AS title_snippet, \
+                    snippet(todo_search, 1, '**', '**', '…', 64) AS description_snippet \
+             FROM todo_search \
+             JOIN todolist ON todolist.id = todo_search.rowid \
+             WHERE todo_search MATCH ? AND todolist.deleted_at IS NULL \
+             ORDER BY rank \
+             LIMIT ?"
+        )
+        .bind::<Text, _>(expression)
+        .bind::<Integer, _>(limit as i32)
+        .load(conn)?;
+
+        let todo_ids: Vec<i32> = matches.iter().map(|found| found.todo_id).collect();
+        let todos: Vec<EntryTodoEntity> = todolist::table
+            .filter(todolist::id.eq_any(&todo_ids))
+            .load(conn)?;
+        let mut tasks: HashMap<u32, _> = into_res_dtos(conn, todos)?
+            .into_iter()
+            .map(|task| (task.id, task))
+            .collect();
+
+        Ok(matches
+            .into_iter()
+            .filter_map(|found| {
+                tasks.remove(&(found.todo_id as u32)).map(|task| ResSearchHitDto {
+                    score: -found.rank,
+                    title_snippet: found.title_snippet,
+                    description_snippet: found.description_snippet,
+                    task,
+                })
+            })
+            .collect())
+    }
+}
\ No newline at end of file
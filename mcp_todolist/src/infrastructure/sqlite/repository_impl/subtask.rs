@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use chrono::Utc;
+use crate::{domain::{dto::{audit_dto::{ChangeOrigin, TaskChangeDto}, event_dto::ChangeKind, subtask_dto::ReqCreateSubtaskDto, todo_dto::ResEntryTodoDto}, entities::todo_entity::{EntryTodoEntity, NewTodoEntity}, error::domain_error::{DomainError, DomainResult}, repository::subtask_repository::SubtaskRepository, schema::schema::todolist}, infrastructure::sqlite::repository_impl::{audit::record_change, todolist::{into_res_dto, into_res_dtos, load_todo, TodoListSqliteRepository}}};
+use diesel::{dsl::{insert_into, max}, update, Connection, ExpressionMethods, QueryDsl, RunQueryDsl, SqliteConnection};
+
+
+
+/// Count `(done, total)` subtasks of every given task, keyed by parent id.
+/// Tasks without subtasks are left out of the map.
+pub(super) fn subtask_progress_by_parent(conn: &mut SqliteConnection, todo_ids: &[i32]) -> DomainResult<HashMap<i32, (i32, i32)>> {
+    let rows: Vec<(Option<i32>, bool)> = todolist::table
+        .filter(todolist::parent_id.eq_any(todo_ids))
+        .filter(todolist::deleted_at.is_null())
+        .select((todolist::parent_id, todolist::is_done))
+        .load(conn)?;
+
+    let mut progress: HashMap<i32, (i32, i32)> = HashMap::new();
+    for (parent, done) in rows {
+        let Some(parent) = parent else { continue };
+        let entry = progress.entry(parent).or_default();
+        if done {
+            entry.0 += 1;
+        }
+        entry.1 += 1;
+    }
+    Ok(progress)
+}
+
+fn load_subtasks(conn: &mut SqliteConnection, the_parent_id: i32) -> DomainResult<Vec<EntryTodoEntity>> {
+    use crate::domain::schema::schema::todolist::dsl::*;
+
+    Ok(todolist
+        .filter(parent_id.eq(the_parent_id))
+        .filter(deleted_at.is_null())
+        .order((position.asc(), id.asc()))
+        .load(conn)?)
+}
+
+
+#[async_trait::async_trait]
+impl SubtaskRepository for TodoListSqliteRepository {
+
+    async fn add_subtask(&self, dto: ReqCreateSubtaskDto, origin: ChangeOrigin) -> DomainResult<TaskChangeDto> {
+        let conn = &mut self.db_pool.get()?;
+
+        conn.transaction(|conn| {
+            let parent = load_todo(conn, dto.parent_id)?;
+            if parent.parent_id.is_some() {
+                return Err(DomainError::validation(format!("Task {} is already a subtask, subtasks cannot be nested", parent.id)));
+            }
+
+            // trashed subtasks count too, so restoring one never collides with a newer step
+            let last_position: Option<i32> = todolist::table
+                .filter(todolist::parent_id.eq(parent.id))
+                .select(max(todolist::position))
+                .first(conn)?;
+
+            let entity = NewTodoEntity {
+                title: dto.title.trim().to_string(),
+                description: dto.description.unwrap_or_default(),
+                is_done: dto.is_done,
+                priority: parent.priority,
+                due_date: None,
+                list_id: parent.list_id,
+                parent_id: Some(parent.id),
+                position: last_position.map_or(0, |last| last + 1),
+                remind_at: None,
+            };
+
+            let now = Utc::now().naive_utc();
+            let inserted: EntryTodoEntity = insert_into(todolist::table)
+                .values((entity, todolist::created_at.eq(now), todolist::updated_at.eq(now)))
+                .returning(todolist::all_columns)
+                .get_result(conn)?;
+
+            let created = into_res_dto(conn, inserted)?;
+            record_change(conn, &origin, ChangeKind::Created, created, None)
+        })
+    }
+
+    async fn get_subtasks(&self, parent_id: i32) -> DomainResult<Vec<ResEntryTodoDto>> {
+        let conn = &mut self.db_pool.get()?;
+
+        load_todo(conn, parent_id)?;
+        let subtasks = load_subtasks(conn, parent_id)?;
+
+        into_res_dtos(conn, subtasks)
+    }
+
+    async fn reorder_subtasks(&self, parent_id: i32, subtask_ids: Vec<i32>, origin: ChangeOrigin) -> DomainResult<(Vec<ResEntryTodoDto>, Vec<TaskChangeDto>)> {
+        let conn = &mut self.db_pool.get()?;
+
+        conn.transaction(|conn| {
+            load_todo(conn, parent_id)?;
+
+            let subtasks = load_subtasks(conn, parent_id)?;
+            let previous = into_res_dtos(conn, subtasks)?;
+            let mut current: Vec<i32> = previous.iter().map(|subtask| subtask.id as i32).collect();
+            let mut requested = subtask_ids.clone();
+            current.sort_unstable();
+            requested.sort_unstable();
+            if current != requested {
+                return Err(DomainError::validation(format!(
+                    "subtask_ids must list every subtask of task {} exactly once, expected {:?}",
+                    parent_id, current
+                )));
+            }
+
+            for (new_position, subtask_id) in subtask_ids.iter().enumerate() {
+                update(todolist::table.filter(todolist::id.eq(subtask_id)))
+                    .set((todolist::position.eq(new_position as i32), todolist::version.eq(todolist::version + 1), todolist::updated_at.eq(Utc::now().naive_utc())))
+                    .execute(conn)?;
+            }
+
+            let subtasks = load_subtasks(conn, parent_id)?;
+            let reordered = into_res_dtos(conn, subtasks)?;
+
+            // only the moved subtasks get an audit entry, the parent row itself is unchanged
+            let mut changes = Vec::new();
+            for subtask in &reordered {
+                let before = previous.iter().find(|old| old.id == subtask.id);
+                if let Some(before) = before.filter(|old| old.position != subtask.position) {
+                    changes.push(record_change(conn, &origin, ChangeKind::Updated, subtask.clone(), Some(before.clone()))?);
+                }
+            }
+            Ok((reordered, changes))
+        })
+    }
+
+    async fn set_subtask_done(&self, subtask_id: i32, is_done: bool, origin: ChangeOrigin) -> DomainResult<(ResEntryTodoDto, TaskChangeDto)> {
+        let conn = &mut self.db_pool.get()?;
+
+        conn.transaction(|conn| {
+            let subtask = load_todo(conn, subtask_id)?;
+            let Some(parent_id) = subtask.parent_id else {
+                return Err(DomainError::validation(format!("Task {} is not a subtask", subtask_id)));
+            };
+            let previous = into_res_dto(conn, subtask)?;
+
+            update(todolist::table.filter(todolist::id.eq(subtask_id)))
+                .set((todolist::is_done.eq(is_done), todolist::version.eq(todolist::version + 1), todolist::updated_at.eq(Utc::now().naive_utc())))
+                .execute(conn)?;
+
+            let subtask = load_todo(conn, subtask_id)?;
+            let subtask = into_res_dto(conn, subtask)?;
+            let kind = if is_done && !previous.is_done { ChangeKind::Completed } else { ChangeKind::Updated };
+            let change = record_change(conn, &origin, kind, subtask, Some(previous))?;
+
+            let parent = load_todo(conn, parent_id)?;
+            Ok((into_res_dto(conn, parent)?, change))
+        })
+    }
+}
\ No newline at end of file
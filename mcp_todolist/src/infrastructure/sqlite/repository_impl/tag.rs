@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use chrono::Utc;
+use crate::{domain::{dto::{audit_dto::{ChangeOrigin, TaskChangeDto}, event_dto::ChangeKind, tag_dto::{ReqCreateTagDto, ReqUpdateTagDto, ResEntryTagDto}}, entities::{tag_entity::{EntryTagEntity, NewTagEntity, TodoTagEntity, UpdateTagEntity}, todo_entity::EntryTodoEntity}, error::domain_error::{DomainError, DomainResult}, repository::tag_repository::TagRepository, schema::schema::{tags, todo_tags, todolist}}, infrastructure::sqlite::repository_impl::{audit::record_change, todolist::{into_res_dto, load_todo, TodoListSqliteRepository}}};
+use diesel::{dsl::{insert_into, insert_or_ignore_into}, update, Connection, ExpressionMethods, OptionalExtension, QueryDsl, SelectableHelper, RunQueryDsl, SqliteConnection};
+
+
+
+/// Load the tag names of every given task, keyed by task id.
+pub(super) fn tag_names_by_todo(conn: &mut SqliteConnection, todo_ids: &[i32]) -> DomainResult<HashMap<i32, Vec<String>>> {
+    let rows: Vec<(i32, String)> = todo_tags::table
+        .inner_join(tags::table)
+        .filter(todo_tags::todo_id.eq_any(todo_ids))
+        .select((todo_tags::todo_id, tags::name))
+        .order(tags::name.asc())
+        .load(conn)?;
+
+    let mut names: HashMap<i32, Vec<String>> = HashMap::new();
+    for (todo_id, name) in rows {
+        names.entry(todo_id).or_default().push(name);
+    }
+    Ok(names)
+}
+
+/// Attach tags by name to a task, creating the missing tags first.
+pub(super) fn attach_tag_names(conn: &mut SqliteConnection, task_id: i32, names: &[String]) -> DomainResult<()> {
+    if names.is_empty() {
+        return Ok(());
+    }
+
+    let new_tags: Vec<NewTagEntity> = names
+        .iter()
+        .map(|name| NewTagEntity { name: name.clone(), color: None })
+        .collect();
+    insert_or_ignore_into(tags::table)
+        .values(new_tags)
+        .execute(conn)?;
+
+    let tag_ids: Vec<i32> = tags::table
+        .filter(tags::name.eq_any(names))
+        .select(tags::id)
+        .load(conn)?;
+    let links: Vec<TodoTagEntity> = tag_ids
+        .into_iter()
+        .map(|tag_id| TodoTagEntity { todo_id: task_id, tag_id })
+        .collect();
+    insert_or_ignore_into(todo_tags::table)
+        .values(links)
+        .execute(conn)?;
+
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl TagRepository for TodoListSqliteRepository {
+
+    async fn create_tag(&self, dto: ReqCreateTagDto) -> DomainResult<ResEntryTagDto> {
+        let conn = &mut self.db_pool.get()?;
+
+        let entity: NewTagEntity = dto.into();
+
+        let inserted: EntryTagEntity = insert_into(tags::table)
+            .values(entity)
+            .returning(tags::all_columns)
+            .get_result(conn)?;
+
+        Ok(inserted.into())
+    }
+
+    async fn update_tag(&self, tag_id: i32, dto: ReqUpdateTagDto) -> DomainResult<ResEntryTagDto> {
+        let conn = &mut self.db_pool.get()?;
+
+        let the_entity: UpdateTagEntity = dto.into();
+
+        let updated: EntryTagEntity = update(tags::table.filter(tags::id.eq(tag_id)))
+            .set(the_entity)
+            .returning(tags::all_columns)
+            .get_result(conn)
+            .optional()?
+            .ok_or_else(|| DomainError::not_found(format!("No tag found with id {}", tag_id)))?;
+
+        Ok(updated.into())
+    }
+
+    async fn get_all_tags(&self) -> DomainResult<Vec<ResEntryTagDto>> {
+        let conn = &mut self.db_pool.get()?;
+
+        let all_tags: Vec<EntryTagEntity> = tags::table
+            .order(tags::name.asc())
+            .load(conn)?;
+
+        Ok(all_tags.into_iter().map(|tag| tag.into()).collect())
+    }
+
+    async fn delete_tag(&self, tag_id: i32) -> DomainResult<()> {
+        let conn = &mut self.db_pool.get()?;
+
+        // links in todo_tags go away through ON DELETE CASCADE
+        let deleted_rows = diesel::delete(tags::table.filter(tags::id.eq(tag_id)))
+            .execute(conn)?;
+
+        if deleted_rows == 0 {
+            return Err(DomainError::not_found(format!("No tag found with id {}", tag_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn attach_tags(&self, task_id: i32, names: Vec<String>, origin: ChangeOrigin) -> DomainResult<TaskChangeDto> {
+        let conn = &mut self.db_pool.get()?;
+
+        conn.transaction(|conn| {
+            let todo = load_todo(conn, task_id)?;
+            let previous = into_res_dto(conn, todo)?;
+            attach_tag_names(conn, task_id, &names)?;
+            let todo = bump_version(conn, task_id)?;
+            let updated = into_res_dto(conn, todo)?;
+            record_change(conn, &origin, ChangeKind::Updated, updated, Some(previous))
+        })
+    }
+
+    async fn detach_tags(&self, task_id: i32, names: Vec<String>, origin: ChangeOrigin) -> DomainResult<TaskChangeDto> {
+        let conn = &mut self.db_pool.get()?;
+
+        conn.transaction(|conn| {
+            let todo = load_todo(conn, task_id)?;
+            // the previous snapshot carries the detached tags into the change scope
+            let previous = into_res_dto(conn, todo)?;
+
+            let tag_ids = tags::table
+                .filter(tags::name.eq_any(&names))
+                .select(tags::id);
+            diesel::delete(
+                todo_tags::table
+                    .filter(todo_tags::todo_id.eq(task_id))
+                    .filter(todo_tags::tag_id.eq_any(tag_ids))
+            )
+            .execute(conn)?;
+
+            let todo = bump_version(conn, task_id)?;
+            let updated = into_res_dto(conn, todo)?;
+            record_change(conn, &origin, ChangeKind::Updated, updated, Some(previous))
+        })
+    }
+}
+
+
+
+/// Tags are part of the task, so changing them moves the task to a new version.
+fn bump_version(conn: &mut SqliteConnection, task_id: i32) -> DomainResult<EntryTodoEntity> {
+    let todo = diesel::update(todolist::table.filter(todolist::id.eq(task_id)))
+        .set((todolist::version.eq(todolist::version + 1), todolist::updated_at.eq(Utc::now().naive_utc())))
+        .returning(EntryTodoEntity::as_returning())
+        .get_result(conn)?;
+    Ok(todo)
+}
\ No newline at end of file
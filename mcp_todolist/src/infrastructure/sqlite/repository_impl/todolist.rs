@@ -1,11 +1,11 @@
-use std::sync::Arc;
-use anyhow::{Result, Context};
-use crate::{domain::{dto::todo_dto::{ReqCreateTodoDto, ReqUpdateTodoDto, ResEntryTodoDto}, entities::todo_entity::{EntryTodoEntity, NewTodoEntity, UpdateTodoEntity}, repository::todo_repository::{TodoOperationRepository, TodoUtilityRepository}, schema::schema::todolist}, infrastructure::sqlite::db_connection::sqlite_con::SqlitePoolSquad};
-use diesel::{RunQueryDsl, dsl::insert_into, update, QueryDsl, ExpressionMethods};
+use std::{collections::HashSet, sync::Arc};
+use chrono::{DateTime, Utc};
+use crate::{domain::{dto::{audit_dto::{ChangeOrigin, TaskChangeDto}, batch_dto::{BatchItemStatus, BatchOperation, ReqBatchTodoDto, ResBatchItemDto, ResBatchResultDto}, event_dto::ChangeKind, query_dto::{encode_cursor, ResTodoPageDto, SortDirection, TodoQuery, TodoSortField}, todo_dto::{ReqCreateTodoDto, ReqUpdateTodoDto, ResEntryTodoDto}}, entities::todo_entity::{EntryTodoEntity, NewTodoEntity, UpdateTodoEntity}, error::domain_error::{DomainError, DomainResult}, repository::todo_repository::{TodoOperationRepository, TodoUtilityRepository}, schema::schema::{tags, todo_tags, todolist}}, infrastructure::sqlite::{db_connection::sqlite_con::SqlitePoolSquad, repository_impl::{audit::record_change, list::ensure_list_exists, recurrence::spawn_next_occurrence, subtask::subtask_progress_by_parent, tag::{attach_tag_names, tag_names_by_todo}}}};
+use diesel::{Connection, RunQueryDsl, SqliteConnection, dsl::insert_into, update, QueryDsl, ExpressionMethods, OptionalExtension, TextExpressionMethods, BoolExpressionMethods, EscapeExpressionMethods, result::Error as DieselError, sqlite::Sqlite};
 
 #[derive(Clone)]
 pub struct TodoListSqliteRepository {
-    db_pool: Arc<SqlitePoolSquad>
+    pub(super) db_pool: Arc<SqlitePoolSquad>
 }
 
 impl TodoListSqliteRepository {
@@ -17,157 +17,582 @@ impl TodoListSqliteRepository {
 }
 
 
+/// Build the `WHERE` part of a list query, shared by the page and the total count.
+fn filtered_query(query: &TodoQuery) -> todolist::BoxedQuery<'static, Sqlite> {
+    use crate::domain::schema::schema::todolist::dsl::*;
+
+    let mut statement = todolist.filter(deleted_at.is_null()).into_boxed();
+
+    if let Some(done) = query.is_done {
+        statement = statement.filter(is_done.eq(done));
+    }
+    if let Some(level) = query.priority {
+        statement = statement.filter(priority.eq(level.as_i32()));
+    }
+    if let Some(list) = query.list_id {
+        statement = statement.filter(list_id.eq(list));
+    }
+    // every requested tag must be attached to the task
+    for tag_name in query.tags.iter().flatten() {
+        let tagged_ids = todo_tags::table
+            .inner_join(tags::table)
+            .filter(tags::name.eq(tag_name.trim().to_string()))
+            .select(todo_tags::todo_id);
+        statement = statement.filter(id.eq_any(tagged_ids));
+    }
+    if let Some(text) = query.search.as_deref().map(str::trim).filter(|text| !text.is_empty()) {
+        let pattern = format!("%{}%", escape_like(text));
+        statement = statement.filter(
+            title.like(pattern.clone()).escape('\\')
+                .or(description.like(pattern).escape('\\'))
+        );
+    }
+    if let Some(from) = query.created_from {
+        statement = statement.filter(created_at.ge(from.naive_utc()));
+    }
+    if let Some(to) = query.created_to {
+        statement = statement.filter(created_at.le(to.naive_utc()));
+    }
+    if let Some(from) = query.updated_from {
+        statement = statement.filter(updated_at.ge(from.naive_utc()));
+    }
+    if let Some(to) = query.updated_to {
+        statement = statement.filter(updated_at.le(to.naive_utc()));
+    }
+
+    statement
+}
+
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+
+/// Load a task that is not in the trash.
+pub(super) fn load_todo(conn: &mut SqliteConnection, task_id: i32) -> DomainResult<EntryTodoEntity> {
+    todolist::table
+        .filter(todolist::id.eq(task_id))
+        .filter(todolist::deleted_at.is_null())
+        .first(conn)
+        .optional()?
+        .ok_or_else(|| DomainError::not_found(format!("Todo with id {} not found", task_id)))
+}
+
+pub(super) fn into_res_dto(conn: &mut SqliteConnection, todo: EntryTodoEntity) -> DomainResult<ResEntryTodoDto> {
+    let mut dtos = into_res_dtos(conn, vec![todo])?;
+    Ok(dtos.remove(0))
+}
+
+/// Turn task rows into DTOs, filling in their tags and subtask roll-up.
+pub(super) fn into_res_dtos(conn: &mut SqliteConnection, todos: Vec<EntryTodoEntity>) -> DomainResult<Vec<ResEntryTodoDto>> {
+    let todo_ids: Vec<i32> = todos.iter().map(|todo| todo.id).collect();
+    let mut names = tag_names_by_todo(conn, &todo_ids)?;
+    let progress = subtask_progress_by_parent(conn, &todo_ids)?;
+
+    Ok(todos
+        .into_iter()
+        .map(|todo| {
+            let todo_id = todo.id;
+            let mut dto: ResEntryTodoDto = todo.into();
+            dto.tags = names.remove(&todo_id).unwrap_or_default();
+            if let Some(&(done, total)) = progress.get(&todo_id) {
+                dto.subtasks_done = done;
+                dto.subtasks_total = total;
+            }
+            dto
+        })
+        .collect())
+}
+
+
+/// Insert a task with its tags, meant to run inside a transaction.
+pub(super) fn insert_task(conn: &mut SqliteConnection, dto: ReqCreateTodoDto) -> DomainResult<ResEntryTodoDto> {
+    let tag_names = dto.tags.clone();
+    let entity: NewTodoEntity = dto.into();
+
+    if let Some(list) = entity.list_id {
+        ensure_list_exists(conn, list)?;
+    }
+
+    let now = Utc::now().naive_utc();
+    let inserted: EntryTodoEntity = insert_into(todolist::table)
+        .values((entity, todolist::created_at.eq(now), todolist::updated_at.eq(now)))
+        .returning(todolist::all_columns)
+        .get_result(conn)?;
+
+    attach_tag_names(conn, inserted.id, &tag_names)?;
+
+    into_res_dto(conn, inserted)
+}
+
+/// Mark a task as done, returns the task before and after.
+fn complete_task(conn: &mut SqliteConnection, task_id: i32) -> DomainResult<(ResEntryTodoDto, ResEntryTodoDto)> {
+    let todo = load_todo(conn, task_id)?;
+    let previous = into_res_dto(conn, todo)?;
+
+    if !previous.is_done {
+        update(todolist::table.filter(todolist::id.eq(task_id)))
+            .set((todolist::is_done.eq(true), todolist::version.eq(todolist::version + 1), todolist::updated_at.eq(Utc::now().naive_utc())))
+            .execute(conn)?;
+    }
+
+    let todo = load_todo(conn, task_id)?;
+    Ok((previous, into_res_dto(conn, todo)?))
+}
+
+/// Move a task and its subtasks to the trash, meant to run inside a transaction.
+/// Returns the ids of the subtasks trashed along with it.
+///
+/// They share the same `deleted_at` so restoring the task brings back exactly these subtasks.
+pub(super) fn trash_task(conn: &mut SqliteConnection, task_id: i32) -> DomainResult<Vec<i32>> {
+    use crate::domain::schema::schema::todolist::dsl::*;
+
+    let now = Utc::now().naive_utc();
+
+    let subtask_ids: Vec<i32> = todolist
+        .filter(parent_id.eq(task_id))
+        .filter(deleted_at.is_null())
+        .select(id)
+        .load(conn)?;
+
+    let trashed_rows = update(todolist.filter(id.eq(task_id)).filter(deleted_at.is_null()))
+    .set((deleted_at.eq(now), version.eq(version + 1), updated_at.eq(now)))
+    .execute(conn)?;
+
+    if trashed_rows == 0 {
+        return Err(DomainError::not_found(format!("No todo item found with id {}", task_id)));
+    }
+
+    update(todolist.filter(id.eq_any(&subtask_ids)))
+    .set((deleted_at.eq(now), version.eq(version + 1), updated_at.eq(now)))
+    .execute(conn)?;
+
+    Ok(subtask_ids)
+}
+
+
+/// Why a batch transaction was rolled back.
+enum BatchAbort {
+    /// some items failed, the results say which
+    ItemsFailed(Vec<ResBatchItemDto>),
+    /// the storage itself failed, no per-item result makes sense
+    Storage(DomainError),
+}
+
+impl From<DieselError> for BatchAbort {
+    fn from(err: DieselError) -> Self {
+        BatchAbort::Storage(err.into())
+    }
+}
+
+/// Per-item errors are recorded and the batch carries on so every failure gets reported,
+/// storage errors stop it right away.
+fn item_error(error: DomainError) -> Result<DomainError, BatchAbort> {
+    match error {
+        DomainError::Storage(_) => Err(BatchAbort::Storage(error)),
+        other => Ok(other),
+    }
+}
+
+/// Returns the item results and the next occurrences of the completed recurring tasks.
+fn apply_batch(conn: &mut SqliteConnection, batch: ReqBatchTodoDto, origin: &ChangeOrigin) -> Result<(Vec<ResBatchItemDto>, Vec<TaskChangeDto>), BatchAbort> {
+    let mut items = Vec::with_capacity(batch.len());
+    let mut spawned = Vec::new();
+
+    // each item runs in its own savepoint so a failed one leaves nothing half written
+    for (index, dto) in batch.create.into_iter().enumerate() {
+        let created = conn.transaction(|conn| {
+            let task = insert_task(conn, dto)?;
+            record_change(conn, origin, ChangeKind::Created, task, None)
+        });
+        match created.map(|change| change.task) {
+            Ok(task) => items.push(ResBatchItemDto {
+                operation: BatchOperation::Create,
+                index,
+                task_id: Some(task.id),
+                status: BatchItemStatus::Created,
+                error: None,
+                task: Some(task),
+            }),
+            Err(e) => items.push(ResBatchItemDto::failed(BatchOperation::Create, index, None, item_error(e)?)),
+        }
+    }
+
+    for (index, task_id) in batch.complete.into_iter().enumerate() {
+        let completed = conn.transaction(|conn| {
+            let (previous, task) = complete_task(conn, task_id)?;
+            if previous.is_done {
+                return Ok((true, task, None));
+            }
+            let change = record_change(conn, origin, ChangeKind::Completed, task, Some(previous))?;
+            let next = spawn_next_occurrence(conn, task_id, origin)?;
+            Ok::<_, DomainError>((false, change.task, next))
+        });
+        match completed {
+            Ok((was_done, task, next)) => {
+                spawned.extend(next);
+                items.push(ResBatchItemDto {
+                    operation: BatchOperation::Complete,
+                    index,
+                    task_id: Some(task.id),
+                    status: if was_done { BatchItemStatus::AlreadyDone } else { BatchItemStatus::Completed },
+                    error: None,
+                    task: Some(task),
+                });
+            }
+            Err(e) => items.push(ResBatchItemDto::failed(BatchOperation::Complete, index, Some(task_id), item_error(e)?)),
+        }
+    }
+
+    // tasks already trashed in this batch, listed twice or trashed along with their parent
+    let mut deleted: HashSet<i32> = HashSet::new();
+    for (index, task_id) in batch.delete.into_iter().enumerate() {
+        if deleted.contains(&task_id) {
+            items.push(ResBatchItemDto {
+                operation: BatchOperation::Delete,
+                index,
+                task_id: Some(task_id as u32),
+                status: BatchItemStatus::Deleted,
+                error: None,
+                task: None,
+            });
+            continue;
+        }
+
+        let removed = conn.transaction(|conn| {
+            let todo = load_todo(conn, task_id)?;
+            let snapshot = into_res_dto(conn, todo)?;
+            let subtask_ids = trash_task(conn, task_id)?;
+            let change = record_change(conn, origin, ChangeKind::Deleted, snapshot, None)?;
+            Ok::<_, DomainError>((change.task, subtask_ids))
+        });
+        match removed {
+            Ok((task, subtask_ids)) => {
+                deleted.insert(task_id);
+                deleted.extend(subtask_ids);
+                items.push(ResBatchItemDto {
+                    operation: BatchOperation::Delete,
+                    index,
+                    task_id: Some(task.id),
+                    status: BatchItemStatus::Deleted,
+                    error: None,
+                    task: Some(task),
+                });
+            }
+            Err(e) => items.push(ResBatchItemDto::failed(BatchOperation::Delete, index, Some(task_id), item_error(e)?)),
+        }
+    }
+
+    if items.iter().any(ResBatchItemDto::is_failed) {
+        return Err(BatchAbort::ItemsFailed(items));
+    }
+    Ok((items, spawned))
+}
+
+
 #[async_trait::async_trait]
 impl TodoOperationRepository for TodoListSqliteRepository {
     
-    async fn create_task(&self, dto: ReqCreateTodoDto) -> Result<ResEntryTodoDto> {
-        let conn = &mut self
-            .db_pool
-            .get()
-            .context("Failed to get DB connection from pool")?;
-
-        let entity: NewTodoEntity = dto.into();
-
-        let inserted: EntryTodoEntity = insert_into(todolist::table)
-            .values(entity)
-            .returning(todolist::all_columns)
-            .get_result(conn)
-            .context("Failed to insert new todo into database")?;
+    async fn create_task(&self, dto: ReqCreateTodoDto, origin: ChangeOrigin) -> DomainResult<TaskChangeDto> {
+        let conn = &mut self.db_pool.get()?;
 
-        Ok(inserted.into())
+        conn.transaction(|conn| {
+            let created = insert_task(conn, dto)?;
+            record_change(conn, &origin, ChangeKind::Created, created, None)
+        })
     }
 
-    async fn update_task(&self, task_id: i32, dto: ReqUpdateTodoDto) -> Result<ResEntryTodoDto> {
+    async fn update_task(&self, task_id: i32, dto: ReqUpdateTodoDto, origin: ChangeOrigin) -> DomainResult<(TaskChangeDto, Option<TaskChangeDto>)> {
         
         use crate::domain::schema::schema::todolist::dsl::*;
 
-        let conn = &mut self
-            .db_pool
-            .get()
-            .context("Failed to get DB connection from pool")?;
+        let conn = &mut self.db_pool.get()?;
 
+        let expected_version = dto.expected_version;
         let the_entity: UpdateTodoEntity = dto.into();
 
-        let updated_rows = update(todolist.filter(id.eq(task_id)))
-        .set(the_entity)
-        .execute(conn)
-        .context("Failed to update todo item")?;
-        
-        if updated_rows == 0 {
-            anyhow::bail!("No todo item found with id {}", task_id);
-        }
-
-        let result: EntryTodoEntity = todolist
-            .filter(id.eq(task_id))
-            .first(conn)
-            .context(format!("Failed to get Data"))?;
-
-        Ok(result.into())
+        conn.transaction(|conn| {
+            let current = load_todo(conn, task_id)?;
+            let current_version = current.version;
+            let previous = into_res_dto(conn, current)?;
+            if let Some(expected) = expected_version
+                && expected != current_version
+            {
+                return Err(DomainError::conflict(format!(
+                    "Task {} is at version {}, the update was based on version {}",
+                    task_id, current_version, expected
+                )));
+            }
+            if let Some(Some(list)) = the_entity.list_id {
+                ensure_list_exists(conn, list)?;
+            }
+            let new_list_id = the_entity.list_id;
+            let now = Utc::now().naive_utc();
+
+            // matching the version read above keeps a concurrent writer from slipping in between
+            let updated_rows = update(todolist.filter(id.eq(task_id)).filter(version.eq(current_version)))
+            .set((the_entity, version.eq(version + 1), updated_at.eq(now)))
+            .execute(conn)?;
+            
+            if updated_rows == 0 {
+                return Err(DomainError::conflict(format!("Task {} was changed concurrently, reload it and retry", task_id)));
+            }
+
+            // live subtasks follow their parent into its new list, trashed ones catch up when restored
+            if let Some(new_list_id) = new_list_id {
+                update(todolist.filter(parent_id.eq(task_id)).filter(deleted_at.is_null()))
+                    .set((list_id.eq(new_list_id), version.eq(version + 1), updated_at.eq(now)))
+                    .execute(conn)?;
+            }
+
+            let result: EntryTodoEntity = todolist
+                .filter(id.eq(task_id))
+                .first(conn)?;
+            let updated = into_res_dto(conn, result)?;
+
+            let kind = if updated.is_done && !previous.is_done { ChangeKind::Completed } else { ChangeKind::Updated };
+            let change = record_change(conn, &origin, kind, updated, Some(previous))?;
+            let next = match kind {
+                ChangeKind::Completed => spawn_next_occurrence(conn, task_id, &origin)?,
+                _ => None,
+            };
+            Ok((change, next))
+        })
     }
-    async fn get_by_id(&self, task_id: i32) -> Result<ResEntryTodoDto> {
+    async fn get_by_id(&self, task_id: i32) -> DomainResult<ResEntryTodoDto> {
         
         use crate::domain::schema::schema::todolist::dsl::*;
 
-        let conn = &mut self
-            .db_pool
-            .get()
-            .context("Failed to get DB connection from pool")?;
+        let conn = &mut self.db_pool.get()?;
 
         let todo: EntryTodoEntity = todolist
             .filter(id.eq(task_id))
+            .filter(deleted_at.is_null())
             .first(conn)
-            .context(format!("Todo with id {} not found", task_id))?;
+            .optional()?
+            .ok_or_else(|| DomainError::not_found(format!("Todo with id {} not found", task_id)))?;
 
-        Ok(todo.into())
+        into_res_dto(conn, todo)
     }
 
 
-    async fn get_all(&self) -> Result<Vec<ResEntryTodoDto>> {
+    async fn get_all(&self) -> DomainResult<Vec<ResEntryTodoDto>> {
         use crate::domain::schema::schema::todolist::dsl::*;
 
-        let conn = &mut self
-            .db_pool
-            .get()
-            .context("Failed to get DB connection from pool")?;
+        let conn = &mut self.db_pool.get()?;
 
         let todos: Vec<EntryTodoEntity> = todolist
-            .load(conn)
-            .context("Failed to load todo items from the database")?;
+            .filter(deleted_at.is_null())
+            .load(conn)?;
 
-        let result: Vec<ResEntryTodoDto> = todos.into_iter().map(|todo| todo.into()).collect();
+        into_res_dtos(conn, todos)
+    }
+
+    async fn get_all_in_list(&self, the_list_id: i32) -> DomainResult<Vec<ResEntryTodoDto>> {
+        use crate::domain::schema::schema::todolist::dsl::*;
+
+        let conn = &mut self.db_pool.get()?;
 
-        Ok(result)
+        ensure_list_exists(conn, the_list_id)?;
+
+        let todos: Vec<EntryTodoEntity> = todolist
+            .filter(list_id.eq(the_list_id))
+            .filter(deleted_at.is_null())
+            .load(conn)?;
+
+        into_res_dtos(conn, todos)
     }
-    async fn delete_task(&self, task_id: i32) -> Result<()> {
-        
+
+    async fn list_tasks(&self, query: TodoQuery) -> DomainResult<ResTodoPageDto> {
         use crate::domain::schema::schema::todolist::dsl::*;
 
-        let conn = &mut self
-            .db_pool
-            .get()
-            .context("Failed to get DB connection from pool")?;
+        let conn = &mut self.db_pool.get()?;
 
-        let deleted_rows = diesel::delete(todolist.filter(id.eq(task_id)))
-        .execute(conn)
-        .context("Failed to delete todo item")?;
+        let limit = query.page_limit();
+        let page_offset = query.page_offset()?;
 
-        if deleted_rows == 0 {
-            anyhow::bail!("No todo item found with id {}", task_id);
-        }
+        let total: i64 = filtered_query(&query)
+            .count()
+            .get_result(conn)?;
+
+        let statement = match (query.sort_by, query.sort_dir) {
+            (TodoSortField::CreatedAt, SortDirection::Asc) => filtered_query(&query).order(created_at.asc()),
+            (TodoSortField::CreatedAt, SortDirection::Desc) => filtered_query(&query).order(created_at.desc()),
+            (TodoSortField::UpdatedAt, SortDirection::Asc) => filtered_query(&query).order(updated_at.asc()),
+            (TodoSortField::UpdatedAt, SortDirection::Desc) => filtered_query(&query).order(updated_at.desc()),
+            (TodoSortField::Title, SortDirection::Asc) => filtered_query(&query).order(title.asc()),
+            (TodoSortField::Title, SortDirection::Desc) => filtered_query(&query).order(title.desc()),
+            (TodoSortField::Id, SortDirection::Asc) => filtered_query(&query).order(id.asc()),
+            (TodoSortField::Id, SortDirection::Desc) => filtered_query(&query).order(id.desc()),
+            (TodoSortField::Priority, SortDirection::Asc) => filtered_query(&query).order(priority.asc()),
+            (TodoSortField::Priority, SortDirection::Desc) => filtered_query(&query).order(priority.desc()),
+            (TodoSortField::DueDate, SortDirection::Asc) => filtered_query(&query).order(due_date.asc()),
+            (TodoSortField::DueDate, SortDirection::Desc) => filtered_query(&query).order(due_date.desc()),
+        };
+
+        // tie-break on id so paging stays stable when the sort key repeats
+        let statement = match query.sort_dir {
+            SortDirection::Asc => statement.then_order_by(id.asc()),
+            SortDirection::Desc => statement.then_order_by(id.desc()),
+        };
+
+        let todos: Vec<EntryTodoEntity> = statement
+            .limit(limit)
+            .offset(page_offset)
+            .load(conn)?;
+
+        let next_offset = page_offset + todos.len() as i64;
+        let next_cursor = (next_offset < total).then(|| encode_cursor(next_offset, query.sort_by, query.sort_dir));
+
+        Ok(ResTodoPageDto {
+            items: into_res_dtos(conn, todos)?,
+            total,
+            next_cursor,
+        })
+    }
+
+    async fn get_overdue(&self, now: DateTime<Utc>) -> DomainResult<Vec<ResEntryTodoDto>> {
+        use crate::domain::schema::schema::todolist::dsl::*;
+
+        let conn = &mut self.db_pool.get()?;
+
+        let todos: Vec<EntryTodoEntity> = todolist
+            .filter(is_done.eq(false))
+            .filter(deleted_at.is_null())
+            .filter(due_date.lt(now.naive_utc()))
+            .order((due_date.asc(), priority.desc(), id.asc()))
+            .load(conn)?;
+
+        into_res_dtos(conn, todos)
+    }
+
+    async fn get_due_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> DomainResult<Vec<ResEntryTodoDto>> {
+        use crate::domain::schema::schema::todolist::dsl::*;
+
+        let conn = &mut self.db_pool.get()?;
+
+        let todos: Vec<EntryTodoEntity> = todolist
+            .filter(is_done.eq(false))
+            .filter(deleted_at.is_null())
+            .filter(due_date.ge(from.naive_utc()))
+            .filter(due_date.lt(to.naive_utc()))
+            .order((due_date.asc(), priority.desc(), id.asc()))
+            .load(conn)?;
+
+        into_res_dtos(conn, todos)
+    }
 
-        Ok(())
+    async fn delete_task(&self, task_id: i32, origin: ChangeOrigin) -> DomainResult<TaskChangeDto> {
+        let conn = &mut self.db_pool.get()?;
+
+        // deleting a task moves it to the trash, with its subtasks
+        conn.transaction(|conn| {
+            let todo = load_todo(conn, task_id)?;
+            let snapshot = into_res_dto(conn, todo)?;
+            trash_task(conn, task_id)?;
+            record_change(conn, &origin, ChangeKind::Deleted, snapshot, None)
+        })
+    }
+
+    async fn run_batch(&self, batch: ReqBatchTodoDto, origin: ChangeOrigin) -> DomainResult<(ResBatchResultDto, Vec<TaskChangeDto>)> {
+        let conn = &mut self.db_pool.get()?;
+
+        match conn.transaction(|conn| apply_batch(conn, batch, &origin)) {
+            Ok((items, spawned)) => Ok((ResBatchResultDto { committed: true, items }, spawned)),
+            Err(BatchAbort::ItemsFailed(items)) => Ok((ResBatchResultDto::rolled_back(items), Vec::new())),
+            Err(BatchAbort::Storage(e)) => Err(e),
+        }
     }
 }
 
 #[async_trait::async_trait]
 impl TodoUtilityRepository for TodoListSqliteRepository {
-    async fn count_all_task(&self) -> Result<i32>{
+    async fn count_all_task(&self) -> DomainResult<i32>{
         
         use crate::domain::schema::schema::todolist::dsl::*;
 
-        let conn = &mut self
-            .db_pool
-            .get()
-            .context("Failed to get DB connection from pool")?;
+        let conn = &mut self.db_pool.get()?;
 
         let count: i64 = todolist
-        .count() // count all rows
-        .get_result(conn)
-        .context("Failed to count all todo items in the database")?;
+        .filter(deleted_at.is_null()) // trashed tasks do not count
+        .count()
+        .get_result(conn)?;
 
         // Since Diesel returns `i64` for the count, we need to cast it to `i32`
         Ok(count as i32)
     }
-    async fn count_done_task(&self) -> Result<i32>{
+    async fn count_done_task(&self) -> DomainResult<i32>{
         
         use crate::domain::schema::schema::todolist::dsl::*;
 
-        let conn = &mut self
-            .db_pool
-            .get()
-            .context("Failed to get DB connection from pool")?;
+        let conn = &mut self.db_pool.get()?;
 
         let count: i64 = todolist
         .filter(is_done.eq(true)) // Filter to count only done tasks
+        .filter(deleted_at.is_null())
         .count()
-        .get_result(conn)
-        .context("Failed to count done todo items in the database")?;
+        .get_result(conn)?;
 
         Ok(count as i32)
     }
-    async fn count_undone_task(&self) -> Result<i32>{
+    async fn count_undone_task(&self) -> DomainResult<i32>{
         use crate::domain::schema::schema::todolist::dsl::*;
 
-        let conn = &mut self
-            .db_pool
-            .get()
-            .context("Failed to get DB connection from pool")?;
+        let conn = &mut self.db_pool.get()?;
 
         let count: i64 = todolist
             .filter(is_done.eq(false)) // Filter to count only undone tasks
+            .filter(deleted_at.is_null())
+            .count()
+            .get_result(conn)?;
+
+        Ok(count as i32)
+    }
+
+    async fn count_all_task_in_list(&self, the_list_id: i32) -> DomainResult<i32> {
+        use crate::domain::schema::schema::todolist::dsl::*;
+
+        let conn = &mut self.db_pool.get()?;
+
+        ensure_list_exists(conn, the_list_id)?;
+
+        let count: i64 = todolist
+            .filter(list_id.eq(the_list_id))
+            .filter(deleted_at.is_null())
+            .count()
+            .get_result(conn)?;
+
+        Ok(count as i32)
+    }
+    async fn count_done_task_in_list(&self, the_list_id: i32) -> DomainResult<i32> {
+        use crate::domain::schema::schema::todolist::dsl::*;
+
+        let conn = &mut self.db_pool.get()?;
+
+        ensure_list_exists(conn, the_list_id)?;
+
+        let count: i64 = todolist
+            .filter(list_id.eq(the_list_id))
+            .filter(deleted_at.is_null())
+            .filter(is_done.eq(true))
+            .count()
+            .get_result(conn)?;
+
+        Ok(count as i32)
+    }
+    async fn count_undone_task_in_list(&self, the_list_id: i32) -> DomainResult<i32> {
+        use crate::domain::schema::schema::todolist::dsl::*;
+
+        let conn = &mut self.db_pool.get()?;
+
+        ensure_list_exists(conn, the_list_id)?;
+
+        let count: i64 = todolist
+            .filter(list_id.eq(the_list_id))
+            .filter(deleted_at.is_null())
+            .filter(is_done.eq(false))
             .count()
-            .get_result(conn)
-            .context("Failed to count undone todo items in the database")?;
+            .get_result(conn)?;
 
         Ok(count as i32)
     }
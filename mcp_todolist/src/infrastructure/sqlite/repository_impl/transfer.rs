@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use chrono::{NaiveDateTime, Utc};
+use crate::{domain::{dto::{audit_dto::ChangeOrigin, event_dto::ChangeKind, transfer_dto::{ImportOutcome, ResImportEntryDto, ResImportReportDto, TransferDocumentDto, TransferTaskDto}}, entities::{list_entity::NewListEntity, recurrence_entity::NewRecurrenceEntity, tag_entity::NewTagEntity, todo_entity::{EntryTodoEntity, NewTodoEntity}}, error::domain_error::{DomainError, DomainResult}, repository::transfer_repository::TransferRepository, schema::schema::{lists, recurrences, tags, todolist}}, infrastructure::sqlite::repository_impl::{audit::record_change, tag::attach_tag_names, todolist::{into_res_dtos, TodoListSqliteRepository}}};
+use diesel::{dsl::{insert_into, insert_or_ignore_into, max}, update, Connection, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl, SqliteConnection};
+
+
+
+/// What makes two tasks duplicates: the same parent, title (ignoring case and spacing) and due date,
+/// both live or both in the trash.
+#[derive(Hash, PartialEq, Eq)]
+struct DuplicateKey {
+    parent: ParentRef,
+    title: String,
+    due_date: Option<NaiveDateTime>,
+    trashed: bool,
+}
+
+/// Parent of a task being imported, a stored task or one of the file not stored because of a dry run.
+#[derive(Hash, PartialEq, Eq, Clone, Copy)]
+enum ParentRef {
+    None,
+    Stored(i32),
+    Pending(u32),
+}
+
+impl DuplicateKey {
+    fn new(parent: ParentRef, title: &str, due_date: Option<NaiveDateTime>, trashed: bool) -> Self {
+        let words: Vec<&str> = title.split_whitespace().collect();
+        DuplicateKey { parent, title: words.join(" ").to_lowercase(), due_date, trashed }
+    }
+}
+
+
+/// Id of the list named `name` (ignoring case), created when missing unless on a dry run.
+fn resolve_list(conn: &mut SqliteConnection, document: &TransferDocumentDto, name: &str, dry_run: bool) -> DomainResult<Option<i32>> {
+    // the NOCASE collation of `lists.name` makes this lookup case-insensitive
+    let existing: Option<i32> = lists::table
+        .filter(lists::name.eq(name))
+        .select(lists::id)
+        .first(conn)
+        .optional()?;
+    if existing.is_some() || dry_run {
+        return Ok(existing);
+    }
+
+    let description = document
+        .lists
+        .iter()
+        .find(|list| list.name.eq_ignore_ascii_case(name))
+        .and_then(|list| list.description.clone());
+    let created: i32 = insert_into(lists::table)
+        .values(NewListEntity { name: name.to_string(), description })
+        .returning(lists::id)
+        .get_result(conn)?;
+    Ok(Some(created))
+}
+
+/// Id of the series the file calls `file_id`, created on first use.
+fn resolve_series(conn: &mut SqliteConnection, document: &TransferDocumentDto, file_id: u32, created: &mut HashMap<u32, i32>) -> DomainResult<i32> {
+    if let Some(&series_id) = created.get(&file_id) {
+        return Ok(series_id);
+    }
+
+    // checked to exist before the import starts
+    let Some(series) = document.recurrences.iter().find(|series| series.id == file_id) else {
+        return Err(DomainError::validation(format!("recurrence_id {} matches no series of the file", file_id)));
+    };
+    let entity = NewRecurrenceEntity {
+        frequency: series.frequency.as_str().to_string(),
+        interval: series.interval as i32,
+        starts_at: series.starts_at.naive_utc(),
+        until: series.until.map(|until| until.naive_utc()),
+        max_occurrences: series.count.map(|count| count as i32),
+        created_at: Utc::now().naive_utc(),
+    };
+    let series_id: i32 = insert_into(recurrences::table)
+        .values((entity, recurrences::stopped_at.eq(series.stopped_at.map(|stopped| stopped.naive_utc()))))
+        .returning(recurrences::id)
+        .get_result(conn)?;
+    created.insert(file_id, series_id);
+    Ok(series_id)
+}
+
+/// Create the tags of the task that do not exist yet with the colour the file gives them.
+fn create_missing_tags(conn: &mut SqliteConnection, document: &TransferDocumentDto, names: &[String]) -> DomainResult<()> {
+    let new_tags: Vec<NewTagEntity> = document
+        .tags
+        .iter()
+        .filter(|tag| tag.color.is_some() && names.iter().any(|name| name.eq_ignore_ascii_case(tag.name.trim())))
+        .map(|tag| NewTagEntity { name: tag.name.trim().to_string(), color: tag.color.clone() })
+        .collect();
+    if !new_tags.is_empty() {
+        insert_or_ignore_into(tags::table)
+            .values(new_tags)
+            .execute(conn)?;
+    }
+    Ok(())
+}
+
+/// Insert a task of the file as a live task, see `import_tasks` for the trashed ones.
+fn insert_imported(conn: &mut SqliteConnection, task: &TransferTaskDto, list_id: Option<i32>, parent_id: Option<i32>, series_id: Option<i32>) -> DomainResult<EntryTodoEntity> {
+    let position = match parent_id {
+        Some(parent) => todolist::table
+            .filter(todolist::parent_id.eq(parent))
+            .select(max(todolist::position))
+            .first::<Option<i32>>(conn)?
+            .map_or(0, |last| last + 1),
+        None => 0,
+    };
+
+    let entity = NewTodoEntity {
+        title: task.title.trim().to_string(),
+        description: task.description.clone(),
+        is_done: task.is_done,
+        priority: task.priority.as_i32(),
+        due_date: task.due_date.map(|due| due.naive_utc()),
+        list_id,
+        parent_id,
+        position,
+        remind_at: task.remind_at.map(|remind| remind.naive_utc()),
+    };
+
+    let now = Utc::now().naive_utc();
+    let created_at = task.created_at.map_or(now, |created| created.naive_utc());
+    let updated_at = task.updated_at.map_or(now, |updated| updated.naive_utc());
+    let inserted: EntryTodoEntity = insert_into(todolist::table)
+        .values((
+            entity,
+            todolist::created_at.eq(created_at),
+            todolist::updated_at.eq(updated_at),
+            todolist::version.eq(task.version.unwrap_or(1)),
+            todolist::recurrence_id.eq(series_id),
+            todolist::occurrence.eq(series_id.and(task.occurrence)),
+        ))
+        .returning(todolist::all_columns)
+        .get_result(conn)?;
+
+    attach_tag_names(conn, inserted.id, &task.tags)?;
+    Ok(inserted)
+}
+
+
+#[async_trait::async_trait]
+impl TransferRepository for TodoListSqliteRepository {
+
+    async fn import_tasks(&self, document: TransferDocumentDto, dry_run: bool, origin: ChangeOrigin) -> DomainResult<ResImportReportDto> {
+        let conn = &mut self.db_pool.get()?;
+
+        conn.transaction(|conn| {
+            let stored: Vec<(i32, Option<i32>, String, Option<NaiveDateTime>, Option<NaiveDateTime>)> = todolist::table
+                .select((todolist::id, todolist::parent_id, todolist::title, todolist::due_date, todolist::deleted_at))
+                .load(conn)?;
+            let mut known: HashMap<DuplicateKey, i32> = stored
+                .into_iter()
+                .map(|(id, parent_id, title, due_date, deleted_at)| {
+                    (DuplicateKey::new(parent_id.map_or(ParentRef::None, ParentRef::Stored), &title, due_date, deleted_at.is_some()), id)
+                })
+                .collect();
+            // file tasks seen so far on a dry run with their file id, they have no id to point at
+            let mut pending: HashMap<DuplicateKey, u32> = HashMap::new();
+
+            // tasks first so their subtasks can point at them, whatever the file order
+            let mut order: Vec<usize> = (0..document.tasks.len()).collect();
+            order.sort_by_key(|&index| document.tasks[index].parent_id.is_some());
+
+            let mut parents: HashMap<u32, (ParentRef, Option<i32>)> = HashMap::new();
+            let mut entries: Vec<ResImportEntryDto> = Vec::with_capacity(order.len());
+            let mut created: Vec<EntryTodoEntity> = Vec::new();
+            let mut series: HashMap<u32, i32> = HashMap::new();
+            // created tasks the file has in the trash, with when they were trashed
+            let mut to_trash: Vec<(i32, Option<i32>, NaiveDateTime)> = Vec::new();
+            for index in order {
+                let task = &document.tasks[index];
+                let (parent, parent_list) = match task.parent_id {
+                    Some(file_id) => parents.get(&file_id).copied().unwrap_or((ParentRef::None, None)),
+                    None => (ParentRef::None, None),
+                };
+                let list_id = match (&task.list, parent) {
+                    // subtasks always sit in the list of their task
+                    (_, ParentRef::Stored(_) | ParentRef::Pending(_)) => parent_list,
+                    (Some(name), ParentRef::None) => resolve_list(conn, &document, name, dry_run)?,
+                    (None, ParentRef::None) => None,
+                };
+
+                let key = DuplicateKey::new(parent, &task.title, task.due_date.map(|due| due.naive_utc()), task.deleted_at.is_some());
+                let (outcome, task_ref, task_id) = if let Some(&existing) = known.get(&key) {
+                    (ImportOutcome::Duplicate, ParentRef::Stored(existing), Some(existing))
+                } else if let Some(&first) = pending.get(&key) {
+                    // its subtasks are matched against the ones of the first copy, as the real run does
+                    (ImportOutcome::Duplicate, ParentRef::Pending(first), None)
+                } else if dry_run {
+                    let file_id = task.id.unwrap_or_default();
+                    pending.insert(key, file_id);
+                    (ImportOutcome::Created, ParentRef::Pending(file_id), None)
+                } else {
+                    let parent_id = match parent {
+                        ParentRef::Stored(parent_id) => Some(parent_id),
+                        _ => None,
+                    };
+                    let series_id = task
+                        .recurrence_id
+                        .map(|file_id| resolve_series(conn, &document, file_id, &mut series))
+                        .transpose()?;
+                    create_missing_tags(conn, &document, &task.tags)?;
+                    let inserted = insert_imported(conn, task, list_id, parent_id, series_id)?;
+                    if let Some(deleted_at) = task.deleted_at {
+                        to_trash.push((inserted.id, parent_id, deleted_at.naive_utc()));
+                    }
+                    known.insert(key, inserted.id);
+                    let inserted_id = inserted.id;
+                    created.push(inserted);
+                    (ImportOutcome::Created, ParentRef::Stored(inserted_id), Some(inserted_id))
+                };
+
+                if let Some(file_id) = task.id {
+                    parents.insert(file_id, (task_ref, list_id));
+                }
+                entries.push(ResImportEntryDto {
+                    record: index as u32 + 1,
+                    title: task.title.trim().to_string(),
+                    outcome,
+                    task_id: task_id.map(|id| id as u32),
+                });
+            }
+            entries.sort_by_key(|entry| entry.record);
+
+            let created_ids: Vec<i32> = created.iter().map(|task| task.id).collect();
+            let mut changes = Vec::with_capacity(created.len());
+            for task in into_res_dtos(conn, created)? {
+                changes.push(record_change(conn, &origin, ChangeKind::Created, task, None)?);
+            }
+
+            // trashed tasks are created then trashed, so the audit log reads like any other delete:
+            // one entry for the task, none for the subtasks that went to the trash along with it
+            for &(task_id, parent_id, deleted_at) in &to_trash {
+                update(todolist::table.filter(todolist::id.eq(task_id)))
+                    .set(todolist::deleted_at.eq(deleted_at))
+                    .execute(conn)?;
+                let with_parent = parent_id.is_some_and(|parent_id| to_trash.iter().any(|&(id, _, at)| id == parent_id && at == deleted_at));
+                if !with_parent && let Some(change) = changes.iter().find(|change| change.task.id == task_id as u32) {
+                    record_change(conn, &origin, ChangeKind::Deleted, change.task.clone(), None)?;
+                }
+            }
+
+            let tasks: Vec<EntryTodoEntity> = todolist::table
+                .filter(todolist::id.eq_any(&created_ids))
+                .order(todolist::id.asc())
+                .load(conn)?;
+            let tasks = into_res_dtos(conn, tasks)?;
+            let duplicates = entries.iter().filter(|entry| entry.outcome == ImportOutcome::Duplicate).count() as u32;
+            Ok(ResImportReportDto {
+                dry_run,
+                created: entries.len() as u32 - duplicates,
+                duplicates,
+                entries,
+                tasks,
+            })
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,127 @@
+use chrono::Utc;
+use crate::{domain::{dto::{audit_dto::{ChangeOrigin, TaskChangeDto}, event_dto::ChangeKind, todo_dto::ResEntryTodoDto}, entities::todo_entity::EntryTodoEntity, error::domain_error::{DomainError, DomainResult}, repository::trash_repository::TrashRepository, schema::schema::todolist}, infrastructure::sqlite::repository_impl::{audit::record_change, todolist::{into_res_dto, into_res_dtos, load_todo, TodoListSqliteRepository}}};
+use diesel::{update, Connection, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl, SqliteConnection};
+
+
+
+fn load_trashed(conn: &mut SqliteConnection, task_id: i32) -> DomainResult<EntryTodoEntity> {
+    todolist::table
+        .filter(todolist::id.eq(task_id))
+        .filter(todolist::deleted_at.is_not_null())
+        .first(conn)
+        .optional()?
+        .ok_or_else(|| DomainError::not_found(format!("No task with id {} in the trash", task_id)))
+}
+
+/// Bring a trashed task back with the subtasks trashed together with it, meant to run inside a transaction.
+pub(super) fn restore_trashed(conn: &mut SqliteConnection, task_id: i32) -> DomainResult<EntryTodoEntity> {
+    let trashed = load_trashed(conn, task_id)?;
+    // a subtask goes back to the list its parent is in now
+    let mut list_id = trashed.list_id;
+    if let Some(parent_id) = trashed.parent_id {
+        match load_todo(conn, parent_id) {
+            Ok(parent) => list_id = parent.list_id,
+            Err(DomainError::NotFound(_)) => {
+                return Err(DomainError::validation(format!(
+                    "Task {} is a subtask of task {} which is in the trash, restore the parent instead",
+                    task_id, parent_id
+                )));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    // subtasks trashed on their own before the parent stay in the trash
+    update(
+        todolist::table
+            .filter(todolist::parent_id.eq(task_id))
+            .filter(todolist::deleted_at.eq(trashed.deleted_at))
+    )
+    .set((todolist::deleted_at.eq(None::<chrono::NaiveDateTime>), todolist::version.eq(todolist::version + 1), todolist::updated_at.eq(Utc::now().naive_utc())))
+    .execute(conn)?;
+
+    update(todolist::table.filter(todolist::id.eq(task_id)))
+    .set((todolist::deleted_at.eq(None::<chrono::NaiveDateTime>), todolist::list_id.eq(list_id), todolist::version.eq(todolist::version + 1), todolist::updated_at.eq(Utc::now().naive_utc())))
+    .execute(conn)?;
+
+    load_todo(conn, task_id)
+}
+
+/// Hard delete a task and all its subtasks, meant to run inside a transaction.
+///
+/// Subtasks are deleted here rather than relying on ON DELETE CASCADE
+/// so it holds whatever the connection pragmas are.
+fn purge(conn: &mut SqliteConnection, task_id: i32) -> DomainResult<Vec<i32>> {
+    use crate::domain::schema::schema::todolist::dsl::*;
+
+    let mut purged_ids: Vec<i32> = todolist
+        .filter(parent_id.eq(task_id))
+        .select(id)
+        .load(conn)?;
+
+    diesel::delete(todolist.filter(parent_id.eq(task_id)))
+    .execute(conn)?;
+
+    diesel::delete(todolist.filter(id.eq(task_id)))
+    .execute(conn)?;
+
+    purged_ids.insert(0, task_id);
+    Ok(purged_ids)
+}
+
+
+#[async_trait::async_trait]
+impl TrashRepository for TodoListSqliteRepository {
+
+    async fn list_trash(&self) -> DomainResult<Vec<ResEntryTodoDto>> {
+        use crate::domain::schema::schema::todolist::dsl::*;
+
+        let conn = &mut self.db_pool.get()?;
+
+        let todos: Vec<EntryTodoEntity> = todolist
+            .filter(deleted_at.is_not_null())
+            .order((deleted_at.desc(), id.asc()))
+            .load(conn)?;
+
+        into_res_dtos(conn, todos)
+    }
+
+    async fn restore_task(&self, task_id: i32, origin: ChangeOrigin) -> DomainResult<TaskChangeDto> {
+        let conn = &mut self.db_pool.get()?;
+
+        conn.transaction(|conn| {
+            let restored = restore_trashed(conn, task_id)?;
+            let restored = into_res_dto(conn, restored)?;
+            record_change(conn, &origin, ChangeKind::Restored, restored, None)
+        })
+    }
+
+    async fn purge_task(&self, task_id: i32) -> DomainResult<Vec<i32>> {
+        let conn = &mut self.db_pool.get()?;
+
+        conn.transaction(|conn| {
+            load_trashed(conn, task_id)?;
+            purge(conn, task_id)
+        })
+    }
+
+    async fn empty_trash(&self) -> DomainResult<Vec<i32>> {
+        let conn = &mut self.db_pool.get()?;
+
+        conn.transaction(|conn| {
+            let trashed_ids: Vec<i32> = todolist::table
+                .filter(todolist::deleted_at.is_not_null())
+                .select(todolist::id)
+                .load(conn)?;
+
+            let mut purged: Vec<i32> = Vec::new();
+            for task_id in trashed_ids {
+                // already gone when its parent was purged earlier in the loop
+                if !purged.contains(&task_id) {
+                    purged.extend(purge(conn, task_id)?);
+                }
+            }
+            Ok(purged)
+        })
+    }
+}
\ No newline at end of file
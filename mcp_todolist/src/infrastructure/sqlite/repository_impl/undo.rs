@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use crate::{domain::{dto::{audit_dto::{ChangeOrigin, ReqTaskAuditDto}, event_dto::ChangeKind, todo_dto::{ResEntryTodoDto, TaskPriority}, undo_dto::ResUndoneChangeDto}, entities::audit_entity::EntryTaskAuditEntity, error::domain_error::{DomainError, DomainResult}, schema::schema::{task_audit, todo_tags, todolist}}, infrastructure::sqlite::repository_impl::{audit::insert_audit, list::ensure_list_exists, tag::attach_tag_names, todolist::{into_res_dto, load_todo, trash_task}, trash::restore_trashed}};
+use diesel::{update, ExpressionMethods, QueryDsl, RunQueryDsl, SqliteConnection};
+
+
+
+/// The editable fields of a task snapshot, other fields are ignored so older snapshots still read.
+#[derive(Deserialize)]
+struct TaskFields {
+    title: String,
+    description: String,
+    is_done: bool,
+    priority: TaskPriority,
+    due_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    remind_at: Option<DateTime<Utc>>,
+    list_id: Option<u32>,
+    #[serde(default)]
+    position: i32,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+
+/// Put the fields of a task back to a snapshot, tags included.
+fn restore_fields(conn: &mut SqliteConnection, task_id: i32, snapshot: &str) -> DomainResult<()> {
+    use crate::domain::schema::schema::todolist::dsl::*;
+
+    let fields: TaskFields = serde_json::from_str(snapshot)
+        .map_err(|e| DomainError::storage(format!("Corrupted audit snapshot: {}", e)))?;
+    let current = load_todo(conn, task_id)?;
+    let now = Utc::now().naive_utc();
+
+    let restored_list = fields.list_id.map(|list| list as i32);
+    if let Some(list) = restored_list {
+        ensure_list_exists(conn, list)?;
+    }
+
+    update(todolist.filter(id.eq(task_id)))
+        .set((
+            title.eq(fields.title),
+            description.eq(fields.description),
+            is_done.eq(fields.is_done),
+            priority.eq(fields.priority.as_i32()),
+            due_date.eq(fields.due_date.map(|due| due.naive_utc())),
+            remind_at.eq(fields.remind_at.map(|remind| remind.naive_utc())),
+            list_id.eq(restored_list),
+            position.eq(fields.position),
+            version.eq(version + 1),
+            updated_at.eq(now),
+        ))
+        .execute(conn)?;
+
+    // subtasks follow their parent back into its previous list
+    if current.list_id != restored_list {
+        update(todolist.filter(parent_id.eq(task_id)))
+            .set((list_id.eq(restored_list), version.eq(version + 1), updated_at.eq(now)))
+            .execute(conn)?;
+    }
+
+    diesel::delete(todo_tags::table.filter(todo_tags::todo_id.eq(task_id)))
+        .execute(conn)?;
+    attach_tag_names(conn, task_id, &fields.tags)
+}
+
+
+/// Apply the opposite of an audited change.
+/// Returns the action of the change, the action that reverted it and the task before and after the revert.
+fn revert(conn: &mut SqliteConnection, entry: &EntryTaskAuditEntity) -> DomainResult<(ChangeKind, ChangeKind, Option<ResEntryTodoDto>, Option<ResEntryTodoDto>)> {
+    let task_id = entry.todo_id;
+    let action = ChangeKind::parse(&entry.action)
+        .ok_or_else(|| DomainError::storage(format!("Unknown audit action {}", entry.action)))?;
+
+    match action {
+        ChangeKind::Created | ChangeKind::Restored => {
+            let current = load_todo(conn, task_id)?;
+            let before = into_res_dto(conn, current)?;
+            trash_task(conn, task_id)?;
+            Ok((action, ChangeKind::Deleted, Some(before), None))
+        }
+        ChangeKind::Deleted => {
+            let restored = restore_trashed(conn, task_id)?;
+            Ok((action, ChangeKind::Restored, None, Some(into_res_dto(conn, restored)?)))
+        }
+        ChangeKind::Updated | ChangeKind::Completed => {
+            let Some(snapshot) = entry.before_snapshot.as_deref() else {
+                return Err(DomainError::validation(format!("Change {} of task {} did not record the task before it, it cannot be undone", entry.id, task_id)));
+            };
+            let current = load_todo(conn, task_id)?;
+            let before = into_res_dto(conn, current)?;
+            restore_fields(conn, task_id, snapshot)?;
+            let restored = load_todo(conn, task_id)?;
+            Ok((action, ChangeKind::Updated, Some(before), Some(into_res_dto(conn, restored)?)))
+        }
+    }
+}
+
+
+/// Revert the last `operations` operations of a session, meant to run inside a transaction.
+///
+/// Every change is checked before anything is reverted: a task changed since by anyone but the
+/// session itself (or an undo of the session) makes the whole undo fail with a conflict.
+pub(super) fn undo_session_changes(conn: &mut SqliteConnection, session_id: &str, operations: u32, origin: ChangeOrigin) -> DomainResult<Vec<ResUndoneChangeDto>> {
+    let session_entries: Vec<EntryTaskAuditEntity> = task_audit::table
+        .filter(task_audit::session_id.eq(session_id))
+        .order(task_audit::id.desc())
+        .load(conn)?;
+
+    // entries written by undoing this session, whichever client ran the undo
+    let revert_ids: HashSet<i32> = session_entries.iter().filter_map(|entry| entry.reverted_by).collect();
+
+    let mut operation_ids: Vec<&str> = Vec::new();
+    let mut to_undo: Vec<&EntryTaskAuditEntity> = Vec::new();
+    for entry in &session_entries {
+        if entry.reverted_by.is_some() || revert_ids.contains(&entry.id) {
+            continue;
+        }
+        let Some(operation_id) = entry.operation_id.as_deref() else { continue };
+        if !operation_ids.contains(&operation_id) {
+            if operation_ids.len() == operations as usize {
+                break;
+            }
+            operation_ids.push(operation_id);
+        }
+        to_undo.push(entry);
+    }
+    if to_undo.is_empty() {
+        return Err(DomainError::validation(format!("Session {} has nothing left to undo", session_id)));
+    }
+
+    for entry in &to_undo {
+        let mut task_ids = vec![entry.todo_id];
+        // trashing the task again would take subtasks added since along with it
+        if matches!(ChangeKind::parse(&entry.action), Some(ChangeKind::Created | ChangeKind::Restored)) {
+            let subtask_ids: Vec<i32> = todolist::table
+                .filter(todolist::parent_id.eq(entry.todo_id))
+                .select(todolist::id)
+                .load(conn)?;
+            task_ids.extend(subtask_ids);
+        }
+
+        let later: Vec<EntryTaskAuditEntity> = task_audit::table
+            .filter(task_audit::todo_id.eq_any(&task_ids))
+            .filter(task_audit::id.gt(entry.id))
+            .order(task_audit::id.asc())
+            .load(conn)?;
+        let clobbered = later
+            .iter()
+            .find(|change| change.session_id.as_deref() != Some(session_id) && !revert_ids.contains(&change.id));
+        if let Some(change) = clobbered {
+            return Err(DomainError::conflict(format!(
+                "Task {} was changed by {} ({}) after this session, undo refused",
+                change.todo_id,
+                change.client_name.as_deref().unwrap_or("an unknown client"),
+                change.origin
+            )));
+        }
+    }
+
+    let mut undone = Vec::with_capacity(to_undo.len());
+    for entry in to_undo {
+        let (undone_action, action, before, after) = revert(conn, entry)?;
+
+        let revert_entry = insert_audit(conn, ReqTaskAuditDto {
+            task_id: entry.todo_id as u32,
+            action,
+            before: before.clone(),
+            after: after.clone(),
+            origin: origin.clone(),
+        })?;
+        update(task_audit::table.filter(task_audit::id.eq(entry.id)))
+            .set(task_audit::reverted_by.eq(revert_entry.id))
+            .execute(conn)?;
+
+        undone.push(ResUndoneChangeDto {
+            audit_id: entry.id as u32,
+            operation_id: entry.operation_id.clone().unwrap_or_default(),
+            task_id: entry.todo_id as u32,
+            undone_action,
+            action,
+            before,
+            after,
+        });
+    }
+
+    Ok(undone)
+}
\ No newline at end of file
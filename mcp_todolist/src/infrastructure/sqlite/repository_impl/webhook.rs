@@ -0,0 +1,187 @@
+use chrono::{DateTime, Utc};
+use crate::{domain::{dto::{event_dto::ChangeKind, webhook_dto::{ReqCreateWebhookDto, ReqUpdateWebhookDto, ResWebhookDeliveryDto, ResWebhookDto, ResWebhookPayloadDto, WebhookAttemptDto, WebhookDeliveryJob, WebhookDeliveryStatus}}, entities::webhook_entity::{join_events, EntryWebhookDeliveryEntity, EntryWebhookEntity, NewWebhookDeliveryEntity, NewWebhookEntity, UpdateWebhookEntity}, error::domain_error::{DomainError, DomainResult}, repository::webhook_repository::WebhookRepository, schema::schema::{webhook_deliveries, webhooks}}, infrastructure::sqlite::repository_impl::todolist::TodoListSqliteRepository};
+use diesel::{dsl::{exists, insert_into, select}, update, Connection, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+
+
+
+#[async_trait::async_trait]
+impl WebhookRepository for TodoListSqliteRepository {
+
+    async fn create_webhook(&self, dto: ReqCreateWebhookDto, secret: String) -> DomainResult<ResWebhookDto> {
+        let conn = &mut self.db_pool.get()?;
+
+        let now = Utc::now().naive_utc();
+        let inserted: EntryWebhookEntity = insert_into(webhooks::table)
+            .values(NewWebhookEntity {
+                url: dto.url,
+                events: join_events(&dto.events),
+                secret,
+                active: dto.active,
+                created_at: now,
+                updated_at: now,
+            })
+            .returning(webhooks::all_columns)
+            .get_result(conn)?;
+
+        inserted.try_into()
+    }
+
+    async fn update_webhook(&self, webhook_id: i32, dto: ReqUpdateWebhookDto) -> DomainResult<ResWebhookDto> {
+        let conn = &mut self.db_pool.get()?;
+
+        let the_entity = UpdateWebhookEntity {
+            url: dto.url,
+            events: dto.events.as_deref().map(join_events),
+            secret: dto.secret,
+            active: dto.active,
+            updated_at: Utc::now().naive_utc(),
+        };
+
+        let updated: EntryWebhookEntity = update(webhooks::table.filter(webhooks::id.eq(webhook_id)))
+            .set(the_entity)
+            .returning(webhooks::all_columns)
+            .get_result(conn)
+            .optional()?
+            .ok_or_else(|| DomainError::not_found(format!("No webhook found with id {}", webhook_id)))?;
+
+        updated.try_into()
+    }
+
+    async fn get_webhook(&self, webhook_id: i32) -> DomainResult<ResWebhookDto> {
+        let conn = &mut self.db_pool.get()?;
+
+        let webhook: EntryWebhookEntity = webhooks::table
+            .filter(webhooks::id.eq(webhook_id))
+            .first(conn)
+            .optional()?
+            .ok_or_else(|| DomainError::not_found(format!("Webhook with id {} not found", webhook_id)))?;
+
+        webhook.try_into()
+    }
+
+    async fn get_all_webhooks(&self) -> DomainResult<Vec<ResWebhookDto>> {
+        let conn = &mut self.db_pool.get()?;
+
+        let all_webhooks: Vec<EntryWebhookEntity> = webhooks::table
+            .order(webhooks::id.asc())
+            .load(conn)?;
+
+        all_webhooks.into_iter().map(|webhook| webhook.try_into()).collect()
+    }
+
+    async fn delete_webhook(&self, webhook_id: i32) -> DomainResult<()> {
+        let conn = &mut self.db_pool.get()?;
+
+        // the delivery log goes with it through ON DELETE CASCADE
+        let deleted_rows = diesel::delete(webhooks::table.filter(webhooks::id.eq(webhook_id)))
+            .execute(conn)?;
+
+        if deleted_rows == 0 {
+            return Err(DomainError::not_found(format!("No webhook found with id {}", webhook_id)));
+        }
+
+        Ok(())
+    }
+
+    async fn get_webhook_deliveries(&self, webhook_id: i32, limit: u32) -> DomainResult<Vec<ResWebhookDeliveryDto>> {
+        let conn = &mut self.db_pool.get()?;
+
+        let found: bool = select(exists(webhooks::table.filter(webhooks::id.eq(webhook_id))))
+            .get_result(conn)?;
+        if !found {
+            return Err(DomainError::not_found(format!("Webhook with id {} not found", webhook_id)));
+        }
+
+        let deliveries: Vec<EntryWebhookDeliveryEntity> = webhook_deliveries::table
+            .filter(webhook_deliveries::webhook_id.eq(webhook_id))
+            .order(webhook_deliveries::id.desc())
+            .limit(limit as i64)
+            .load(conn)?;
+
+        deliveries.into_iter().map(|delivery| delivery.try_into()).collect()
+    }
+
+    async fn enqueue_webhook_deliveries(&self, kind: ChangeKind, payload: ResWebhookPayloadDto) -> DomainResult<usize> {
+        let conn = &mut self.db_pool.get()?;
+
+        let body = serde_json::to_string(&payload)
+            .map_err(|e| DomainError::storage(format!("Failed to serialize webhook payload: {}", e)))?;
+
+        conn.transaction(|conn| {
+            let listening: Vec<EntryWebhookEntity> = webhooks::table
+                .filter(webhooks::active.eq(true))
+                .load(conn)?;
+
+            let now = Utc::now().naive_utc();
+            let queued: Vec<NewWebhookDeliveryEntity> = listening
+                .iter()
+                .filter(|webhook| webhook.wants(kind))
+                .map(|webhook| NewWebhookDeliveryEntity {
+                    webhook_id: webhook.id,
+                    event: payload.event.clone(),
+                    payload: body.clone(),
+                    created_at: now,
+                    next_attempt_at: Some(now),
+                })
+                .collect();
+            if queued.is_empty() {
+                return Ok(0);
+            }
+
+            Ok(insert_into(webhook_deliveries::table)
+                .values(queued)
+                .execute(conn)?)
+        })
+    }
+
+    async fn get_due_webhook_deliveries(&self, now: DateTime<Utc>, limit: u32) -> DomainResult<Vec<WebhookDeliveryJob>> {
+        let conn = &mut self.db_pool.get()?;
+
+        let due: Vec<(EntryWebhookDeliveryEntity, EntryWebhookEntity)> = webhook_deliveries::table
+            .inner_join(webhooks::table)
+            .filter(webhook_deliveries::status.eq(WebhookDeliveryStatus::Pending.as_str()))
+            .filter(webhook_deliveries::next_attempt_at.le(now.naive_utc()))
+            .order(webhook_deliveries::id.asc())
+            .limit(limit as i64)
+            .load(conn)?;
+
+        Ok(due
+            .into_iter()
+            .map(|(delivery, webhook)| WebhookDeliveryJob {
+                delivery_id: delivery.id as u32,
+                webhook_id: webhook.id as u32,
+                url: webhook.url,
+                secret: webhook.secret,
+                event: delivery.event,
+                payload: delivery.payload,
+                attempts: delivery.attempts as u32,
+            })
+            .collect())
+    }
+
+    async fn record_webhook_attempt(&self, delivery_id: i32, attempt: WebhookAttemptDto) -> DomainResult<()> {
+        use crate::domain::schema::schema::webhook_deliveries::dsl::*;
+
+        let conn = &mut self.db_pool.get()?;
+
+        let attempted_at = attempt.attempted_at.naive_utc();
+        let delivered = (attempt.status == WebhookDeliveryStatus::Delivered).then_some(attempted_at);
+        let updated_rows = update(webhook_deliveries.filter(id.eq(delivery_id)))
+            .set((
+                status.eq(attempt.status.as_str()),
+                attempts.eq(attempts + 1),
+                response_status.eq(attempt.response_status.map(i32::from)),
+                last_error.eq(attempt.error),
+                last_attempt_at.eq(attempted_at),
+                next_attempt_at.eq(attempt.next_attempt_at.map(|at| at.naive_utc())),
+                delivered_at.eq(delivered),
+            ))
+            .execute(conn)?;
+
+        if updated_rows == 0 {
+            return Err(DomainError::not_found(format!("No webhook delivery found with id {}", delivery_id)));
+        }
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -3,4 +3,7 @@ pub mod configuration;
 pub mod domain;
 pub mod infrastructure;
 pub mod app;
-pub mod api_docs;
\ No newline at end of file
+pub mod api_docs;
+
+#[cfg(test)]
+mod test_support;
\ No newline at end of file
@@ -2,12 +2,12 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use mcp_todolist::{
-    api_docs::init_open_api::init_openapi, application::usecase::todo_usecase::TodolistUseCase, configuration::config, infrastructure::{
-        faring::cors::CORS, http_handler::init_handler::init_controller_setup, mcp_handler::handler::MCPHandler, sqlite::{db_connection::sqlite_con::conn, repository_impl::todolist::TodoListSqliteRepository}
+    api_docs::init_open_api::init_openapi, application::{reminder::reminder_scheduler::ReminderScheduler, usecase::todo_usecase::TodolistUseCase, webhook::webhook_dispatcher::WebhookDispatcher}, configuration::config::{self, MigrationMode}, infrastructure::{
+        faring::cors::CORS, http_handler::init_handler::init_controller_setup, mcp_handler::{handler::MCPHandler, reminders::McpLoggingSink}, notification::{http_webhook_sender::HttpWebhookSender, log_sink::LogSink, webhook_sink::WebhookSink}, sqlite::{db_connection::sqlite_con::conn, repository_impl::todolist::TodoListSqliteRepository}
     }
 };
 
-use rmcp::{transport::stdio, ServiceExt};
+use rmcp::{transport::{stdio, SseServer}, ServiceExt};
 use tokio::signal;
 use tracing_subscriber::EnvFilter;
 use utoipa_swagger_ui::SwaggerUi;
@@ -26,12 +26,62 @@ async fn main() -> Result<()> {
 
     tracing::info!("Start the application");
 
-    let db_pool = Arc::new(conn(&config.database_url)?);
+    let db_pool = Arc::new(conn(&config.database_url, config.migration_mode)?);
+
+    if config.migration_mode == MigrationMode::Only {
+        tracing::info!("Migrations applied, exiting (--migrate-only)");
+        return Ok(());
+    }
+
     let todo_repo = TodoListSqliteRepository::new(Arc::clone(&db_pool));
-    let todo_use_case = Arc::new(TodolistUseCase::new(Arc::new(todo_repo)));
+    let todo_use_case = Arc::new(TodolistUseCase::new(Arc::new(todo_repo)).with_private_webhook_targets(config.webhook_allow_private_targets));
+
+    // reminder scheduler, notifies through the log, MCP logging and an optional webhook
+    let reminder_job = match config.reminder_poll_interval {
+        Some(poll_interval) => {
+            let mut scheduler = ReminderScheduler::new(Arc::clone(&todo_use_case), poll_interval, config.reminder_lookback)
+                .with_sink(Arc::new(LogSink))
+                .with_sink(Arc::new(McpLoggingSink::new(Arc::clone(&todo_use_case))));
+            if let Some(url) = &config.reminder_webhook_url {
+                scheduler = scheduler.with_sink(Arc::new(WebhookSink::new(url.clone())?));
+                tracing::info!("Reminders are also POSTed to {}", url);
+            }
+            Some(scheduler.spawn())
+        }
+        None => {
+            tracing::info!("Reminder scheduler disabled (REMINDER_POLL_SECS=0)");
+            None
+        }
+    };
+
+    // webhook dispatcher, sends the task events queued for the registered webhooks
+    if config.webhook_allow_private_targets {
+        tracing::warn!("Webhooks may target loopback, link-local and private addresses (WEBHOOK_ALLOW_PRIVATE_TARGETS)");
+    }
+    let webhook_job = WebhookDispatcher::new(Arc::clone(&todo_use_case), Arc::new(HttpWebhookSender::new(config.webhook_allow_private_targets)?)).spawn();
+
+    // MCP over SSE, every client session gets its own MCPHandler over the shared use case
+    let sse_shutdown = if config.mcp_transport.uses_sse() {
+        let use_case = Arc::clone(&todo_use_case);
+        let shutdown = SseServer::serve(config.mcp_sse_bind)
+            .await?
+            .with_service(move || MCPHandler::new(Arc::clone(&use_case)));
+        tracing::info!("MCP SSE endpoint listening on http://{}/sse", config.mcp_sse_bind);
+        Some(shutdown)
+    } else {
+        None
+    };
 
-    // MCP service future
-    let mcp_service = MCPHandler::new(Arc::clone(&todo_use_case)).serve(stdio());
+    // MCP over stdio future, only resolves once the client disconnects
+    let stdio_use_case = Arc::clone(&todo_use_case);
+    let mcp_service = async move {
+        if !config.mcp_transport.uses_stdio() {
+            return std::future::pending::<Result<()>>().await;
+        }
+        let service = MCPHandler::new(stdio_use_case).serve(stdio()).await?;
+        service.waiting().await?;
+        Ok(())
+    };
 
     // Rocket future
     let rocket = rocket::build()
@@ -56,13 +106,24 @@ async fn main() -> Result<()> {
         _ = rocket => {
             tracing::info!("Rocket server exited");
         }
-        _ = mcp_service => {
-            tracing::info!("MCP service exited");
+        result = mcp_service => {
+            match result {
+                Ok(()) => tracing::info!("MCP stdio service exited"),
+                Err(e) => tracing::error!("MCP stdio service failed: {}", e),
+            }
         }
         _ = shutdown_signal => {
             tracing::info!("Shutdown signal received");
         }
     }
 
+    if let Some(shutdown) = sse_shutdown {
+        shutdown.cancel();
+    }
+    if let Some(job) = reminder_job {
+        job.abort();
+    }
+    webhook_job.abort();
+
     Ok(())
 }
@@ -0,0 +1,46 @@
+use std::{path::PathBuf, sync::Arc};
+
+use rocket::local::asynchronous::Client;
+
+use crate::{
+    application::usecase::todo_usecase::TodolistUseCase,
+    configuration::config::MigrationMode,
+    infrastructure::{http_handler::init_handler::init_controller_setup, sqlite::{db_connection::sqlite_con::{conn, SqlitePoolSquad}, repository_impl::todolist::TodoListSqliteRepository}},
+};
+
+
+
+/// A migrated database in a file of its own, removed when dropped.
+pub struct TestDb(PathBuf);
+
+impl TestDb {
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("mcp_todolist_{}_{}.db", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        Self(path)
+    }
+
+    pub fn pool(&self) -> Arc<SqlitePoolSquad> {
+        Arc::new(conn(self.0.to_str().expect("a UTF-8 temp path"), MigrationMode::Run).expect("a migrated test database"))
+    }
+
+    pub fn use_case(&self) -> Arc<TodolistUseCase> {
+        let repo = TodoListSqliteRepository::new(self.pool());
+        Arc::new(TodolistUseCase::new(Arc::new(repo)))
+    }
+}
+
+impl Drop for TestDb {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+
+/// The HTTP API over `use_case`, with every route and catcher mounted.
+pub async fn http_client(use_case: Arc<TodolistUseCase>) -> Client {
+    let rocket = rocket::build()
+        .manage(use_case)
+        .attach(init_controller_setup());
+    Client::tracked(rocket).await.expect("a valid rocket instance")
+}
\ No newline at end of file